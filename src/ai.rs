@@ -1,60 +1,395 @@
 use alloc::rc::Rc;
-use core::time::Duration;
+use core::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+    time::Duration,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use oorandom::Rand32;
+use oorandom::{Rand32, Rand64};
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    ai::{
+        mcts::{MctsAi, MctsOpts},
+        tablebase::TablebaseAi,
+    },
     game::{
         Action,
         Action::Ready,
-        Ai, Cell,
+        Ai, Board, Cell,
         Phase::{Beginning, Inround, Outround},
     },
+    solver::Position,
     util::time::Timer,
     ActionQueue, DefaultActionQueue, PlayerId, State,
 };
 
+pub mod book;
+pub mod external;
+pub mod mcts;
+pub mod menace;
+pub mod personality;
+pub mod replay;
+pub mod tablebase;
 mod test;
+pub mod worker;
 
 pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(700);
 
+/// Derives the seeds of the [`Ai`]s indexed by [`PlayerId`] from the `seed` of a game,
+/// such that the [`Ai`]s do not share a seed.
+pub fn player_seeds(seed: u64) -> [u64; 2] {
+    let mut rng = Rand64::new(u128::from(seed));
+    [rng.rand_u64(), rng.rand_u64()]
+}
+
+/// Returns a seed for when a user has not specified one.
+pub fn time_based_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64
+}
+
+/// The kinds of [`Ai`]s that may be chosen by a user.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AiKind {
+    Random,
+    Mcts,
+    Tablebase,
+}
+
+impl AiKind {
+    pub const NAMES: [&'static str; 3] = ["random", "mcts", "tablebase"];
+
+    pub fn create(self, seed: u64, action_queue: Rc<DefaultActionQueue>) -> Box<dyn Ai> {
+        self.create_with(seed, action_queue, MctsOpts::DEFAULT)
+    }
+
+    /// The `mcts` options are used only by [`Self::Mcts`].
+    pub fn create_with(
+        self,
+        seed: u64,
+        action_queue: Rc<DefaultActionQueue>,
+        mcts: MctsOpts,
+    ) -> Box<dyn Ai> {
+        let mut ai: Box<dyn Ai> = match self {
+            Self::Random => Box::new(RandomAi::new(seed, action_queue)),
+            Self::Mcts => Box::new(MctsAi::new(seed, action_queue, mcts.budget, mcts.playout)),
+            Self::Tablebase => Box::new(TablebaseAi::new(seed, action_queue)),
+        };
+        ai.set_tempo(self.tempo());
+        ai
+    }
+
+    fn tempo(self) -> Tempo {
+        match self {
+            Self::Random => Tempo::CASUAL,
+            Self::Mcts | Self::Tablebase => Tempo::DEFAULT,
+        }
+    }
+}
+
+impl Display for AiKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.write_str(match self {
+            Self::Random => Self::NAMES[0],
+            Self::Mcts => Self::NAMES[1],
+            Self::Tablebase => Self::NAMES[2],
+        })
+    }
+}
+
+impl FromStr for AiKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "random" => Ok(Self::Random),
+            "mcts" => Ok(Self::Mcts),
+            "tablebase" => Ok(Self::Tablebase),
+            _ => Err(format!(
+                "unknown AI kind {s:?}, expected one of {:?}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+/// How long an [`Ai`] takes to act relative to its base delay, see [`Ai::set_base_act_delay()`],
+/// depending on the position, so that it looks like it is thinking.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tempo {
+    /// The relative width of the range the delays are picked from around their means,
+    /// e.g., `0.5` for ±25%.
+    pub spread: f32,
+    /// The factor of the delay of a move when there is only one sensible move.
+    pub forced: f32,
+    /// The factor of the delay of a move when a round may be decided by it,
+    /// or by the next move of the opponent.
+    pub critical: f32,
+    /// The probability of a long pause before a move that is not forced.
+    pub pause_chance: f32,
+    /// The factor of the delay of a move with a long pause.
+    pub pause: f32,
+}
+
+impl Tempo {
+    pub const DEFAULT: Self = Self {
+        spread: 0.5,
+        forced: 0.0,
+        critical: 1.8,
+        pause_chance: 0.08,
+        pause: 3.0,
+    };
+
+    /// Does not think over critical moves much, but gets distracted often.
+    pub const CASUAL: Self = Self {
+        spread: 0.6,
+        forced: 0.0,
+        critical: 1.2,
+        pause_chance: 0.15,
+        pause: 2.5,
+    };
+
+    /// Returns the factor of the base delay of an action,
+    /// which is a move if the `criticality` of the position is known, otherwise, e.g., readying up.
+    fn factor(&self, criticality: Option<Criticality>, rng: &mut Rand32) -> f32 {
+        let mean = match criticality {
+            Some(Criticality::Forced) => return self.forced,
+            None | Some(Criticality::Normal) => 1.0,
+            Some(Criticality::Critical) => self.critical,
+        };
+        let pause = if criticality.is_some() && rng.rand_float() < self.pause_chance {
+            self.pause
+        } else {
+            1.0
+        };
+        mean * pause * (1.0 + (rng.rand_float() - 0.5) * self.spread)
+    }
+}
+
+impl Default for Tempo {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// How much thinking a move in a [`Position`] looks like it needs.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Criticality {
+    /// There is only one empty cell, or one cell that blocks the win of the opponent,
+    /// and no cell that wins.
+    Forced,
+    Normal,
+    /// There is a cell that wins, the opponent has two ways to win already,
+    /// or there is a cell that creates two ways to win for either player.
+    Critical,
+}
+
+impl Criticality {
+    fn of(position: &Position) -> Self {
+        let moves = position.moves();
+        let threats = position.threats();
+        let wins = Self::wins(position.board(), position.turn());
+        if moves.len() <= 1 || wins == 0 && threats.len() == 1 {
+            Self::Forced
+        } else if wins > 0
+            || threats.len() >= 2
+            || [position.turn(), position.opponent()]
+                .into_iter()
+                .any(|player_id| {
+                    moves.iter().any(|cell| {
+                        let mut board = position.board().clone();
+                        board.set(cell, player_id);
+                        Self::wins(&board, player_id) >= 2
+                    })
+                })
+        {
+            Self::Critical
+        } else {
+            Self::Normal
+        }
+    }
+
+    /// Returns the number of the empty cells `player_id` would win by occupying.
+    fn wins(board: &Board, player_id: PlayerId) -> usize {
+        Position::new(board.clone(), PlayerId::new(1 - player_id.idx))
+            .threats()
+            .len()
+    }
+}
+
+/// Why an [`Ai`] has made a move, which is shown to the user as commentary.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Reason {
+    WinNow,
+    /// Occupies a cell the opponent would win by occupying.
+    Block,
+    /// Creates two ways to win on the next move.
+    Fork,
+    Center,
+    Corner,
+    /// Occupies a random cell without thinking.
+    Random,
+    /// Occupies a random cell, because an external engine has failed to decide.
+    EngineFailed,
+}
+
+impl Reason {
+    /// Explains occupying the `cell` in the `position` by the first of the reasons
+    /// in the order of the variants that applies, except for [`Self::Random`], if any.
+    pub fn of(position: &Position, cell: &Cell) -> Option<Self> {
+        let last = position.board().size() - 1;
+        let next = position.play(cell);
+        if next.winner().is_some() {
+            Some(Self::WinNow)
+        } else if position.threats().contains(cell) {
+            Some(Self::Block)
+        } else if Criticality::wins(next.board(), position.turn()) >= 2 {
+            Some(Self::Fork)
+        } else if cell.x() * 2 == last && cell.y() * 2 == last {
+            Some(Self::Center)
+        } else if [0, last].contains(&cell.x()) && [0, last].contains(&cell.y()) {
+            Some(Self::Corner)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.write_str(match self {
+            Self::WinNow => "wins now",
+            Self::Block => "blocks a win",
+            Self::Fork => "creates a fork",
+            Self::Center => "takes the center",
+            Self::Corner => "takes a corner",
+            Self::Random => "a random move",
+            Self::EngineFailed => "the engine has failed, a random move",
+        })
+    }
+}
+
+/// Delays the actions of an [`Ai`], so that it does not act instantly, and readies it up.
 #[derive(Debug)]
-pub struct RandomAi {
-    rng: Rand32,
+struct Pacing {
+    /// Decides delays. It is separate from the one deciding moves,
+    /// because the moves must depend only on the seed and the game,
+    /// while the number of times delays are decided depends on timing.
+    delay_rng: Rand32,
     action_queue: Rc<DefaultActionQueue>,
     base_act_delay: Duration,
+    tempo: Tempo,
+    /// The factor of the [`Self::base_act_delay`] for the pending action, see [`Tempo::factor()`].
+    act_factor: f32,
     act_timer: Timer,
 }
 
-impl RandomAi {
-    pub fn new(seed: u64, action_queue: Rc<DefaultActionQueue>) -> Self {
+impl Pacing {
+    const DELAY_RNG_INCREMENT: u64 = 0x2545_f491_4f6c_dd1d;
+
+    fn new(seed: u64, action_queue: Rc<DefaultActionQueue>) -> Self {
         Self {
-            rng: Rand32::new(seed),
+            delay_rng: Rand32::new_inc(seed, Self::DELAY_RNG_INCREMENT),
             action_queue,
             base_act_delay: DEFAULT_BASE_DELAY,
+            tempo: Tempo::DEFAULT,
+            act_factor: 1.0,
             act_timer: Timer::new(),
         }
     }
 
+    fn player_id(&self) -> PlayerId {
+        self.action_queue.player_id()
+    }
+
     fn act_beginning_outround(&mut self, state: &State) {
         // By handling `act_timer` here as the first thing, we are making sure that
         // when AI plays vs. AI, there is no double waiting before becoming ready.
-        if self.can_act(state)
-            && state
-                .required_ready
-                .contains(&self.action_queue.player_id())
-        {
+        if self.can_act(state) && state.required_ready.contains(&self.player_id()) {
             self.action_queue.add(Ready);
         }
     }
 
-    fn act_inround(&mut self, state: &State) {
-        if state.turn() != self.action_queue.player_id() {
-            return;
+    /// Returns `true` if it is the turn of the player in the [`Inround`] phase,
+    /// and the delay has expired.
+    fn can_move(&mut self, state: &State) -> bool {
+        state.turn() == self.player_id() && self.can_act(state)
+    }
+
+    /// The delay of a move depends on the [`Criticality`] of the position.
+    fn can_act(&mut self, state: &State) -> bool {
+        let player_id = self.player_id();
+        let (base, tempo, rng, act_factor) = (
+            self.base_act_delay,
+            self.tempo,
+            &mut self.delay_rng,
+            &mut self.act_factor,
+        );
+        base.is_zero()
+            || self
+                .act_timer
+                .check_expired_then_unset_if_true_or_set_if_unset(state.clock.now(), || {
+                    let criticality = (state.phase == Inround && state.turn() == player_id)
+                        .then(|| Criticality::of(&Position::new(state.board.clone(), player_id)));
+                    *act_factor = tempo.factor(criticality, rng);
+                    base.mul_f32(*act_factor)
+                })
+    }
+
+    /// Like [`Self::can_move()`], but the delay is the `think_time` of a recorded move,
+    /// scaled as the [`DEFAULT_BASE_DELAY`] is to the [`Self::base_act_delay`].
+    fn can_replay_move(&mut self, state: &State, think_time: Duration) -> bool {
+        let (base, act_factor) = (self.base_act_delay, &mut self.act_factor);
+        state.turn() == self.action_queue.player_id()
+            && (base.is_zero()
+                || self
+                    .act_timer
+                    .check_expired_then_unset_if_true_or_set_if_unset(state.clock.now(), || {
+                        *act_factor = think_time.as_secs_f32() / DEFAULT_BASE_DELAY.as_secs_f32();
+                        base.mul_f32(*act_factor)
+                    }))
+    }
+
+    /// Rescales the delay of the pending action, if any.
+    fn set_base_act_delay(&mut self, delay: Duration) {
+        self.base_act_delay = delay;
+        self.act_timer
+            .set_duration(self.base_act_delay.mul_f32(self.act_factor));
+    }
+
+    /// Applies to the actions after the pending one, if any.
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.tempo = tempo;
+    }
+}
+
+#[derive(Debug)]
+pub struct RandomAi {
+    /// Decides moves, see [`Pacing::delay_rng`].
+    rng: Rand32,
+    pacing: Pacing,
+}
+
+impl RandomAi {
+    pub fn new(seed: u64, action_queue: Rc<DefaultActionQueue>) -> Self {
+        Self {
+            rng: Rand32::new(seed),
+            pacing: Pacing::new(seed, action_queue),
         }
-        if self.can_act(state) {
-            self.action_queue
-                .add(Action::Occupy(Self::decide_cell(&mut self.rng, state)));
+    }
+
+    fn act_inround(&mut self, state: &State) {
+        if self.pacing.can_move(state) {
+            self.pacing.action_queue.add(Action::Occupy(
+                Self::decide_cell(&mut self.rng, state),
+                Some(Reason::Random),
+            ));
         }
     }
 
@@ -75,37 +410,30 @@ impl RandomAi {
         }
         unreachable!("This method must called only if a decision is possible.")
     }
-
-    fn can_act(&mut self, state: &State) -> bool {
-        self.base_act_delay.is_zero()
-            || self
-                .act_timer
-                .check_expired_then_unset_if_true_or_set_if_unset(
-                    state.clock.as_ref().unwrap().now(),
-                    || Self::delay(self.base_act_delay, &mut self.rng),
-                )
-    }
-
-    fn delay(base: Duration, rng: &mut Rand32) -> Duration {
-        base.mul_f32(1.0 + (rng.rand_float() - 0.5) / 2.0)
-    }
 }
 
 impl Ai for RandomAi {
     fn player_id(&self) -> PlayerId {
-        self.action_queue.player_id()
+        self.pacing.player_id()
     }
 
     fn act(&mut self, state: &State) {
         match state.phase {
-            Beginning | Outround => self.act_beginning_outround(state),
+            Beginning | Outround => self.pacing.act_beginning_outround(state),
             Inround => self.act_inround(state),
         };
     }
 
     fn set_base_act_delay(&mut self, delay: Duration) {
-        self.base_act_delay = delay;
-        self.act_timer
-            .set_duration(Self::delay(self.base_act_delay, &mut self.rng));
+        self.pacing.set_base_act_delay(delay);
+    }
+
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.pacing.set_tempo(tempo);
     }
 }
+
+/// Returns a random index in a collection of `len` items.
+fn random_idx(rng: &mut Rand32, len: usize) -> usize {
+    rng.rand_range(0..u32::try_from(len).unwrap()) as usize
+}
@@ -0,0 +1,147 @@
+//! See [`BookAi`].
+
+use alloc::{rc::Rc, sync::Arc};
+use core::time::Duration;
+use std::path::{Path, PathBuf};
+
+use oorandom::Rand32;
+
+use crate::{
+    ai::{Pacing, Reason, Tempo},
+    book::Book,
+    game::{Action, ActionQueue, Ai, Cell, Phase::Inround},
+    solver::Position,
+    DefaultActionQueue, PlayerId, State,
+};
+
+mod test;
+
+/// An opening book for [`Ai`]s to consult first, see [`Self::consulted_by()`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BookOpts {
+    pub book: Arc<Book>,
+    /// Where the [`Self::book`] has been read from, which saved games refer to.
+    pub path: PathBuf,
+    /// In percent, see [`Book::pick()`].
+    pub randomness: u8,
+}
+
+impl BookOpts {
+    /// # Errors
+    ///
+    /// If the book cannot be read, described for the user.
+    pub fn read(path: &Path, randomness: u8) -> Result<Self, String> {
+        Book::read(path)
+            .map(|book| Self {
+                book: Arc::new(book),
+                path: path.to_owned(),
+                randomness,
+            })
+            .map_err(|e| format!("Failed to read {}: {e}.", path.display()))
+    }
+
+    /// Returns a [`BookAi`] consulting the book before the `ai`,
+    /// which must use the same `action_queue`.
+    pub fn consulted_by(
+        &self,
+        ai: Box<dyn Ai>,
+        seed: u64,
+        action_queue: Rc<DefaultActionQueue>,
+    ) -> Box<dyn Ai> {
+        Box::new(BookAi::new(
+            ai,
+            seed,
+            action_queue,
+            Arc::clone(&self.book),
+            f32::from(self.randomness) / 100.0,
+        ))
+    }
+}
+
+/// Makes the moves from a [`Book`] while the positions are in it,
+/// and lets another [`Ai`] act otherwise, e.g., to ready up.
+#[derive(Debug)]
+pub struct BookAi {
+    ai: Box<dyn Ai>,
+    book: Arc<Book>,
+    /// See [`Book::pick()`].
+    randomness: f32,
+    /// Picks moves from the [`Self::book`], see [`Pacing::delay_rng`].
+    rng: Rand32,
+    pacing: Pacing,
+    /// The move picked for the position, so that the move is picked once per position,
+    /// regardless of how many times [`Ai::act()`] is called before the delay expires.
+    picked: Option<(Position, Cell)>,
+}
+
+impl BookAi {
+    /// Makes the [`Self::rng`] independent of that of the [`Self::ai`] with the same seed.
+    const RNG_INCREMENT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    /// The `ai` must use the same `action_queue`.
+    pub fn new(
+        ai: Box<dyn Ai>,
+        seed: u64,
+        action_queue: Rc<DefaultActionQueue>,
+        book: Arc<Book>,
+        randomness: f32,
+    ) -> Self {
+        assert_eq!(ai.player_id(), action_queue.player_id());
+        Self {
+            ai,
+            book,
+            randomness,
+            rng: Rand32::new_inc(seed, Self::RNG_INCREMENT),
+            pacing: Pacing::new(seed, action_queue),
+            picked: None,
+        }
+    }
+
+    /// Returns the move from the [`Self::book`] for the position in the `state`, if any.
+    fn pick(&mut self, state: &State) -> Option<Cell> {
+        let position = Position::new(state.board.clone(), state.turn());
+        match &self.picked {
+            Some((picked_position, cell)) if *picked_position == position => Some(*cell),
+            _ => {
+                let cell = self.book.pick(&position, self.randomness, &mut self.rng);
+                self.picked = cell.map(|cell| (position, cell));
+                cell
+            }
+        }
+    }
+}
+
+impl Ai for BookAi {
+    fn player_id(&self) -> PlayerId {
+        self.pacing.player_id()
+    }
+
+    fn act(&mut self, state: &State) {
+        let in_book = state.phase == Inround && state.turn() == self.player_id();
+        match in_book.then(|| self.pick(state)).flatten() {
+            Some(cell) => {
+                if self.pacing.can_move(state) {
+                    let position = Position::new(state.board.clone(), state.turn());
+                    self.pacing
+                        .action_queue
+                        .add(Action::Occupy(cell, Reason::of(&position, &cell)));
+                }
+            }
+            None => self.ai.act(state),
+        }
+    }
+
+    fn set_base_act_delay(&mut self, delay: Duration) {
+        self.pacing.set_base_act_delay(delay);
+        self.ai.set_base_act_delay(delay);
+    }
+
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.pacing.set_tempo(tempo);
+        self.ai.set_tempo(tempo);
+    }
+
+    fn set_blocking(&mut self, blocking: bool) {
+        self.ai.set_blocking(blocking);
+    }
+}
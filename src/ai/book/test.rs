@@ -0,0 +1,54 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod BookOpts {
+    use alloc::sync::Arc;
+    use std::path::PathBuf;
+
+    use crate::{
+        ai::{book::BookOpts, AiKind},
+        book::Book,
+        game::{Board, PlayerId},
+        save::SavedGame,
+        sim,
+        solver::Position,
+    };
+
+    #[test]
+    fn consulted_by__makes_book_moves() {
+        let book = BookOpts {
+            book: Arc::new(
+                format!(
+                    "{:016x} b2:1",
+                    Book::hash(&Position::new(Board::default(), PlayerId::new(0)))
+                )
+                .parse::<Book>()
+                .unwrap(),
+            ),
+            path: PathBuf::new(),
+            randomness: 100,
+        };
+        let game = SavedGame::new(0, 4, [AiKind::Random; 2]);
+        let (_, world) = sim::play_world(game.world_with(|player_id, seed, action_queue| {
+            let ai = AiKind::Random.create(seed, action_queue.clone());
+            if player_id.idx == 0 {
+                book.consulted_by(ai, seed, action_queue)
+            } else {
+                ai
+            }
+        }));
+        let first_moves = world
+            .state()
+            .log
+            .iter()
+            .map(|round_log| {
+                let first = round_log.moves[0];
+                (first.player_id.idx, first.cell.to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(first_moves.len(), 4);
+        for (idx, cell) in first_moves {
+            assert!(idx == 1 || cell == "b2", "{idx}, {cell}");
+        }
+    }
+}
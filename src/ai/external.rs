@@ -0,0 +1,367 @@
+//! See [`ExternalAi`].
+
+use alloc::rc::Rc;
+use core::{
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering::Relaxed},
+    time::Duration,
+};
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Instant,
+};
+
+use oorandom::Rand32;
+
+use crate::{
+    ai::{
+        random_idx,
+        worker::{Decider, Worker},
+        Pacing, Reason, Tempo,
+    },
+    engine,
+    engine::Reply,
+    game::{
+        Action, Ai, Cell,
+        Phase::{Beginning, Inround, Outround},
+    },
+    solver::Position,
+    DefaultActionQueue, PlayerId, State,
+};
+
+mod test;
+
+/// Decides moves by running an engine process that speaks the protocol described in [`engine`].
+/// An engine that fails, e.g., crashes, times out, or replies with an illegal move,
+/// is restarted for the next move, and a random move is played instead,
+/// unless it has failed [`Engine::MAX_FAILURES`] times in a row, in which case only random
+/// moves are played.
+#[derive(Debug)]
+pub struct ExternalAi {
+    pacing: Pacing,
+    worker: Worker<Engine>,
+}
+
+impl ExternalAi {
+    pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_millis(100);
+
+    /// The engine is started when the first move is to be decided.
+    pub fn new(
+        seed: u64,
+        action_queue: Rc<DefaultActionQueue>,
+        command: EngineCommand,
+        time_limit: Duration,
+    ) -> Self {
+        Self {
+            pacing: Pacing::new(seed, action_queue),
+            worker: Worker::new(Engine::new(seed, command, time_limit)),
+        }
+    }
+
+    /// The delay starts once the move has been decided.
+    fn act_inround(&mut self, state: &State) {
+        if state.turn() != self.player_id() {
+            return;
+        }
+        let position = Position::new(state.board.clone(), state.turn());
+        if let Some((cell, reason)) = self.worker.poll(&position) {
+            if self.pacing.can_act(state) {
+                self.pacing.action_queue.add(Action::Occupy(cell, reason));
+            }
+        }
+    }
+}
+
+impl Ai for ExternalAi {
+    fn player_id(&self) -> PlayerId {
+        self.pacing.player_id()
+    }
+
+    fn act(&mut self, state: &State) {
+        match state.phase {
+            Beginning | Outround => {
+                self.worker.cancel();
+                self.pacing.act_beginning_outround(state);
+            }
+            Inround => self.act_inround(state),
+        }
+    }
+
+    fn set_base_act_delay(&mut self, delay: Duration) {
+        self.pacing.set_base_act_delay(delay);
+    }
+
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.pacing.set_tempo(tempo);
+    }
+
+    fn set_blocking(&mut self, blocking: bool) {
+        self.worker.set_blocking(blocking);
+    }
+}
+
+/// A program with its arguments, which are separated by whitespace.
+/// Like in a shell, a part of the program or an argument may be quoted with `"` or `'`,
+/// e.g., to contain whitespace.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EngineCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+impl FromStr for EngineCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        // `None` between the parts
+        let mut part = None::<String>;
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' | '\'' => {
+                    let part = part.get_or_insert_with(String::new);
+                    loop {
+                        match chars.next() {
+                            Some(quoted) if quoted == c => break,
+                            Some(quoted) => part.push(quoted),
+                            None => return Err(format!("unterminated quote {c}")),
+                        }
+                    }
+                }
+                c if c.is_whitespace() => parts.extend(part.take()),
+                c => part.get_or_insert_with(String::new).push(c),
+            }
+        }
+        parts.extend(part);
+        let mut parts = parts.into_iter();
+        let program = parts
+            .next()
+            .ok_or_else(|| "the command must not be blank".to_owned())?;
+        Ok(Self {
+            program,
+            args: parts.collect(),
+        })
+    }
+}
+
+impl Display for EngineCommand {
+    /// The parts are quoted as needed, so that the result is parsed back as is.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(&quote(&self.program))?;
+        for arg in &self.args {
+            write!(f, " {}", quote(arg))?;
+        }
+        Ok(())
+    }
+}
+
+/// Quotes the `part` of an [`EngineCommand`] if it is empty, or contains whitespace or quotes.
+fn quote(part: &str) -> String {
+    if !part.is_empty() && !part.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'') {
+        return part.to_owned();
+    }
+    // `"` cannot be quoted with `"`, so it is quoted with `'`
+    part.split('"')
+        .map(|s| format!("\"{s}\""))
+        .collect::<Vec<_>>()
+        .join("'\"'")
+}
+
+/// The part of an [`ExternalAi`] that is run by its [`Worker`].
+#[derive(Debug)]
+struct Engine {
+    command: EngineCommand,
+    time_limit: Duration,
+    /// [`None`] if the engine has not been started, or has failed.
+    process: Option<Process>,
+    /// The number of consecutive failures.
+    failures: u32,
+    /// Decides moves instead of a failed engine.
+    rng: Rand32,
+}
+
+impl Engine {
+    const MAX_FAILURES: u32 = 3;
+    /// How long the engine may be late with a reply, e.g., because of a slow start.
+    const GRACE_TIME: Duration = Duration::from_secs(1);
+
+    fn new(seed: u64, command: EngineCommand, time_limit: Duration) -> Self {
+        Self {
+            command,
+            time_limit,
+            process: None,
+            failures: 0,
+            rng: Rand32::new(seed),
+        }
+    }
+
+    /// Starts the engine if needed, and lets it decide.
+    fn try_decide_cell(
+        &mut self,
+        position: &Position,
+        cancelled: &AtomicBool,
+    ) -> core::result::Result<Cell, String> {
+        if self.process.is_none() {
+            let mut process = Process::spawn(&self.command)?;
+            process.send(&engine::Command::Init {
+                board_size: position.board().size(),
+            })?;
+            process.receive(self.time_limit + Self::GRACE_TIME, cancelled, |reply| {
+                (reply == Reply::Ready).then_some(())
+            })?;
+            self.process = Some(process);
+        }
+        let process = self.process.as_mut().expect("the engine must be started");
+        process.send(&engine::Command::Position(position.clone()))?;
+        process.send(&engine::Command::Go(self.time_limit))?;
+        let cell = process.receive(self.time_limit + Self::GRACE_TIME, cancelled, |reply| {
+            if let Reply::BestMove(cell) = reply {
+                Some(cell)
+            } else {
+                None
+            }
+        })?;
+        if position.moves().contains(&cell) {
+            Ok(cell)
+        } else {
+            Err(format!("illegal move {cell} in {position}"))
+        }
+    }
+}
+
+impl Decider for Engine {
+    /// The [`Reason`] is [`None`] if the engine has decided, as the protocol does not explain moves,
+    /// and [`Reason::EngineFailed`] if it has failed, so that the failure is shown as commentary
+    /// rather than printed over the user interface.
+    type Decision = (Cell, Option<Reason>);
+
+    fn decide_cell(&mut self, position: &Position, cancelled: &AtomicBool) -> Self::Decision {
+        if self.failures < Self::MAX_FAILURES {
+            match self.try_decide_cell(position, cancelled) {
+                Ok(cell) => {
+                    self.failures = 0;
+                    return (cell, None);
+                }
+                Err(_) if cancelled.load(Relaxed) => {
+                    // the engine is busy with a stale position
+                    self.process = None;
+                    let moves = position.moves();
+                    return (moves[random_idx(&mut self.rng, moves.len())], None);
+                }
+                Err(_) => {
+                    self.process = None;
+                    self.failures += 1;
+                }
+            }
+        }
+        let moves = position.moves();
+        (
+            moves[random_idx(&mut self.rng, moves.len())],
+            Some(Reason::EngineFailed),
+        )
+    }
+}
+
+/// A running engine, which is killed when dropped.
+#[derive(Debug)]
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    /// The lines of the standard output, read on a separate thread
+    /// so that waiting for them may time out.
+    lines: Receiver<String>,
+}
+
+impl Process {
+    /// How often a [`Decider::decide_cell()`] cancellation is checked while waiting for a reply.
+    const CANCELLATION_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+    fn spawn(command: &EngineCommand) -> core::result::Result<Self, String> {
+        let mut child = Command::new(&command.program)
+            .args(&command.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("cannot start: {e}"))?;
+        let stdin = child
+            .stdin
+            .take()
+            .expect("the standard input must be piped");
+        let stdout = child
+            .stdout
+            .take()
+            .expect("the standard output must be piped");
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            child,
+            stdin,
+            lines,
+        })
+    }
+
+    fn send(&mut self, command: &engine::Command) -> core::result::Result<(), String> {
+        writeln!(self.stdin, "{command}")
+            .and_then(|()| self.stdin.flush())
+            .map_err(|e| format!("cannot send `{command}`: {e}"))
+    }
+
+    /// Waits for a [`Reply`] for which `expected` returns [`Some`], ignoring [`Reply::Info`].
+    fn receive<T>(
+        &mut self,
+        timeout: Duration,
+        cancelled: &AtomicBool,
+        expected: impl Fn(Reply) -> Option<T>,
+    ) -> core::result::Result<T, String> {
+        let start = Instant::now();
+        loop {
+            if cancelled.load(Relaxed) {
+                return Err("cancelled".to_owned());
+            }
+            let left = timeout.saturating_sub(start.elapsed());
+            if left.is_zero() {
+                return Err(format!("no reply within {timeout:?}"));
+            }
+            match self
+                .lines
+                .recv_timeout(left.min(Self::CANCELLATION_CHECK_INTERVAL))
+            {
+                Ok(line) => match line.parse::<Reply>()? {
+                    Reply::Info(_) => {}
+                    reply => {
+                        return expected(reply.clone())
+                            .ok_or_else(|| format!("unexpected `{reply}`"))
+                    }
+                },
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(match self.child.wait() {
+                        Ok(status) => format!("exited with {status}"),
+                        Err(e) => format!("exited: {e}"),
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        // errors mean the engine has already exited
+        drop(self.send(&engine::Command::Quit));
+        drop(self.child.kill());
+        drop(self.child.wait());
+    }
+}
@@ -0,0 +1,122 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+#[cfg(unix)]
+mod Engine {
+    use core::{sync::atomic::AtomicBool, time::Duration};
+    use std::fs;
+
+    use ntest::timeout;
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        ai::{external::Engine, worker::Decider, Reason},
+        solver::Position,
+        test::{temp_dir, TempDir},
+    };
+
+    /// Replies with `a1` to each `go`.
+    const A1: &str = "while read -r command args; do
+        case $command in
+            init) echo ready ;;
+            go) echo 'info thinking'; echo 'bestmove a1' ;;
+            quit) exit 0 ;;
+        esac
+    done";
+    const CRASHING: &str = "read -r command; exit 1";
+    const SILENT: &str = "while read -r command; do :; done";
+
+    /// Writes the `script` to a file in a directory with a space in its name,
+    /// and returns an [`Engine`] running it, with the directory, which must outlive the engine.
+    fn engine(name: &str, script: &str) -> (Engine, TempDir) {
+        let dir = temp_dir(&format!("engine {name}"));
+        fs::create_dir_all(&*dir).unwrap();
+        let path = dir.join("engine.sh");
+        fs::write(&path, script).unwrap();
+        let engine = Engine::new(
+            0,
+            format!("sh '{}'", path.display()).parse().unwrap(),
+            Duration::from_millis(10),
+        );
+        (engine, dir)
+    }
+
+    fn decide(engine: &mut Engine, position: &str) -> String {
+        let (cell, reason) = engine.decide_cell(
+            &position.parse::<Position>().unwrap(),
+            &AtomicBool::new(false),
+        );
+        assert_eq!(reason, None);
+        cell.to_string()
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn decide_cell() {
+        let (mut engine, _dir) = engine("a1", A1);
+        assert_eq!(decide(&mut engine, ".../.../... X"), "a1");
+        assert_eq!(decide(&mut engine, ".../.X./... O"), "a1");
+        assert!(engine.process.is_some());
+        assert_eq!(engine.failures, 0);
+    }
+
+    #[test_case("illegal", A1)]
+    #[test_case("crashing", CRASHING)]
+    #[test_case("silent", SILENT)]
+    #[timeout(3_000)]
+    fn decide_cell__failed(name: &str, script: &str) {
+        let (mut engine, _dir) = engine(name, script);
+        let position = "X../.O./... X".parse::<Position>().unwrap();
+        let (cell, reason) = engine.decide_cell(&position, &AtomicBool::new(false));
+        assert!(position.moves().contains(&cell));
+        assert_eq!(reason, Some(Reason::EngineFailed));
+        assert!(engine.process.is_none());
+        assert_eq!(engine.failures, 1);
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn decide_cell__max_failures() {
+        let (mut engine, _dir) = engine("max-failures", CRASHING);
+        let position = ".../.../... X".parse::<Position>().unwrap();
+        for _ in 0..=Engine::MAX_FAILURES {
+            engine.decide_cell(&position, &AtomicBool::new(false));
+        }
+        assert_eq!(engine.failures, Engine::MAX_FAILURES);
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn decide_cell__cancelled() {
+        let (mut engine, _dir) = engine("cancelled", SILENT);
+        let position = ".../.../... X".parse::<Position>().unwrap();
+        let (cell, _) = engine.decide_cell(&position, &AtomicBool::new(true));
+        assert!(position.moves().contains(&cell));
+        assert_eq!(engine.failures, 0);
+    }
+}
+
+mod EngineCommand {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::ai::external::EngineCommand;
+
+    #[test_case(" engine  --seed 1 ", "engine --seed 1")]
+    #[test_case("engine", "engine")]
+    #[test_case("'my engine' --name \"a b\"", "\"my engine\" --name \"a b\"")]
+    #[test_case("/opt/my\" \"engine/run", "\"/opt/my engine/run\"")]
+    #[test_case("engine '' 'say \"hi\"'", "engine \"\" \"say \"'\"'\"hi\"'\"'\"\"")]
+    fn from_str(command: &str, expected: &str) {
+        let parsed = command.parse::<EngineCommand>().unwrap();
+        assert_eq!(parsed.to_string(), expected);
+        assert_eq!(expected.parse::<EngineCommand>().unwrap(), parsed);
+    }
+
+    #[test_case(" ")]
+    #[test_case("engine 'a b")]
+    fn from_str__Err(command: &str) {
+        assert!(command.parse::<EngineCommand>().is_err());
+    }
+}
@@ -0,0 +1,374 @@
+//! See [`MctsAi`].
+
+use alloc::rc::Rc;
+use core::{
+    f64::consts::SQRT_2,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering::Relaxed},
+    time::Duration,
+};
+use std::time::Instant;
+
+use oorandom::Rand32;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::{
+        random_idx,
+        worker::{Decider, Worker},
+        Pacing, Reason, Tempo,
+    },
+    game::{
+        Action, Ai, Cell,
+        Phase::{Beginning, Inround, Outround},
+    },
+    solver::Position,
+    DefaultActionQueue, PlayerId, State,
+};
+
+mod test;
+
+/// How much an [`MctsAi`] searches before each move, unless it is cancelled.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Budget {
+    /// The number of playouts. The moves depend only on the seed and the game.
+    Iterations(u32),
+    /// The time to search for. The moves also depend on the speed of the machine.
+    Time(Duration),
+}
+
+/// How an [`MctsAi`] plays out a position to estimate its value.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Playout {
+    /// Occupies random cells.
+    Random,
+    /// Occupies a cell that wins, if any, otherwise a cell that blocks the win of the opponent,
+    /// if any, otherwise a random cell.
+    Heuristic,
+}
+
+impl Playout {
+    pub const NAMES: [&'static str; 2] = ["heuristic", "random"];
+}
+
+impl FromStr for Playout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "heuristic" => Ok(Self::Heuristic),
+            "random" => Ok(Self::Random),
+            _ => Err(format!(
+                "unknown playout {s:?}, expected one of {:?}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+/// How an [`MctsAi`] created for an [`AiKind::Mcts`](crate::ai::AiKind::Mcts) searches.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MctsOpts {
+    pub budget: Budget,
+    pub playout: Playout,
+}
+
+impl MctsOpts {
+    pub const DEFAULT_ITERATIONS: u32 = 1_000;
+    pub const DEFAULT: Self = Self {
+        budget: Budget::Iterations(Self::DEFAULT_ITERATIONS),
+        playout: Playout::Heuristic,
+    };
+}
+
+/// Decides moves via the [Monte Carlo tree search](https://en.wikipedia.org/wiki/Monte_Carlo_tree_search)
+/// with the UCT selection on a worker thread, and reuses the search tree between the moves of a round.
+#[derive(Debug)]
+pub struct MctsAi {
+    pacing: Pacing,
+    worker: Worker<Search>,
+}
+
+impl MctsAi {
+    pub fn new(
+        seed: u64,
+        action_queue: Rc<DefaultActionQueue>,
+        budget: Budget,
+        playout: Playout,
+    ) -> Self {
+        Self {
+            pacing: Pacing::new(seed, action_queue),
+            worker: Worker::new(Search::new(seed, budget, playout)),
+        }
+    }
+
+    /// The delay starts once the move has been decided.
+    fn act_inround(&mut self, state: &State) {
+        if state.turn() != self.player_id() {
+            return;
+        }
+        let position = Position::new(state.board.clone(), state.turn());
+        if let Some(cell) = self.worker.poll(&position) {
+            if self.pacing.can_act(state) {
+                self.pacing
+                    .action_queue
+                    .add(Action::Occupy(cell, Reason::of(&position, &cell)));
+            }
+        }
+    }
+}
+
+impl Ai for MctsAi {
+    fn player_id(&self) -> PlayerId {
+        self.pacing.player_id()
+    }
+
+    fn act(&mut self, state: &State) {
+        match state.phase {
+            Beginning | Outround => {
+                // e.g., the opponent has surrendered while the move was being decided
+                self.worker.cancel();
+                self.pacing.act_beginning_outround(state);
+            }
+            Inround => self.act_inround(state),
+        }
+    }
+
+    fn set_base_act_delay(&mut self, delay: Duration) {
+        self.pacing.set_base_act_delay(delay);
+    }
+
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.pacing.set_tempo(tempo);
+    }
+
+    fn set_blocking(&mut self, blocking: bool) {
+        self.worker.set_blocking(blocking);
+    }
+}
+
+/// The part of an [`MctsAi`] that is run by its [`Worker`].
+#[derive(Debug)]
+pub struct Search {
+    /// Decides moves, see [`Pacing::delay_rng`].
+    rng: Rand32,
+    budget: Budget,
+    playout: Playout,
+    /// The tree searched for the latest move, re-rooted at that move.
+    tree: Option<Tree>,
+}
+
+impl Search {
+    pub fn new(seed: u64, budget: Budget, playout: Playout) -> Self {
+        Self {
+            rng: Rand32::new(seed),
+            budget,
+            playout,
+            tree: None,
+        }
+    }
+
+    pub fn set_budget(&mut self, budget: Budget) {
+        self.budget = budget;
+    }
+}
+
+impl Decider for Search {
+    type Decision = Cell;
+
+    fn decide_cell(&mut self, position: &Position, cancelled: &AtomicBool) -> Cell {
+        let mut tree = self
+            .tree
+            .take()
+            .and_then(|tree| tree.reused(position))
+            .unwrap_or_else(|| Tree::new(position.clone()));
+        let start = Instant::now();
+        let mut iterations = 0;
+        // at least one iteration is needed to decide
+        while iterations == 0
+            || !cancelled.load(Relaxed)
+                && match self.budget {
+                    Budget::Iterations(max) => iterations < max,
+                    Budget::Time(max) => start.elapsed() < max,
+                }
+        {
+            tree.iterate(&mut self.rng, self.playout);
+            iterations += 1;
+        }
+        let best = tree.best_child();
+        let cell = tree.nodes[best]
+            .cell
+            .expect("a child must have been reached by a move");
+        self.tree = Some(tree.subtree(best));
+        cell
+    }
+}
+
+/// The nodes are stored in a [`Vec`] and refer to each other by their indices.
+#[derive(Debug, Clone)]
+struct Tree {
+    /// The root is the first one.
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    /// The exploration parameter of UCT, which is theoretically optimal for rewards in `[0, 1]`.
+    const EXPLORATION: f64 = SQRT_2;
+
+    fn new(position: Position) -> Self {
+        Self {
+            nodes: vec![Node::new(position, None, None)],
+        }
+    }
+
+    /// Selects a node, expands it, plays out the new node, and backs up the result.
+    fn iterate(&mut self, rng: &mut Rand32, playout: Playout) {
+        let mut idx = 0;
+        while self.nodes[idx].untried.is_empty() && !self.nodes[idx].children.is_empty() {
+            idx = self.select_child(idx);
+        }
+        if !self.nodes[idx].untried.is_empty() {
+            let untried = &mut self.nodes[idx].untried;
+            let cell = untried.swap_remove(random_idx(rng, untried.len()));
+            let position = self.nodes[idx].position.play(&cell);
+            let child = self.nodes.len();
+            self.nodes.push(Node::new(position, Some(cell), Some(idx)));
+            self.nodes[idx].children.push(child);
+            idx = child;
+        }
+        let winner = play_out(self.nodes[idx].position.clone(), rng, playout);
+        let mut next = Some(idx);
+        while let Some(idx) = next {
+            let node = &mut self.nodes[idx];
+            node.visits += 1;
+            // the player who made the move leading to the node
+            let mover = node.position.opponent();
+            node.reward += match winner {
+                Some(winner) if winner == mover => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+            next = node.parent;
+        }
+    }
+
+    /// Returns the child with the highest UCT value.
+    fn select_child(&self, idx: usize) -> usize {
+        let ln_visits = f64::from(self.nodes[idx].visits).ln();
+        let uct = |child: usize| {
+            let node = &self.nodes[child];
+            let visits = f64::from(node.visits);
+            node.reward / visits + Self::EXPLORATION * (ln_visits / visits).sqrt()
+        };
+        self.nodes[idx]
+            .children
+            .iter()
+            .copied()
+            .max_by(|&a, &b| uct(a).total_cmp(&uct(b)))
+            .expect("there must be children")
+    }
+
+    /// Returns the most visited child of the root.
+    fn best_child(&self) -> usize {
+        self.nodes[0]
+            .children
+            .iter()
+            .copied()
+            .rev()
+            .max_by_key(|&child| self.nodes[child].visits)
+            .expect("there must be children")
+    }
+
+    /// Returns the tree rooted at the node `idx`.
+    fn subtree(&self, idx: usize) -> Self {
+        let mut tree = Self { nodes: Vec::new() };
+        // the indices of the nodes in `self` and of their parents in `tree`
+        let mut stack = vec![(idx, None)];
+        while let Some((idx, parent)) = stack.pop() {
+            let new_idx = tree.nodes.len();
+            let node = &self.nodes[idx];
+            tree.nodes.push(Node {
+                parent,
+                children: Vec::with_capacity(node.children.len()),
+                ..node.clone()
+            });
+            if let Some(parent) = parent {
+                tree.nodes[parent].children.push(new_idx);
+            }
+            stack.extend(
+                node.children
+                    .iter()
+                    .rev()
+                    .map(|&child| (child, Some(new_idx))),
+            );
+        }
+        tree
+    }
+
+    /// Returns the tree rooted at the `position`, if it is the root,
+    /// or one of its children, e.g., when the opponent has moved since the tree was searched.
+    fn reused(self, position: &Position) -> Option<Self> {
+        if self.nodes[0].position == *position {
+            return Some(self);
+        }
+        self.nodes[0]
+            .children
+            .iter()
+            .find(|&&child| self.nodes[child].position == *position)
+            .map(|&child| self.subtree(child))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    position: Position,
+    /// The move leading to the node from its parent, [`None`] for the root.
+    cell: Option<Cell>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// The moves that do not lead to any of the children yet.
+    untried: Vec<Cell>,
+    visits: u32,
+    /// The sum of the rewards of the playouts through the node for the player
+    /// who made the move leading to it: 1 for a win, 0.5 for a draw, 0 for a loss.
+    reward: f64,
+}
+
+impl Node {
+    fn new(position: Position, cell: Option<Cell>, parent: Option<usize>) -> Self {
+        Self {
+            untried: position.moves(),
+            position,
+            cell,
+            parent,
+            children: Vec::new(),
+            visits: 0,
+            reward: 0.0,
+        }
+    }
+}
+
+/// Plays until the `position` is over, and returns the winner, if any.
+fn play_out(mut position: Position, rng: &mut Rand32, playout: Playout) -> Option<PlayerId> {
+    loop {
+        if let Some((winner, _)) = position.winner() {
+            return Some(winner);
+        }
+        let moves = position.moves();
+        if moves.is_empty() {
+            return None;
+        }
+        let cell = match playout {
+            Playout::Random => None,
+            Playout::Heuristic => moves
+                .iter()
+                .find(|&cell| position.play(cell).winner().is_some())
+                .copied()
+                .or_else(|| position.threats().first().copied()),
+        }
+        .unwrap_or_else(|| moves[random_idx(rng, moves.len())]);
+        position = position.play(&cell);
+    }
+}
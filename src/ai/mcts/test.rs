@@ -0,0 +1,191 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod MctsAi {
+    use alloc::rc::Rc;
+    use core::time::Duration;
+    use std::{thread, time::Instant};
+
+    use ntest::timeout;
+
+    use crate::{
+        ai::{
+            mcts::{Budget, MctsAi, Playout},
+            AiKind,
+        },
+        game::Ai,
+        save::SavedGame,
+        sim, DefaultActionQueue, Local, LocalPlayerType, Logic, Player, PlayerId, State, World,
+    };
+
+    #[test]
+    #[timeout(1_000)]
+    fn act__does_not_block() {
+        let players = [0, 1].map(|idx| Player::new(PlayerId::new(idx), Local(LocalPlayerType::Ai)));
+        let queues = [0, 1].map(|idx| Rc::new(DefaultActionQueue::new(PlayerId::new(idx))));
+        let ais = queues.clone().map(|queue| {
+            let mut ai = MctsAi::new(
+                0,
+                queue,
+                Budget::Time(Duration::from_millis(100)),
+                Playout::Random,
+            );
+            ai.set_base_act_delay(Duration::ZERO);
+            Box::new(ai) as Box<dyn Ai>
+        });
+        let mut world = World::new(
+            State::new(players, State::DEFAULT_ROUNDS),
+            Logic::new(queues),
+            ais.into(),
+        );
+        // readying up
+        world.advance();
+        let start = Instant::now();
+        world.advance();
+        assert!(start.elapsed() < Duration::from_millis(50));
+        assert_eq!(world.state().step, 0);
+        while world.state().step == 0 {
+            thread::sleep(Duration::from_millis(10));
+            world.advance();
+        }
+    }
+
+    #[test]
+    fn never_loses_against_random() {
+        for seed in 0..2 {
+            for ais in [
+                [AiKind::Mcts, AiKind::Random],
+                [AiKind::Random, AiKind::Mcts],
+            ] {
+                let mcts_id = PlayerId::new(usize::from(ais[0] == AiKind::Random));
+                let outcomes = sim::play_game(&SavedGame::new(seed, 3, ais), None);
+                assert!(
+                    outcomes
+                        .iter()
+                        .all(|outcome| outcome.winner.map_or(true, |winner| winner == mcts_id)),
+                    "{seed}, {ais:?}, {outcomes:?}"
+                );
+            }
+        }
+    }
+}
+
+mod Search {
+    use core::{sync::atomic::AtomicBool, time::Duration};
+
+    use ntest::timeout;
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        ai::{
+            mcts::{Budget, MctsOpts, Playout, Search},
+            worker::Decider,
+        },
+        game::Cell,
+        solver::Position,
+    };
+
+    fn decide(search: &mut Search, position: &str) -> Cell {
+        search.decide_cell(&position.parse().unwrap(), &AtomicBool::new(false))
+    }
+
+    #[test_case("OO./XX./X.. O", Playout::Random; "win")]
+    #[test_case("OO./XX./X.. O", Playout::Heuristic; "win heuristic")]
+    #[test_case("OO./X../..X X", Playout::Random; "block")]
+    #[test_case("OO./X../..X X", Playout::Heuristic; "block heuristic")]
+    fn decide_cell(position: &str, playout: Playout) {
+        let cell = decide(
+            &mut Search::new(0, MctsOpts::DEFAULT.budget, playout),
+            position,
+        );
+        assert_eq!(cell, "c1".parse::<Cell>().unwrap());
+    }
+
+    #[test]
+    fn decide_cell__deterministic() {
+        let search = || Search::new(7, Budget::Iterations(100), Playout::Random);
+        assert_eq!(
+            decide(&mut search(), "X../.../... O"),
+            decide(&mut search(), "X../.../... O")
+        );
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn decide_cell__time_budget() {
+        let position = "X../.../... O".parse::<Position>().unwrap();
+        let cell = Search::new(
+            0,
+            Budget::Time(Duration::from_millis(10)),
+            Playout::Heuristic,
+        )
+        .decide_cell(&position, &AtomicBool::new(false));
+        assert!(position.moves().contains(&cell));
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn decide_cell__cancelled() {
+        let position = "X../.../... O".parse::<Position>().unwrap();
+        let mut search = Search::new(0, Budget::Time(Duration::from_secs(3_600)), Playout::Random);
+        let cell = search.decide_cell(&position, &AtomicBool::new(true));
+        assert!(position.moves().contains(&cell));
+        // a single iteration
+        assert_eq!(search.tree.unwrap().nodes[0].visits, 1);
+    }
+
+    #[test]
+    fn decide_cell__reuses_tree() {
+        let mut search = Search::new(0, Budget::Iterations(200), Playout::Heuristic);
+        let position = ".../.../... X".parse::<Position>().unwrap();
+        let position = position.play(&search.decide_cell(&position, &AtomicBool::new(false)));
+        let tree = search.tree.clone().unwrap();
+        assert_eq!(tree.nodes[0].position, position);
+        // the opponent replies with the move searched the most
+        let reply = &tree.nodes[tree.best_child()];
+        let position = position.play(&reply.cell.unwrap());
+        let reused = tree.clone().reused(&position).unwrap();
+        assert_eq!(reused.nodes[0].visits, reply.visits);
+        assert!(reused.nodes[0].visits > 0);
+        assert!(tree.reused(&".../.../... X".parse().unwrap()).is_none());
+    }
+}
+
+mod Tree {
+    use oorandom::Rand32;
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::{
+        ai::mcts::{Playout, Tree},
+        solver::Position,
+    };
+
+    #[test]
+    fn subtree() {
+        let mut tree = Tree::new(".../.../... X".parse::<Position>().unwrap());
+        let mut rng = Rand32::new(0);
+        for _ in 0..500 {
+            tree.iterate(&mut rng, Playout::Random);
+        }
+        let best = tree.best_child();
+        let subtree = tree.subtree(best);
+        assert_eq!(subtree.nodes[0].visits, tree.nodes[best].visits);
+        assert_eq!(subtree.nodes[0].parent, None);
+        // the playout of a node visits only the node itself, not its children
+        assert_eq!(
+            subtree.nodes[0]
+                .children
+                .iter()
+                .map(|&child| subtree.nodes[child].visits)
+                .sum::<u32>()
+                + 1,
+            subtree.nodes[0].visits
+        );
+        for (idx, node) in subtree.nodes.iter().enumerate().skip(1) {
+            let parent = &subtree.nodes[node.parent.unwrap()];
+            assert!(parent.children.contains(&idx));
+            assert_eq!(node.position, parent.position.play(&node.cell.unwrap()));
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! See [`MenaceAi`].
+
+use alloc::{collections::BTreeMap, rc::Rc};
+use core::{cell::RefCell, time::Duration};
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use oorandom::Rand32;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::{Pacing, Tempo},
+    game::{
+        Action, Ai, Board, Cell,
+        Phase::{Beginning, Inround, Outround},
+    },
+    profile::RoundResult,
+    solver::Position,
+    util::dirs,
+    DefaultActionQueue, PlayerId, State,
+};
+
+mod test;
+
+/// Plays as [MENACE](https://en.wikipedia.org/wiki/Matchbox_Educable_Noughts_and_Crosses_Engine),
+/// which draws a random bead from the matchbox of the position, and occupies the cell
+/// the bead stands for. It does not learn while playing, see [`Menace::learn()`].
+#[derive(Debug)]
+pub struct MenaceAi {
+    /// Decides moves, see [`Pacing::delay_rng`].
+    rng: Rand32,
+    pacing: Pacing,
+    /// Shared by both [`MenaceAi`]s when it plays against itself.
+    menace: Rc<RefCell<Menace>>,
+}
+
+impl MenaceAi {
+    pub fn new(
+        seed: u64,
+        action_queue: Rc<DefaultActionQueue>,
+        menace: Rc<RefCell<Menace>>,
+    ) -> Self {
+        Self {
+            rng: Rand32::new(seed),
+            pacing: Pacing::new(seed, action_queue),
+            menace,
+        }
+    }
+
+    fn act_inround(&mut self, state: &State) {
+        if self.pacing.can_move(state) {
+            let position = Position::new(state.board.clone(), state.turn());
+            let cell = self.menace.borrow_mut().draw(&position, &mut self.rng);
+            self.pacing.action_queue.add(Action::Occupy(cell, None));
+        }
+    }
+}
+
+impl Ai for MenaceAi {
+    fn player_id(&self) -> PlayerId {
+        self.pacing.player_id()
+    }
+
+    fn act(&mut self, state: &State) {
+        match state.phase {
+            Beginning | Outround => self.pacing.act_beginning_outround(state),
+            Inround => self.act_inround(state),
+        }
+    }
+
+    fn set_base_act_delay(&mut self, delay: Duration) {
+        self.pacing.set_base_act_delay(delay);
+    }
+
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.pacing.set_tempo(tempo);
+    }
+}
+
+/// The matchboxes of [`MenaceAi`] after a number of epochs of training.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Menace {
+    pub epoch: u32,
+    /// The numbers of beads for each cell, row by row, by the positions.
+    /// A matchbox is added when its position is encountered for the first time.
+    matchboxes: BTreeMap<String, Vec<u32>>,
+}
+
+impl Menace {
+    const DIR: &'static str = "menace";
+    const EXTENSION: &'static str = "json";
+    /// The number of beads added for each move of a won round.
+    const WIN_BEADS: i32 = 3;
+    const DRAW_BEADS: i32 = 1;
+    const LOSS_BEADS: i32 = -1;
+
+    /// Returns the directory with the [`Menace`]s of all epochs inside [`dirs::data_dir()`].
+    pub fn dir(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::data_dir(env).map(|dir| dir.join(Self::DIR))
+    }
+
+    /// Returns the path of the [`Menace`] of the `epoch` in `dir`.
+    pub fn path(dir: &Path, epoch: u32) -> PathBuf {
+        dir.join(format!("epoch-{epoch}.{}", Self::EXTENSION))
+    }
+
+    /// Returns the epochs of the [`Menace`]s in `dir` in ascending order.
+    /// If `dir` does not exist, returns nothing.
+    pub fn epochs(dir: &Path) -> io::Result<Vec<u32>> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut epochs = Vec::new();
+        for entry in entries {
+            let epoch = entry?
+                .path()
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("epoch-"))
+                .and_then(|name| name.strip_suffix(&format!(".{}", Self::EXTENSION)))
+                .and_then(|epoch| epoch.parse::<u32>().ok());
+            epochs.extend(epoch);
+        }
+        epochs.sort_unstable();
+        Ok(epochs)
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Creates the parent directory of `path` if needed.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Draws a random bead from the matchbox of the `position`, which must not be over.
+    pub fn draw(&mut self, position: &Position, rng: &mut Rand32) -> Cell {
+        let beads = self.matchbox(position);
+        let mut bead = rng.rand_range(0..beads.iter().sum());
+        let idx = beads
+            .iter()
+            .position(|&count| {
+                let found = bead < count;
+                bead = bead.saturating_sub(count);
+                found
+            })
+            .expect("a bead must have been drawn");
+        position
+            .board()
+            .cells()
+            .nth(idx)
+            .expect("each bead must stand for a cell")
+    }
+
+    /// Reinforces the moves the `player_id` made in the finished rounds of the game:
+    /// [`Self::WIN_BEADS`] are added for each move of a won round, and so on.
+    pub fn learn(&mut self, game_state: &State, player_id: PlayerId) {
+        for round_log in &game_state.log {
+            let (Some(outcome), Some(first)) = (round_log.outcome, round_log.moves.first()) else {
+                continue;
+            };
+            let change = match RoundResult::new(outcome, player_id) {
+                RoundResult::Win => Self::WIN_BEADS,
+                RoundResult::Draw => Self::DRAW_BEADS,
+                RoundResult::Loss => Self::LOSS_BEADS,
+            };
+            let mut position = Position::new(Board::default(), first.player_id);
+            for mv in &round_log.moves {
+                if mv.player_id == player_id {
+                    let idx = cell_idx(&position, &mv.cell);
+                    let count = &mut self.matchbox(&position)[idx];
+                    // the last bead of a move is never removed, so that a matchbox never runs out of beads
+                    *count = count.saturating_add_signed(change).max(1);
+                }
+                position = position.play(&mv.cell);
+            }
+        }
+    }
+
+    /// Returns the numbers of beads, adding the matchbox if needed.
+    /// A new matchbox has fewer beads for later moves, as in the original MENACE,
+    /// so that it learns them faster.
+    fn matchbox(&mut self, position: &Position) -> &mut Vec<u32> {
+        self.matchboxes
+            .entry(position.to_string())
+            .or_insert_with(|| {
+                let empty = position.moves();
+                let marks = position.board().size().pow(2) - empty.len();
+                let initial = u32::try_from(4_usize.saturating_sub(marks / 2).max(1)).unwrap();
+                position
+                    .board()
+                    .cells()
+                    .map(|cell| if empty.contains(&cell) { initial } else { 0 })
+                    .collect()
+            })
+    }
+}
+
+fn cell_idx(position: &Position, cell: &Cell) -> usize {
+    position.board().size() * cell.x() + cell.y()
+}
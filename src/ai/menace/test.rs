@@ -0,0 +1,88 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Menace {
+    use core::time::Duration;
+    use std::fs;
+
+    use oorandom::Rand32;
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        ai::menace::Menace,
+        game::{Cell, Move, PlayerId},
+        solver::Position,
+        test::{state, temp_dir},
+        State,
+    };
+
+    /// Creates a [`State`] with a round won by X in the first row.
+    fn won_by_x() -> State {
+        let mut state = state(&["W0"]);
+        state.log[0].moves = ["a1", "a2", "b1", "b2", "c1"]
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| Move {
+                player_id: PlayerId::new(idx % 2),
+                cell: cell.parse::<Cell>().unwrap(),
+                think_time: Duration::ZERO,
+                round_time: Duration::ZERO,
+                reason: None,
+            })
+            .collect();
+        state
+    }
+
+    fn beads(menace: &Menace, position: &str) -> Vec<u32> {
+        menace.matchboxes[&position.parse::<Position>().unwrap().to_string()].clone()
+    }
+
+    #[test_case(".../.../... X")]
+    #[test_case("X../.O./... X")]
+    #[test_case("XOX/OXO/O.. X")]
+    fn draw__occupies_empty_cell(position: &str) {
+        let position = position.parse::<Position>().unwrap();
+        let mut menace = Menace::default();
+        let mut rng = Rand32::new(0);
+        for _ in 0..100 {
+            assert!(position.moves().contains(&menace.draw(&position, &mut rng)));
+        }
+    }
+
+    #[test]
+    fn learn__reinforces_moves_of_won_round_and_punishes_those_of_lost_one() {
+        let mut menace = Menace::default();
+        let state = won_by_x();
+        menace.learn(&state, PlayerId::new(0));
+        menace.learn(&state, PlayerId::new(1));
+        assert_eq!(beads(&menace, ".../.../... X"), [7, 4, 4, 4, 4, 4, 4, 4, 4]);
+        assert_eq!(beads(&menace, "X../.../... O"), [0, 4, 4, 3, 4, 4, 4, 4, 4]);
+        assert_eq!(beads(&menace, "X../O../... X"), [0, 6, 3, 0, 3, 3, 3, 3, 3]);
+    }
+
+    #[test]
+    fn learn__keeps_last_bead() {
+        let mut menace = Menace::default();
+        let state = won_by_x();
+        for _ in 0..10 {
+            menace.learn(&state, PlayerId::new(1));
+        }
+        assert_eq!(beads(&menace, "X../.../... O"), [0, 4, 4, 1, 4, 4, 4, 4, 4]);
+    }
+
+    #[test]
+    fn write_read_epochs() {
+        let dir = temp_dir("menace");
+        assert_eq!(Menace::epochs(&dir).unwrap(), [0; 0]);
+        let mut menace = Menace::default();
+        menace.learn(&won_by_x(), PlayerId::new(0));
+        for epoch in [10, 2] {
+            menace.epoch = epoch;
+            menace.write(&Menace::path(&dir, epoch)).unwrap();
+        }
+        fs::write(dir.join("other.json"), "").unwrap();
+        assert_eq!(Menace::epochs(&dir).unwrap(), [2, 10]);
+        assert_eq!(Menace::read(&Menace::path(&dir, 2)).unwrap(), menace);
+    }
+}
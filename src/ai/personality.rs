@@ -0,0 +1,243 @@
+//! See [`PersonalityAi`].
+
+use alloc::rc::Rc;
+use core::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+use oorandom::Rand32;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::{random_idx, Pacing, Reason, Tempo},
+    game::{
+        Action, Ai, Board, Cell, Line,
+        Phase::{Beginning, Inround, Outround},
+    },
+    solver::{tablebase, Position},
+    DefaultActionQueue, PlayerId, State,
+};
+
+mod test;
+
+/// The named sets of [`Traits`] that may be chosen by a user.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Personality {
+    Aggressive,
+    Defensive,
+    RandomIsh,
+    Careless,
+}
+
+impl Personality {
+    pub const ALL: [Self; 4] = [
+        Self::Aggressive,
+        Self::Defensive,
+        Self::RandomIsh,
+        Self::Careless,
+    ];
+
+    pub fn traits(self) -> Traits {
+        match self {
+            Self::Aggressive => Traits {
+                style: Style::Aggressive,
+                randomness: 0.1,
+                missed_blocks: 0.2,
+                tempo: Tempo {
+                    critical: 1.3,
+                    pause_chance: 0.04,
+                    ..Tempo::DEFAULT
+                },
+            },
+            Self::Defensive => Traits {
+                style: Style::Defensive,
+                randomness: 0.1,
+                missed_blocks: 0.0,
+                tempo: Tempo {
+                    critical: 2.2,
+                    pause_chance: 0.12,
+                    ..Tempo::DEFAULT
+                },
+            },
+            Self::RandomIsh => Traits {
+                style: Style::Balanced,
+                randomness: 0.6,
+                missed_blocks: 0.3,
+                tempo: Tempo::CASUAL,
+            },
+            Self::Careless => Traits {
+                style: Style::Balanced,
+                randomness: 0.0,
+                missed_blocks: 0.3,
+                tempo: Tempo::DEFAULT,
+            },
+        }
+    }
+
+    pub fn create(self, seed: u64, action_queue: Rc<DefaultActionQueue>) -> Box<dyn Ai> {
+        Box::new(PersonalityAi::new(seed, action_queue, self.traits()))
+    }
+}
+
+impl Display for Personality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Aggressive => "aggressive",
+            Self::Defensive => "defensive",
+            Self::RandomIsh => "random-ish",
+            Self::Careless => "careless",
+        })
+    }
+}
+
+/// How a [`PersonalityAi`] deviates from perfect play, and how long it thinks.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Traits {
+    pub style: Style,
+    /// The probability of occupying a random cell instead of deciding.
+    pub randomness: f32,
+    /// The probability of overlooking the cells the opponent would win by occupying.
+    pub missed_blocks: f32,
+    pub tempo: Tempo,
+}
+
+/// Which of the moves with the best [`Value`](crate::solver::Value) is preferred.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Style {
+    /// Any of them.
+    Balanced,
+    /// The one building the most towards the own lines, see [`potential()`].
+    Aggressive,
+    /// The one leaving the opponent the least to build on, see [`potential()`].
+    Defensive,
+}
+
+/// Plays like a perfect player by looking the positions up in [`tablebase::STANDARD`],
+/// but with the flaws and the preferences of its [`Traits`].
+#[derive(Debug)]
+pub struct PersonalityAi {
+    /// Decides moves, see [`Pacing::delay_rng`].
+    rng: Rand32,
+    pacing: Pacing,
+    traits: Traits,
+}
+
+impl PersonalityAi {
+    pub fn new(seed: u64, action_queue: Rc<DefaultActionQueue>, traits: Traits) -> Self {
+        let mut pacing = Pacing::new(seed, action_queue);
+        pacing.set_tempo(traits.tempo);
+        Self {
+            rng: Rand32::new(seed),
+            pacing,
+            traits,
+        }
+    }
+
+    fn act_inround(&mut self, state: &State) {
+        if self.pacing.can_move(state) {
+            let position = Position::new(state.board.clone(), state.turn());
+            let (cell, reason) = decide_cell(&self.traits, &mut self.rng, &position);
+            self.pacing.action_queue.add(Action::Occupy(cell, reason));
+        }
+    }
+}
+
+impl Ai for PersonalityAi {
+    fn player_id(&self) -> PlayerId {
+        self.pacing.player_id()
+    }
+
+    fn act(&mut self, state: &State) {
+        match state.phase {
+            Beginning | Outround => self.pacing.act_beginning_outround(state),
+            Inround => self.act_inround(state),
+        }
+    }
+
+    fn set_base_act_delay(&mut self, delay: Duration) {
+        self.pacing.set_base_act_delay(delay);
+    }
+
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.pacing.set_tempo(tempo);
+    }
+}
+
+/// Returns the cell with the [`Reason`] for occupying it.
+/// The `position` must not be over.
+fn decide_cell(traits: &Traits, rng: &mut Rand32, position: &Position) -> (Cell, Option<Reason>) {
+    if rng.rand_float() < traits.randomness {
+        let moves = position.moves();
+        return (moves[random_idx(rng, moves.len())], Some(Reason::Random));
+    }
+    let mut moves = tablebase::STANDARD
+        .analyze(position)
+        .expect("the position must be reachable")
+        .moves;
+    let threats = position.threats();
+    if !threats.is_empty() && rng.rand_float() < traits.missed_blocks {
+        let unblocking = moves
+            .iter()
+            .copied()
+            .filter(|(cell, _)| !threats.contains(cell))
+            .collect::<Vec<_>>();
+        if !unblocking.is_empty() {
+            moves = unblocking;
+        }
+    }
+    let (_, best) = moves[0];
+    let best = moves
+        .into_iter()
+        .filter(|&(_, value)| value == best)
+        .map(|(cell, _)| cell)
+        .collect::<Vec<_>>();
+    let opponent = position.opponent();
+    let preferred = match traits.style {
+        Style::Balanced => best,
+        Style::Aggressive => preferred(&best, |cell| {
+            potential(position.play(cell).board(), position.turn())
+        }),
+        Style::Defensive => preferred(&best, |cell| {
+            -potential(position.play(cell).board(), opponent)
+        }),
+    };
+    let cell = preferred[random_idx(rng, preferred.len())];
+    (cell, Reason::of(position, &cell))
+}
+
+/// Returns the `cells` with the greatest `score`.
+fn preferred(cells: &[Cell], score: impl Fn(&Cell) -> i32) -> Vec<Cell> {
+    let scores = cells.iter().map(score).collect::<Vec<_>>();
+    let max = scores.iter().copied().max().unwrap_or_default();
+    cells
+        .iter()
+        .zip(scores)
+        .filter(|&(_, score)| score == max)
+        .map(|(&cell, _)| cell)
+        .collect()
+}
+
+/// The sum of the squares of the numbers of the cells `player_id` occupies
+/// in each [`Line`] the opponent does not occupy, which grows the closer `player_id` is
+/// to occupying the lines.
+fn potential(board: &Board, player_id: PlayerId) -> i32 {
+    (0..Board::SIZE)
+        .flat_map(|idx| [Line::H(idx), Line::V(idx)])
+        .chain([Line::D1, Line::D2])
+        .map(|line| {
+            let occupants = board
+                .cells()
+                .filter(|cell| line.contains(cell))
+                .filter_map(|cell| board.get(&cell))
+                .collect::<Vec<_>>();
+            if occupants.iter().any(|&occupant| occupant != player_id) {
+                0
+            } else {
+                let count = i32::try_from(occupants.len()).unwrap();
+                count * count
+            }
+        })
+        .sum()
+}
@@ -0,0 +1,86 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod PersonalityAi {
+    use oorandom::Rand32;
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        ai::{
+            personality::{self, Personality, PersonalityAi, Style, Traits},
+            AiKind, Tempo,
+        },
+        game::Cell,
+        save::SavedGame,
+        sim,
+        solver::Position,
+        PlayerId,
+    };
+
+    const PERFECT: Traits = Traits {
+        style: Style::Balanced,
+        randomness: 0.0,
+        missed_blocks: 0.0,
+        tempo: Tempo::DEFAULT,
+    };
+
+    /// Returns the cells decided in the `position` in many attempts, in the order of the cells.
+    fn decided(traits: &Traits, position: &str) -> Vec<String> {
+        let position = position.parse::<Position>().unwrap();
+        let mut rng = Rand32::new(0);
+        let mut cells = (0..100)
+            .map(|_| personality::decide_cell(traits, &mut rng, &position).0)
+            .collect::<Vec<Cell>>();
+        cells.sort_by_key(|cell| (cell.x(), cell.y()));
+        cells.dedup();
+        cells.iter().map(ToString::to_string).collect()
+    }
+
+    #[test_case(PERFECT, "XX./O../... O", &["c1"]; "blocks")]
+    #[test_case(Traits { missed_blocks: 1.0, ..PERFECT }, "XX./O../... O", &["b2", "c2", "a3", "b3", "c3"]; "misses blocks")]
+    #[test_case(Traits { missed_blocks: 1.0, ..PERFECT }, "XX./OO./X.. O", &["c2"]; "wins while missing blocks")]
+    #[test_case(Traits { randomness: 1.0, ..PERFECT }, "XX./O../... O", &["c1", "b2", "c2", "a3", "b3", "c3"]; "random")]
+    #[test_case(Traits { style: Style::Aggressive, ..PERFECT }, ".../.../... X", &["b2"]; "aggressive")]
+    #[test_case(Traits { style: Style::Defensive, ..PERFECT }, "X../.../... O", &["b2"]; "defensive")]
+    fn decide_cell(traits: Traits, position: &str, expected: &[&str]) {
+        assert_eq!(decided(&traits, position), expected);
+    }
+
+    #[test_case(".../.../... X", 0, 0)]
+    #[test_case(".../.X./... O", 4, 0)]
+    #[test_case("X../.O./... X", 2, 3)]
+    #[test_case("XX./.O./... O", 5, 2)]
+    fn potential(position: &str, x: i32, o: i32) {
+        let position = position.parse::<Position>().unwrap();
+        assert_eq!(
+            [0, 1].map(|idx| personality::potential(position.board(), PlayerId::new(idx))),
+            [x, o]
+        );
+    }
+
+    #[test]
+    fn play_against_tablebase() {
+        for personality in Personality::ALL {
+            let mut lost = 0;
+            for seed in 0..5 {
+                let game = SavedGame::new(seed, 4, [AiKind::Tablebase; 2]);
+                let (outcomes, _) =
+                    sim::play_world(game.world_with(|player_id, seed, action_queue| {
+                        if player_id.idx == 0 {
+                            Box::new(PersonalityAi::new(seed, action_queue, personality.traits()))
+                        } else {
+                            AiKind::Tablebase.create(seed, action_queue)
+                        }
+                    }));
+                assert_eq!(outcomes.len(), 4);
+                lost += outcomes
+                    .iter()
+                    .filter(|outcome| outcome.winner == Some(PlayerId::new(1)))
+                    .count();
+            }
+            // none is perfect
+            assert!(lost > 0, "{personality}");
+        }
+    }
+}
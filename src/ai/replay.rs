@@ -0,0 +1,76 @@
+//! See [`ReplayAi`].
+
+use alloc::rc::Rc;
+use core::time::Duration;
+
+use crate::{
+    ai::{Pacing, Tempo},
+    game::{
+        Action, Ai,
+        Phase::{Beginning, Inround, Outround},
+    },
+    save::SavedRound,
+    DefaultActionQueue, PlayerId, State,
+};
+
+/// Makes the moves of its player recorded in a [`SavedGame::log`](crate::save::SavedGame::log)
+/// after their think times, and surrenders where its player has surrendered.
+/// A move to an occupied cell, which only an edited save may have, is not made,
+/// so the replay stops there.
+#[derive(Debug)]
+pub struct ReplayAi {
+    pacing: Pacing,
+    log: Rc<[SavedRound]>,
+}
+
+impl ReplayAi {
+    pub fn new(seed: u64, action_queue: Rc<DefaultActionQueue>, log: Rc<[SavedRound]>) -> Self {
+        Self {
+            pacing: Pacing::new(seed, action_queue),
+            log,
+        }
+    }
+
+    fn act_inround(&mut self, state: &State) {
+        let Some(round) = self.log.get(usize::try_from(state.round).unwrap()) else {
+            return;
+        };
+        match round.moves.get(state.round_log().moves.len()) {
+            Some(move_) => {
+                if state.board.get(&move_.cell).is_none()
+                    && self.pacing.can_replay_move(state, move_.think_time)
+                {
+                    self.pacing
+                        .action_queue
+                        .add(Action::Occupy(move_.cell, move_.reason));
+                }
+            }
+            None => {
+                if round.surrendered && self.pacing.can_move(state) {
+                    self.pacing.action_queue.add(Action::Surrender);
+                }
+            }
+        }
+    }
+}
+
+impl Ai for ReplayAi {
+    fn player_id(&self) -> PlayerId {
+        self.pacing.player_id()
+    }
+
+    fn act(&mut self, state: &State) {
+        match state.phase {
+            Beginning | Outround => self.pacing.act_beginning_outround(state),
+            Inround => self.act_inround(state),
+        }
+    }
+
+    fn set_base_act_delay(&mut self, delay: Duration) {
+        self.pacing.set_base_act_delay(delay);
+    }
+
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.pacing.set_tempo(tempo);
+    }
+}
@@ -0,0 +1,68 @@
+//! See [`TablebaseAi`].
+
+use alloc::rc::Rc;
+use core::time::Duration;
+
+use oorandom::Rand32;
+
+use crate::{
+    ai::{random_idx, Pacing, Reason, Tempo},
+    game::{
+        Action, Ai,
+        Phase::{Beginning, Inround, Outround},
+    },
+    solver::{tablebase, Position},
+    DefaultActionQueue, PlayerId, State,
+};
+
+mod test;
+
+/// Plays perfectly by looking the positions up in [`tablebase::STANDARD`],
+/// and picks a random move among the best ones.
+#[derive(Debug)]
+pub struct TablebaseAi {
+    /// Decides moves, see [`Pacing::delay_rng`].
+    rng: Rand32,
+    pacing: Pacing,
+}
+
+impl TablebaseAi {
+    pub fn new(seed: u64, action_queue: Rc<DefaultActionQueue>) -> Self {
+        Self {
+            rng: Rand32::new(seed),
+            pacing: Pacing::new(seed, action_queue),
+        }
+    }
+
+    fn act_inround(&mut self, state: &State) {
+        if self.pacing.can_move(state) {
+            let position = Position::new(state.board.clone(), state.turn());
+            let best = tablebase::STANDARD.best_moves(&position);
+            let cell = best[random_idx(&mut self.rng, best.len())];
+            self.pacing
+                .action_queue
+                .add(Action::Occupy(cell, Reason::of(&position, &cell)));
+        }
+    }
+}
+
+impl Ai for TablebaseAi {
+    fn player_id(&self) -> PlayerId {
+        self.pacing.player_id()
+    }
+
+    fn act(&mut self, state: &State) {
+        match state.phase {
+            Beginning | Outround => self.pacing.act_beginning_outround(state),
+            Inround => self.act_inround(state),
+        }
+    }
+
+    fn set_base_act_delay(&mut self, delay: Duration) {
+        self.pacing.set_base_act_delay(delay);
+    }
+
+    fn set_tempo(&mut self, tempo: Tempo) {
+        self.pacing.set_tempo(tempo);
+    }
+}
@@ -0,0 +1,30 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod TablebaseAi {
+    use crate::{ai::AiKind, save::SavedGame, sim, PlayerId};
+
+    #[test]
+    fn draws_against_itself_and_never_loses_against_mcts() {
+        for seed in 0..2 {
+            let outcomes = sim::play_game(&SavedGame::new(seed, 3, [AiKind::Tablebase; 2]), None);
+            assert!(
+                outcomes.iter().all(|outcome| outcome.winner.is_none()),
+                "{seed}, {outcomes:?}"
+            );
+            for ais in [
+                [AiKind::Tablebase, AiKind::Mcts],
+                [AiKind::Mcts, AiKind::Tablebase],
+            ] {
+                let tablebase_id = PlayerId::new(usize::from(ais[1] == AiKind::Tablebase));
+                let outcomes = sim::play_game(&SavedGame::new(seed, 3, ais), None);
+                assert!(
+                    outcomes.iter().all(|outcome| outcome
+                        .winner
+                        .map_or(true, |winner| winner == tablebase_id)),
+                    "{seed}, {ais:?}, {outcomes:?}"
+                );
+            }
+        }
+    }
+}
@@ -13,10 +13,41 @@ mod Random {
     use oorandom::Rand64;
 
     use crate::{
-        ai::RandomAi, game::Ai, DefaultActionQueue, Local, LocalPlayerType, Logic, Player,
-        PlayerId, State, World,
+        ai::RandomAi,
+        game::{Ai, Phase},
+        util::time::ManualClockSource,
+        DefaultActionQueue, Local, LocalPlayerType, Logic, Player, PlayerId, State, World,
     };
 
+    #[test]
+    fn act__waits_for_delay() {
+        let clock_source = ManualClockSource::new();
+        let p0 = Player::new(PlayerId::new(0), Local(LocalPlayerType::Ai));
+        let p1 = Player::new(PlayerId::new(1), Local(LocalPlayerType::Ai));
+        let p0_act_queue = Rc::new(DefaultActionQueue::new(p0.id));
+        let p1_act_queue = Rc::new(DefaultActionQueue::new(p1.id));
+        let mut world = World::new(
+            State::new([p0, p1], State::DEFAULT_ROUNDS),
+            Logic::new([Rc::clone(&p0_act_queue), Rc::clone(&p1_act_queue)]),
+            vec![
+                Box::new(RandomAi::new(0, Rc::clone(&p0_act_queue))),
+                Box::new(RandomAi::new(1, Rc::clone(&p1_act_queue))),
+            ],
+        )
+        .with_clock_source(Box::new(clock_source.clone()));
+        // the delays are within ±25% of the base one
+        for ai in world.ais() {
+            ai.set_base_act_delay(Duration::from_millis(100));
+        }
+        world.advance();
+        clock_source.advance(Duration::from_millis(74));
+        world.advance();
+        assert_eq!(world.state().phase, Phase::Beginning);
+        clock_source.advance(Duration::from_millis(52));
+        world.advance();
+        assert_eq!(world.state().phase, Phase::Inround);
+    }
+
     #[test]
     #[timeout(100)]
     fn play_against_itself() {
@@ -66,3 +97,90 @@ mod Random {
         }
     }
 }
+
+mod Tempo {
+    use oorandom::Rand32;
+    use test_case::test_case;
+
+    use crate::ai::{Criticality, Tempo};
+
+    const TEMPO: Tempo = Tempo {
+        spread: 0.0,
+        forced: 0.5,
+        critical: 2.0,
+        pause_chance: 0.0,
+        pause: 3.0,
+    };
+
+    #[test_case(TEMPO, None, 1.0)]
+    #[test_case(TEMPO, Some(Criticality::Forced), 0.5)]
+    #[test_case(TEMPO, Some(Criticality::Normal), 1.0)]
+    #[test_case(TEMPO, Some(Criticality::Critical), 2.0)]
+    #[test_case(Tempo { pause_chance: 1.0, ..TEMPO }, None, 1.0; "no pause before readying up")]
+    #[test_case(Tempo { pause_chance: 1.0, ..TEMPO }, Some(Criticality::Forced), 0.5; "no pause before a forced move")]
+    #[test_case(Tempo { pause_chance: 1.0, ..TEMPO }, Some(Criticality::Normal), 3.0; "pause before a normal move")]
+    #[test_case(Tempo { pause_chance: 1.0, ..TEMPO }, Some(Criticality::Critical), 6.0; "pause before a critical move")]
+    fn factor(tempo: Tempo, criticality: Option<Criticality>, expected: f32) {
+        assert!((tempo.factor(criticality, &mut Rand32::new(0)) - expected).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn factor__spread() {
+        let tempo = Tempo {
+            spread: 0.5,
+            ..TEMPO
+        };
+        let mut rng = Rand32::new(0);
+        for _ in 0..1_000 {
+            let factor = tempo.factor(Some(Criticality::Critical), &mut rng);
+            assert!((1.5..=2.5).contains(&factor), "{factor}");
+        }
+    }
+}
+
+mod Criticality {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{ai::Criticality, solver::Position};
+
+    #[test_case(".../.../... X", Criticality::Normal; "empty")]
+    #[test_case("X../.O./... X", Criticality::Normal; "no forks yet")]
+    #[test_case("XOX/OXO/OX. O", Criticality::Forced; "one empty cell")]
+    #[test_case("XX./O../... O", Criticality::Forced; "one threat")]
+    #[test_case("XX./OO./... X", Criticality::Critical; "win")]
+    #[test_case("XX./OO./X.. O", Criticality::Critical; "win while threatened")]
+    #[test_case("X../.O./..X O", Criticality::Critical; "fork of the opponent")]
+    #[test_case("X../.X./O.. O", Criticality::Forced; "block")]
+    #[test_case("..O/XXO/OXX O", Criticality::Critical; "fork of the opponent made")]
+    fn of(position: &str, expected: Criticality) {
+        assert_eq!(
+            Criticality::of(&position.parse::<Position>().unwrap()),
+            expected
+        );
+    }
+}
+
+mod Reason {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{ai::Reason, solver::Position};
+
+    #[test_case("XX./OO./... X", "c1", Some(Reason::WinNow); "win")]
+    #[test_case("XX./OO./X.. O", "c2", Some(Reason::WinNow); "win rather than block")]
+    #[test_case("XX./O../... O", "c1", Some(Reason::Block); "block")]
+    #[test_case("XOX/..O/... X", "a3", Some(Reason::Fork); "fork")]
+    #[test_case(".../.../... X", "b2", Some(Reason::Center); "center")]
+    #[test_case(".../.X./... O", "a3", Some(Reason::Corner); "corner")]
+    #[test_case(".../.X./... O", "b1", None; "edge")]
+    fn of(position: &str, cell: &str, expected: Option<Reason>) {
+        assert_eq!(
+            Reason::of(
+                &position.parse::<Position>().unwrap(),
+                &cell.parse().unwrap()
+            ),
+            expected
+        );
+    }
+}
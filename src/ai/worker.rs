@@ -0,0 +1,144 @@
+//! See [`Worker`].
+
+use alloc::sync::Arc;
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicBool, Ordering::Relaxed},
+};
+use std::thread::{self, JoinHandle};
+
+use crate::solver::Position;
+
+mod test;
+
+/// Decides moves, possibly for a long time, which is why it is run by a [`Worker`].
+pub trait Decider: Send + 'static {
+    /// The decided [`Cell`](crate::game::Cell), possibly with more about the decision.
+    type Decision: Copy + Debug + Send + 'static;
+
+    /// Must be called only if the `position` is not over.
+    /// Once `cancelled` is set, the decision must be returned as soon as possible.
+    fn decide_cell(&mut self, position: &Position, cancelled: &AtomicBool) -> Self::Decision;
+}
+
+/// Runs a [`Decider`] on a worker thread, so that the thread calling [`Ai::act`](crate::game::Ai::act),
+/// e.g., that of the user interface, is not blocked, and is polled for the decision instead.
+#[derive(Debug)]
+pub struct Worker<D>
+where
+    D: Decider,
+{
+    /// [`None`] while the [`Self::job`] is running.
+    decider: Option<D>,
+    job: Option<Job<D>>,
+    /// The latest decision, until [`Self::cancel()`] is called.
+    decided: Option<(Position, D::Decision)>,
+    /// Whether to decide on the calling thread instead.
+    blocking: bool,
+}
+
+#[derive(Debug)]
+struct Job<D>
+where
+    D: Decider,
+{
+    position: Position,
+    cancelled: Arc<AtomicBool>,
+    handle: JoinHandle<(D, D::Decision)>,
+}
+
+impl<D> Worker<D>
+where
+    D: Decider,
+{
+    pub fn new(decider: D) -> Self {
+        Self {
+            decider: Some(decider),
+            job: None,
+            decided: None,
+            blocking: false,
+        }
+    }
+
+    /// See [`Ai::set_blocking()`](crate::game::Ai::set_blocking).
+    pub fn set_blocking(&mut self, blocking: bool) {
+        self.blocking = blocking;
+    }
+
+    /// Returns the decision for the `position`, if it is ready,
+    /// otherwise starts deciding it unless that has already been done.
+    /// Deciding for another position is cancelled.
+    pub fn poll(&mut self, position: &Position) -> Option<D::Decision> {
+        if let Some((decided_position, cell)) = &self.decided {
+            if decided_position == position {
+                return Some(*cell);
+            }
+        }
+        if self
+            .job
+            .as_ref()
+            .map_or(false, |job| job.position != *position)
+        {
+            self.cancel();
+        }
+        if self.job.is_none() {
+            let mut decider = self.decider.take().expect("the decider must be idle");
+            if self.blocking {
+                let cell = decider.decide_cell(position, &AtomicBool::new(false));
+                self.decider = Some(decider);
+                self.decided = Some((position.clone(), cell));
+                return Some(cell);
+            }
+            let cancelled = Arc::new(AtomicBool::new(false));
+            self.job = Some(Job {
+                position: position.clone(),
+                cancelled: Arc::clone(&cancelled),
+                handle: {
+                    let position = position.clone();
+                    thread::spawn(move || {
+                        let cell = decider.decide_cell(&position, &cancelled);
+                        (decider, cell)
+                    })
+                },
+            });
+        }
+        if !self.blocking && !self.job.as_ref()?.handle.is_finished() {
+            return None;
+        }
+        let job = self.job.take()?;
+        let cell = self.join(job);
+        self.decided = Some((position.clone(), cell));
+        Some(cell)
+    }
+
+    /// Discards the latest decision, and cancels deciding, if that is in progress.
+    /// Blocks until the [`Decider`] returns, which it does soon after being cancelled.
+    pub fn cancel(&mut self) {
+        self.decided = None;
+        if let Some(job) = self.job.take() {
+            job.cancelled.store(true, Relaxed);
+            self.join(job);
+        }
+    }
+
+    fn join(&mut self, job: Job<D>) -> D::Decision {
+        let (decider, cell) = job
+            .handle
+            .join()
+            .expect("a decider thread should not panic");
+        self.decider = Some(decider);
+        cell
+    }
+}
+
+impl<D> Drop for Worker<D>
+where
+    D: Decider,
+{
+    /// Cancels deciding without waiting for the [`Decider`] to return.
+    fn drop(&mut self) {
+        if let Some(job) = &self.job {
+            job.cancelled.store(true, Relaxed);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Worker {
+    use core::{
+        sync::atomic::{AtomicBool, Ordering::Relaxed},
+        time::Duration,
+    };
+    use std::thread;
+
+    use ntest::timeout;
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::{
+        ai::worker::{Decider, Worker},
+        game::Cell,
+        solver::Position,
+    };
+
+    /// Decides the first empty cell, after being cancelled if `until_cancelled`.
+    #[derive(Debug)]
+    struct FirstEmpty {
+        until_cancelled: bool,
+        decisions: u32,
+    }
+
+    impl Decider for FirstEmpty {
+        type Decision = Cell;
+
+        fn decide_cell(&mut self, position: &Position, cancelled: &AtomicBool) -> Cell {
+            while self.until_cancelled && !cancelled.load(Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            self.decisions += 1;
+            position.moves()[0]
+        }
+    }
+
+    fn worker(until_cancelled: bool) -> Worker<FirstEmpty> {
+        Worker::new(FirstEmpty {
+            until_cancelled,
+            decisions: 0,
+        })
+    }
+
+    fn position(s: &str) -> Position {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn poll() {
+        let mut worker = worker(false);
+        let position = position("X../.../... O");
+        let cell = loop {
+            if let Some(cell) = worker.poll(&position) {
+                break cell;
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+        assert_eq!(cell, "b1".parse().unwrap());
+        assert_eq!(worker.poll(&position), Some(cell));
+        assert_eq!(worker.decider.as_ref().unwrap().decisions, 1);
+    }
+
+    #[test]
+    fn poll__blocking() {
+        let mut worker = worker(false);
+        worker.set_blocking(true);
+        assert_eq!(
+            worker.poll(&position("X../.../... O")),
+            Some("b1".parse().unwrap())
+        );
+        assert!(worker.job.is_none());
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn poll__another_position() {
+        let mut worker = worker(true);
+        assert_eq!(worker.poll(&position("X../.../... O")), None);
+        assert_eq!(worker.poll(&position("X../.../... O")), None);
+        assert_eq!(worker.poll(&position(".X./.../... O")), None);
+        worker.cancel();
+        assert_eq!(worker.decider.as_ref().unwrap().decisions, 2);
+        assert_eq!(worker.decided, None);
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn drop() {
+        let mut worker = worker(true);
+        assert_eq!(worker.poll(&position("X../.../... O")), None);
+        core::mem::drop(worker);
+    }
+}
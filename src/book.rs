@@ -0,0 +1,303 @@
+//! Opening books, see [`Book`], and the builder of them, see [`run()`].
+
+use alloc::collections::BTreeMap;
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+use std::{
+    env,
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use oorandom::{Rand32, Rand64};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::{
+    ai,
+    ai::AiKind,
+    cli::BookArgs,
+    game::{Board, Cell, PlayerId, State},
+    profile::RoundResult,
+    save::SavedGame,
+    sim,
+    solver::{
+        tablebase::{self, Tablebase},
+        Position,
+    },
+    util::dirs,
+};
+
+mod test;
+
+/// Where the moves of a [`Book`] come from.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Source {
+    /// The best moves as per [`Tablebase`], with equal weights.
+    Tablebase,
+    /// The moves an [`AiKind`] makes when playing against itself,
+    /// weighted by how often and how successfully they are made.
+    SelfPlay,
+}
+
+impl Source {
+    pub const NAMES: [&'static str; 2] = ["tablebase", "self-play"];
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Tablebase => Self::NAMES[0],
+            Self::SelfPlay => Self::NAMES[1],
+        })
+    }
+}
+
+impl FromStr for Source {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tablebase" => Ok(Self::Tablebase),
+            "self-play" => Ok(Self::SelfPlay),
+            _ => Err(format!(
+                "unknown source {s:?}, expected one of {:?}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+/// Builds the [`Book`] specified by `args`, writes it,
+/// and prints where it has been written to the standard output.
+pub fn run(args: &BookArgs) -> Result<(), Box<dyn Error>> {
+    let path = match args.output() {
+        Some(path) => path.clone(),
+        None => Book::path(&|name| env::var_os(name)).ok_or(
+            "Cannot locate the data directory, neither $XDG_DATA_HOME nor $HOME is specified.",
+        )?,
+    };
+    let book = match args.source() {
+        Source::Tablebase => Book::from_tablebase(&tablebase::STANDARD, args.plies()),
+        Source::SelfPlay => Book::from_self_play(
+            args.ai(),
+            args.games(),
+            args.plies(),
+            args.seed().unwrap_or_else(ai::time_based_seed),
+        ),
+    };
+    book.write(&path)
+        .map_err(|e| format!("Failed to write {}: {e}.", path.display()))?;
+    println!(
+        "Built a book of {} positions, written to {}.",
+        book.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Weighted moves by the hashes of the positions, see [`Book::hash()`].
+/// A move is picked with a probability proportional to its weight.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Book {
+    /// The moves of each position are ordered by their weights, the heaviest first,
+    /// and have non-zero weights.
+    entries: BTreeMap<u64, Vec<(Cell, u32)>>,
+}
+
+impl Book {
+    const FILE_NAME: &'static str = "book.txt";
+
+    /// Returns the path of the book file inside [`dirs::data_dir()`].
+    pub fn path(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::data_dir(env).map(|dir| dir.join(Self::FILE_NAME))
+    }
+
+    /// The hash of the notation of the `position`.
+    pub fn hash(position: &Position) -> u64 {
+        xxh3_64(position.to_string().as_bytes())
+    }
+
+    /// The number of positions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the legal moves for the `position` with their weights, the heaviest first.
+    pub fn moves(&self, position: &Position) -> Vec<(Cell, u32)> {
+        let legal = position.moves();
+        self.entries
+            .get(&Self::hash(position))
+            .map_or_else(Vec::new, |moves| {
+                moves
+                    .iter()
+                    .filter(|(cell, _)| legal.contains(cell))
+                    .copied()
+                    .collect()
+            })
+    }
+
+    /// Returns a move for the `position`, if it is in the book.
+    /// With the probability of `randomness`, which must be in `[0, 1]`,
+    /// the move is picked with a probability proportional to its weight,
+    /// otherwise, the heaviest move is picked.
+    pub fn pick(&self, position: &Position, randomness: f32, rng: &mut Rand32) -> Option<Cell> {
+        let moves = self.moves(position);
+        let (heaviest, _) = *moves.first()?;
+        if rng.rand_float() >= randomness {
+            return Some(heaviest);
+        }
+        let mut weight = rng.rand_range(0..moves.iter().map(|&(_, weight)| weight).sum());
+        moves
+            .iter()
+            .find(|&&(_, move_weight)| {
+                let found = weight < move_weight;
+                weight = weight.saturating_sub(move_weight);
+                found
+            })
+            .map(|&(cell, _)| cell)
+    }
+
+    /// Adds the best moves in all positions with fewer than `plies` marks
+    /// reachable by any player moving first.
+    pub fn from_tablebase(tablebase: &Tablebase, plies: usize) -> Self {
+        let mut book = Self::default();
+        let mut stack = [0, 1]
+            .map(|idx| Position::new(Board::default(), PlayerId::new(idx)))
+            .to_vec();
+        while let Some(position) = stack.pop() {
+            let board = position.board();
+            let marks = board
+                .cells()
+                .filter(|cell| board.get(cell).is_some())
+                .count();
+            if marks >= plies || book.entries.contains_key(&Self::hash(&position)) {
+                continue;
+            }
+            for cell in tablebase.best_moves(&position) {
+                book.add(&position, cell, 1);
+            }
+            stack.extend(position.moves().iter().map(|cell| position.play(cell)));
+        }
+        book.sort();
+        book
+    }
+
+    /// Plays `games` games of the `ai` against itself, and adds the first `plies` moves
+    /// of each round with the weights of 2 for a won round, 1 for a drawn one.
+    pub fn from_self_play(ai: AiKind, games: u64, plies: usize, seed: u64) -> Self {
+        let mut book = Self::default();
+        let mut rng = Rand64::new(u128::from(seed));
+        for _ in 0..games {
+            let game = SavedGame::new(rng.rand_u64(), State::DEFAULT_ROUNDS, [ai; 2]);
+            let (_, world) = sim::play_world(
+                game.world_with(|_, seed, action_queue| ai.create(seed, action_queue)),
+            );
+            for round_log in &world.state().log {
+                let (Some(outcome), Some(first)) = (round_log.outcome, round_log.moves.first())
+                else {
+                    continue;
+                };
+                let mut position = Position::new(Board::default(), first.player_id);
+                for mv in round_log.moves.iter().take(plies) {
+                    match RoundResult::new(outcome, mv.player_id) {
+                        RoundResult::Win => book.add(&position, mv.cell, 2),
+                        RoundResult::Draw => book.add(&position, mv.cell, 1),
+                        RoundResult::Loss => {}
+                    }
+                    position = position.play(&mv.cell);
+                }
+            }
+        }
+        book.sort();
+        book
+    }
+
+    fn add(&mut self, position: &Position, cell: Cell, weight: u32) {
+        let moves = self.entries.entry(Self::hash(position)).or_default();
+        match moves.iter_mut().find(|(move_cell, _)| *move_cell == cell) {
+            Some((_, move_weight)) => *move_weight += weight,
+            None => moves.push((cell, weight)),
+        }
+    }
+
+    /// Orders the moves of each position as required by [`Self::entries`].
+    fn sort(&mut self) {
+        for moves in self.entries.values_mut() {
+            // the sort is stable, so the moves with equal weights remain in the order of addition
+            moves.sort_by(|(_, w1), (_, w2)| w2.cmp(w1));
+        }
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Creates the parent directory of `path` if needed.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())
+    }
+}
+
+impl Display for Book {
+    /// A position per line: the hexadecimal hash followed by the moves with their weights,
+    /// e.g., `0123456789abcdef b2:3 a1:1`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (hash, moves) in &self.entries {
+            write!(f, "{hash:016x}")?;
+            for (cell, weight) in moves {
+                write!(f, " {cell}:{weight}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Book {
+    type Err = String;
+
+    /// Parses the format described in [`Book::fmt()`], ignoring blank lines.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut book = Self::default();
+        for (idx, line) in s.lines().enumerate() {
+            let err = |e: String| format!("line {}: {e}", idx + 1);
+            let mut parts = line.split_whitespace();
+            let Some(hash) = parts.next() else {
+                continue;
+            };
+            let hash = u64::from_str_radix(hash, 16)
+                .map_err(|e| err(format!("invalid hash {hash:?}: {e}")))?;
+            let moves = parts
+                .map(|part| {
+                    let (cell, weight) = part
+                        .split_once(':')
+                        .ok_or_else(|| format!("expected <cell>:<weight>, got {part:?}"))?;
+                    let weight = weight
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|&weight| weight > 0)
+                        .ok_or_else(|| format!("invalid weight {weight:?}"))?;
+                    Ok((cell.parse::<Cell>()?, weight))
+                })
+                .collect::<Result<Vec<_>, String>>()
+                .map_err(err)?;
+            if moves.is_empty() {
+                return Err(err("expected moves".to_owned()));
+            }
+            if book.entries.insert(hash, moves).is_some() {
+                return Err(err(format!("duplicate hash {hash:016x}")));
+            }
+        }
+        book.sort();
+        Ok(book)
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Book {
+    use oorandom::Rand32;
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        ai::AiKind,
+        book::Book,
+        game::Cell,
+        solver::{tablebase, Position},
+    };
+
+    fn position(s: &str) -> Position {
+        s.parse().unwrap()
+    }
+
+    fn cells(cells: &[&str]) -> Vec<Cell> {
+        cells.iter().map(|cell| cell.parse().unwrap()).collect()
+    }
+
+    /// The book with the moves for the empty board with X to move.
+    fn book(moves: &str) -> Book {
+        format!("{:016x} {moves}", Book::hash(&position(".../.../... X")))
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn from_str__to_string() {
+        let s = "00000000000000ff b2:3 a1:1\n0123456789abcdef c3:1\n";
+        assert_eq!(s.parse::<Book>().unwrap().to_string(), s);
+        assert_eq!(
+            "00000000000000ff a1:1 b2:3"
+                .parse::<Book>()
+                .unwrap()
+                .to_string(),
+            "00000000000000ff b2:3 a1:1\n"
+        );
+    }
+
+    #[test_case("xyz b2:1"; "invalid hash")]
+    #[test_case("00000000000000ff"; "no moves")]
+    #[test_case("00000000000000ff b2"; "no weight")]
+    #[test_case("00000000000000ff b2:0"; "zero weight")]
+    #[test_case("00000000000000ff z9:1"; "invalid cell")]
+    #[test_case("00000000000000ff b2:1\n00000000000000ff a1:1"; "duplicate hash")]
+    fn from_str__Err(s: &str) {
+        assert!(s.parse::<Book>().is_err(), "{s:?}");
+    }
+
+    #[test]
+    fn moves() {
+        let book = book("b2:3 a1:1");
+        assert_eq!(
+            book.moves(&position(".../.../... X")),
+            [("b2".parse().unwrap(), 3), ("a1".parse().unwrap(), 1)]
+        );
+        assert_eq!(book.moves(&position(".../.../... O")), []);
+    }
+
+    #[test_case(0.0, &["b2"])]
+    #[test_case(1.0, &["b2", "a1"])]
+    fn pick(randomness: f32, expected: &[&str]) {
+        let book = book("b2:3 a1:1");
+        let mut rng = Rand32::new(0);
+        let mut picked = Vec::new();
+        for _ in 0..100 {
+            let cell = book
+                .pick(&position(".../.../... X"), randomness, &mut rng)
+                .unwrap();
+            if !picked.contains(&cell) {
+                picked.push(cell);
+            }
+        }
+        assert_eq!(picked, cells(expected));
+    }
+
+    #[test]
+    fn from_tablebase() {
+        let book = Book::from_tablebase(&tablebase::STANDARD, 2);
+        // the empty board with either mark to move, and 9 cells occupied by either mark
+        assert_eq!(book.len(), 2 + 2 * 9);
+        // all moves draw
+        assert_eq!(book.moves(&position(".../.../... O")).len(), 9);
+        assert_eq!(
+            book.moves(&position(".../.X./... O")),
+            cells(&["a1", "c1", "a3", "c3"])
+                .into_iter()
+                .map(|cell| (cell, 1))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn from_self_play() {
+        let book = Book::from_self_play(AiKind::Random, 20, 1, 0);
+        // the empty board with either mark to move
+        assert_eq!(book.len(), 2);
+        for s in [".../.../... X", ".../.../... O"] {
+            assert!(!book.moves(&position(s)).is_empty(), "{s}");
+        }
+    }
+}
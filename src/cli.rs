@@ -1,35 +1,123 @@
-use std::{ffi::OsString, net::SocketAddr};
+use core::{fmt::Display, num::NonZeroUsize, time::Duration};
+use std::{
+    env,
+    ffi::OsString,
+    fs, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
 
-use clap::{crate_authors, crate_description, value_parser, Arg, ArgAction, Command, Error};
+use clap::{
+    builder::{PossibleValuesParser, TypedValueParser},
+    crate_authors, crate_description,
+    error::ErrorKind,
+    parser::ValueSource,
+    value_parser, Arg, ArgAction, ArgMatches, Command, Error,
+};
+use toml::{value::Table, Value};
 
-use crate::{process::APP_METADATA, Dedicated, Interactive};
+use crate::{
+    ai::{
+        external::{EngineCommand, ExternalAi},
+        mcts::{Budget, MctsOpts, Playout},
+        AiKind,
+    },
+    book::Source,
+    game::{Board, State},
+    process::APP_METADATA,
+    rating::RatingPeriod,
+    sim::ReportFormat,
+    solver::{tablebase, Position},
+    tournament::{Entrant, System},
+    tui::theme::Themes,
+    util::dirs,
+    Analyze, Book, Engine, Interactive, Play, Server, Simulate, Tablebase, Tournament, Train,
+};
 
 mod test;
 
-const DEDICATED_ARG_ID: &str = "TODO-dedicated";
-const LISTEN_ARG_ID: &str = "TODO-listen";
+const CONFIG_ARG_ID: &str = "config";
+const INTERACTIVE_CMD_ID: &str = "interactive";
+const PLAY_CMD_ID: &str = "play";
+const SERVER_CMD_ID: &str = "server";
+const LISTEN_ARG_ID: &str = "listen";
+const SIMULATE_CMD_ID: &str = "simulate";
+const GAMES_ARG_ID: &str = "games";
+const ROUNDS_ARG_ID: &str = "rounds";
+const AI_X_ARG_ID: &str = "ai-x";
+const AI_O_ARG_ID: &str = "ai-o";
+const SEED_ARG_ID: &str = "seed";
+const THEME_ARG_ID: &str = "theme";
+const RATING_PERIOD_ARG_ID: &str = "rating-period";
+const THREADS_ARG_ID: &str = "threads";
+const FORMAT_ARG_ID: &str = "format";
+const TOURNAMENT_CMD_ID: &str = "tournament";
+const SYSTEM_ARG_ID: &str = "system";
+const ENTRANTS_ARG_ID: &str = "entrants";
+const SWISS_ROUNDS_ARG_ID: &str = "swiss-rounds";
+const ANALYZE_CMD_ID: &str = "analyze";
+const POSITION_ARG_ID: &str = "position";
+const ENGINE_CMD_ID: &str = "engine";
+const ENGINE_X_ARG_ID: &str = "engine-x";
+const ENGINE_O_ARG_ID: &str = "engine-o";
+const ENGINE_TIME_ARG_ID: &str = "engine-time";
+const TABLEBASE_CMD_ID: &str = "tablebase";
+const TABLEBASE_ARG_ID: &str = "tablebase";
+const SIZE_ARG_ID: &str = "size";
+const OUTPUT_ARG_ID: &str = "output";
+const BOOK_CMD_ID: &str = "book";
+const BOOK_ARG_ID: &str = "book";
+const BOOK_X_ARG_ID: &str = "book-x";
+const BOOK_O_ARG_ID: &str = "book-o";
+const BOOK_RANDOMNESS_ARG_ID: &str = "book-randomness";
+const SOURCE_ARG_ID: &str = "source";
+const PLIES_ARG_ID: &str = "plies";
+const AI_ARG_ID: &str = "ai";
+const MCTS_ITERATIONS_ARG_ID: &str = "mcts-iterations";
+const MCTS_TIME_ARG_ID: &str = "mcts-time";
+const MCTS_PLAYOUT_ARG_ID: &str = "mcts-playout";
+const TRAIN_CMD_ID: &str = "train";
+const EPOCHS_ARG_ID: &str = "epochs";
+const ENV_VAR_PREFIX: &str = "TIC_TAC_TOE";
+const CONFIG_FILE_NAME: &str = "config.toml";
 
 fn command() -> Command {
     let about = format!(
-    "{crate_description} The game rules can be read at <https://en.wikipedia.org/wiki/Tic-tac-toe>.\n\
+        "{crate_description} The game rules can be read at <https://en.wikipedia.org/wiki/Tic-tac-toe>.\n\
         \n\
         Can run in one of the two modes:\n  \
-          * interactive;\n  \
-          * dedicated server.\n\
+        * interactive;\n  \
+        * dedicated server.\n\
         \n\
         Interactive mode:\n    \
-            The interactive mode is the default one and allows playing offline against an AI,\
-            hosting a game, joining a game as a guest.\n\
+        The interactive mode is the default one and allows playing offline against an AI,\
+        hosting a game, joining a game as a guest. \
+        The `play` command starts it with a Vs. AI game right away.\n\
         Dedicated server mode:\n    \
-            The dedicated server mode allows guests to join, \
-            find other players and play with them.\n\
+        The dedicated server mode allows guests to join, \
+        find other players and play with them.\n\
+        \n\
+        Configuration:\n    \
+        Each option that takes a value may also be specified \
+        via an environment variable or in the configuration file, \
+        as shown in the help of each command. \
+        An option specified in the command line takes precedence \
+        over an environment variable, which takes precedence over the configuration file. \
+        The configuration file is the first one of:\n  \
+        * the one specified via --{CONFIG_ARG_ID};\n  \
+        * the one specified via the {config_env_var} environment variable;\n  \
+        * {config_file} in $XDG_CONFIG_HOME/{app_dir} or $HOME/.config/{app_dir}, \
+        if exists.\n\
         \n\
         Warning:\n    \
-            The project is being developed, unimplemented functionality is marked with \"TODO\".\n\
+        The project is being developed, unimplemented functionality is marked with \"TODO\".\n\
         \n\
         Homepage:\n    \
-            <{homepage}>.",
+        <{homepage}>.",
         crate_description = crate_description!(),
+        config_env_var = config_env_var(),
+        config_file = CONFIG_FILE_NAME,
+        app_dir = dirs::APP_DIR,
         homepage = APP_METADATA.homepage()
     );
     Command::new(APP_METADATA.name())
@@ -48,14 +136,8 @@ fn command() -> Command {
         )
         .disable_help_flag(true)
         .disable_version_flag(true)
-        .arg(
-            Arg::new("help")
-                .short('h')
-                .long("help")
-                .required(false)
-                .action(ArgAction::Help)
-                .help("Print help."),
-        )
+        .disable_help_subcommand(true)
+        .arg(help_arg())
         .arg(
             Arg::new("version")
                 .short('V')
@@ -65,40 +147,777 @@ fn command() -> Command {
                 .help("Print version."),
         )
         .arg(
-            Arg::new(DEDICATED_ARG_ID)
-                .long(DEDICATED_ARG_ID)
+            Arg::new(CONFIG_ARG_ID)
+                .long(CONFIG_ARG_ID)
                 .required(false)
-                .action(ArgAction::SetTrue)
-                .help(
-                    "Start in the dedicated server mode. \
-                    If not specified, the application starts in the interactive mode.",
+                .global(true)
+                .action(ArgAction::Set)
+                .value_parser(value_parser!(PathBuf))
+                .help("The configuration file."),
+        )
+        .subcommand(interactive_command())
+        .subcommand(play_command())
+        .subcommand(server_command())
+        .subcommand(simulate_command())
+        .subcommand(tournament_command())
+        .subcommand(analyze_command())
+        .subcommand(engine_command())
+        .subcommand(train_command())
+        .subcommand(tablebase_command())
+        .subcommand(book_command())
+}
+
+fn help_arg() -> Arg {
+    Arg::new("help")
+        .short('h')
+        .long("help")
+        .required(false)
+        .action(ArgAction::Help)
+        .help("Print help.")
+}
+
+/// Creates an option that may also be specified via an environment variable
+/// or in the configuration file, see [`ParsedArgs::try_from_sources()`].
+fn configurable_arg(cmd_id: &str, id: &'static str, help: &str) -> Arg {
+    Arg::new(id)
+        .long(id)
+        .required(false)
+        .action(ArgAction::Set)
+        .help(format!(
+            "{help}\n[env: {env_var}] [config: {cmd_id}.{id}]",
+            env_var = env_var(cmd_id, id)
+        ))
+}
+
+fn is_configurable(arg: &Arg) -> bool {
+    arg.get_id() != CONFIG_ARG_ID
+        && arg.get_long().is_some()
+        && matches!(arg.get_action(), ArgAction::Set)
+}
+
+fn env_var(cmd_id: &str, arg_id: &str) -> String {
+    format!("{ENV_VAR_PREFIX}_{cmd_id}_{arg_id}")
+        .to_uppercase()
+        .replace('-', "_")
+}
+
+fn config_env_var() -> String {
+    format!("{ENV_VAR_PREFIX}_{CONFIG_ARG_ID}").to_uppercase()
+}
+
+fn interactive_command() -> Command {
+    Command::new(INTERACTIVE_CMD_ID)
+        .about("Start in the interactive mode. This is the default command.")
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .args(interactive_args(INTERACTIVE_CMD_ID))
+}
+
+fn play_command() -> Command {
+    Command::new(PLAY_CMD_ID)
+        .about(
+            "Start in the interactive mode with a Vs. AI game, \
+            as if it was started via the menu.",
+        )
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .args(interactive_args(PLAY_CMD_ID))
+        .arg(
+            configurable_arg(PLAY_CMD_ID, ROUNDS_ARG_ID, "The number of rounds.")
+                .value_parser(value_parser!(u32).range(1..))
+                .default_value(&*State::DEFAULT_ROUNDS.to_string().leak()),
+        )
+        .arg(
+            configurable_arg(PLAY_CMD_ID, AI_ARG_ID, "The AI to play against.")
+                .value_parser(
+                    PossibleValuesParser::new(AiKind::NAMES)
+                        .map(|name| name.parse::<AiKind>().expect("the name must be valid")),
+                )
+                .default_value(AiKind::NAMES[0]),
+        )
+}
+
+/// The arguments of [`INTERACTIVE_CMD_ID`], which are shared by [`PLAY_CMD_ID`].
+fn interactive_args(cmd_id: &str) -> [Arg; 5] {
+    [
+        configurable_arg(
+            cmd_id,
+            SEED_ARG_ID,
+            "The seed from which the seeds of all AIs in a game are derived. \
+                It is the default in the options of each game, \
+                and the same seed with the same options results in the same game. \
+                If not specified, a seed is picked based on the current time.",
+        )
+        .value_parser(value_parser!(u64)),
+        configurable_arg(
+            cmd_id,
+            THEME_ARG_ID,
+            &format!(
+                "The color theme, either a built-in one ({names}), \
+                    or one defined in {file} next to the configuration file.",
+                names = Themes::BUILT_IN_NAMES.join(", "),
+                file = Themes::FILE_NAME
+            ),
+        )
+        .default_value(Themes::DEFAULT),
+        rating_period_arg(cmd_id),
+        book_arg(
+            cmd_id,
+            BOOK_ARG_ID,
+            "The opening book file the AI of Vs. AI games consults first",
+        ),
+        book_randomness_arg(cmd_id),
+    ]
+}
+
+fn rating_period_arg(cmd_id: &str) -> Arg {
+    configurable_arg(
+        cmd_id,
+        RATING_PERIOD_ARG_ID,
+        "Whether the ratings of players are updated after each round, \
+            or once per game based on the average score of its rounds.",
+    )
+    .value_parser(PossibleValuesParser::new(RatingPeriod::NAMES).map(|name| {
+        name.parse::<RatingPeriod>()
+            .expect("the name must be valid")
+    }))
+    .default_value(RatingPeriod::NAMES[1])
+}
+
+fn server_command() -> Command {
+    Command::new(SERVER_CMD_ID)
+        .about("Start in the dedicated server mode.")
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .arg(
+            configurable_arg(
+                SERVER_CMD_ID,
+                LISTEN_ARG_ID,
+                "The TCP socket address to listen on for game clients \
+                and web console requests.",
+            )
+            .value_parser(value_parser!(SocketAddr))
+            .default_value("127.0.0.1:2020"),
+        )
+        .arg(rating_period_arg(SERVER_CMD_ID))
+}
+
+fn simulate_command() -> Command {
+    let ai_arg = |id: &'static str, mark: &str| {
+        configurable_arg(SIMULATE_CMD_ID, id, &format!("The AI playing {mark}."))
+            .value_parser(
+                PossibleValuesParser::new(AiKind::NAMES)
+                    .map(|name| name.parse::<AiKind>().expect("the name must be valid")),
+            )
+            .default_value(AiKind::NAMES[0])
+    };
+    let engine_arg = |id: &'static str, mark: &str, ai_id: &str| {
+        configurable_arg(
+            SIMULATE_CMD_ID,
+            id,
+            &format!(
+                "The command starting an external engine playing {mark} instead of --{ai_id}, \
+                with the program and its arguments separated by whitespace, \
+                each of which may be quoted with `\"` or `'`, e.g., to contain whitespace. \
+                See `{ENGINE_CMD_ID} --help` for the protocol."
+            ),
+        )
+        .value_parser(|s: &str| s.parse::<EngineCommand>())
+    };
+    Command::new(SIMULATE_CMD_ID)
+        .about(
+            "Play games between AIs without the user interface, \
+            and print the statistics of the results.",
+        )
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .arg(
+            configurable_arg(
+                SIMULATE_CMD_ID,
+                GAMES_ARG_ID,
+                "The number of games to play.",
+            )
+            .value_parser(value_parser!(u64).range(1..))
+            .default_value("1000"),
+        )
+        .arg(
+            configurable_arg(
+                SIMULATE_CMD_ID,
+                ROUNDS_ARG_ID,
+                "The number of rounds in each game.",
+            )
+            .value_parser(value_parser!(u32).range(1..))
+            .default_value(&*State::DEFAULT_ROUNDS.to_string().leak()),
+        )
+        .arg(ai_arg(AI_X_ARG_ID, "X"))
+        .arg(ai_arg(AI_O_ARG_ID, "O"))
+        .arg(engine_arg(ENGINE_X_ARG_ID, "X", AI_X_ARG_ID))
+        .arg(engine_arg(ENGINE_O_ARG_ID, "O", AI_O_ARG_ID))
+        .arg(
+            configurable_arg(
+                SIMULATE_CMD_ID,
+                ENGINE_TIME_ARG_ID,
+                "The time in milliseconds an external engine has to decide each move.",
+            )
+            .value_parser(value_parser!(u64).range(1..))
+            .default_value(
+                &*ExternalAi::DEFAULT_TIME_LIMIT
+                    .as_millis()
+                    .to_string()
+                    .leak(),
+            ),
+        )
+        .args(book_args())
+        .args(mcts_args(SIMULATE_CMD_ID))
+        .arg(
+            configurable_arg(
+                SIMULATE_CMD_ID,
+                SEED_ARG_ID,
+                "The seed from which the seeds of all AIs in all games are derived. \
+                If not specified, a seed is picked based on the current time \
+                and is printed with the results.",
+            )
+            .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            configurable_arg(
+                SIMULATE_CMD_ID,
+                THREADS_ARG_ID,
+                "The number of threads to play games on. \
+                If not specified, the available parallelism is used. \
+                The results do not depend on the number of threads.",
+            )
+            .value_parser(value_parser!(NonZeroUsize)),
+        )
+        .arg(
+            configurable_arg(SIMULATE_CMD_ID, FORMAT_ARG_ID, "The format of the results.")
+                .value_parser(PossibleValuesParser::new(ReportFormat::NAMES).map(|name| {
+                    name.parse::<ReportFormat>()
+                        .expect("the name must be valid")
+                }))
+                .default_value(ReportFormat::NAMES[0]),
+        )
+}
+
+/// The arguments of [`SIMULATE_CMD_ID`] specifying the opening books.
+fn book_args() -> [Arg; 3] {
+    let book_x_o_arg = |id: &'static str, mark: &str| {
+        book_arg(
+            SIMULATE_CMD_ID,
+            id,
+            &format!(
+                "The opening book file the AI or the external engine playing {mark} consults first"
+            ),
+        )
+    };
+    [
+        book_x_o_arg(BOOK_X_ARG_ID, "X"),
+        book_x_o_arg(BOOK_O_ARG_ID, "O"),
+        book_randomness_arg(SIMULATE_CMD_ID),
+    ]
+}
+
+/// The `help` names who consults the book, and is continued with a reference to [`BOOK_CMD_ID`].
+fn book_arg(cmd_id: &str, id: &'static str, help: &str) -> Arg {
+    configurable_arg(cmd_id, id, &format!("{help}, see `{BOOK_CMD_ID} --help`."))
+        .value_parser(value_parser!(PathBuf))
+}
+
+fn book_randomness_arg(cmd_id: &str) -> Arg {
+    configurable_arg(
+        cmd_id,
+        BOOK_RANDOMNESS_ARG_ID,
+        "The probability in percent of picking a move from an opening book randomly, \
+        with the probabilities proportional to the weights of the moves, \
+        rather than picking the heaviest move.",
+    )
+    .value_parser(value_parser!(u8).range(0..=100))
+    .default_value("50")
+}
+
+/// The arguments specifying [`MctsOpts`].
+fn mcts_args(cmd_id: &str) -> [Arg; 3] {
+    [
+        configurable_arg(
+            cmd_id,
+            MCTS_ITERATIONS_ARG_ID,
+            "The number of playouts the MCTS AI searches with before each move.",
+        )
+        .value_parser(value_parser!(u32).range(1..))
+        .default_value(&*MctsOpts::DEFAULT_ITERATIONS.to_string().leak()),
+        configurable_arg(
+            cmd_id,
+            MCTS_TIME_ARG_ID,
+            &format!(
+                "The time in milliseconds the MCTS AI searches for before each move \
+                instead of --{MCTS_ITERATIONS_ARG_ID}. \
+                The moves then also depend on the speed of the machine, \
+                so the results are not reproducible with the seed."
+            ),
+        )
+        .value_parser(value_parser!(u64).range(1..))
+        .conflicts_with(MCTS_ITERATIONS_ARG_ID),
+        configurable_arg(
+            cmd_id,
+            MCTS_PLAYOUT_ARG_ID,
+            "How the MCTS AI plays out positions: occupying random cells, \
+            or winning and blocking when possible.",
+        )
+        .value_parser(
+            PossibleValuesParser::new(Playout::NAMES)
+                .map(|name| name.parse::<Playout>().expect("the name must be valid")),
+        )
+        .default_value(Playout::NAMES[0]),
+    ]
+}
+
+fn mcts_opts(arg_matches: &ArgMatches) -> MctsOpts {
+    MctsOpts {
+        budget: arg_matches.get_one::<u64>(MCTS_TIME_ARG_ID).map_or_else(
+            || Budget::Iterations(get(arg_matches, MCTS_ITERATIONS_ARG_ID)),
+            |&time| Budget::Time(Duration::from_millis(time)),
+        ),
+        playout: get(arg_matches, MCTS_PLAYOUT_ARG_ID),
+    }
+}
+
+fn tournament_command() -> Command {
+    Command::new(TOURNAMENT_CMD_ID)
+        .about(
+            "Play a tournament between AIs and humans without the user interface, \
+            and print the standings and the crosstable. \
+            Humans play via a server the tournament starts, \
+            to which they connect as to the dedicated server.",
+        )
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .arg(
+            configurable_arg(
+                TOURNAMENT_CMD_ID,
+                SYSTEM_ARG_ID,
+                "How the entrants are paired.",
+            )
+            .value_parser(
+                PossibleValuesParser::new(System::NAMES)
+                    .map(|name| name.parse::<System>().expect("the name must be valid")),
+            )
+            .default_value(System::NAMES[0]),
+        )
+        .arg(
+            configurable_arg(
+                TOURNAMENT_CMD_ID,
+                ENTRANTS_ARG_ID,
+                &format!(
+                    "The comma-separated entrants, in the order of their seeding, \
+                    the top seed first, either AIs ({names}), \
+                    or humans as the names of their accounts preceded by \"@\", e.g., \"@alice\". \
+                    There must be at least 2 entrants.",
+                    names = AiKind::NAMES.join(", ")
                 ),
+            )
+            .value_delimiter(',')
+            .value_parser(|s: &str| s.parse::<Entrant>())
+            .default_value(&*[AiKind::NAMES[0]; 4].join(",").leak()),
         )
         .arg(
-            Arg::new(LISTEN_ARG_ID)
-                .long(LISTEN_ARG_ID)
-                .required(false)
-                .requires(DEDICATED_ARG_ID)
+            configurable_arg(
+                TOURNAMENT_CMD_ID,
+                LISTEN_ARG_ID,
+                "The TCP socket address to listen on for the game clients of the human entrants, \
+                if any.",
+            )
+            .value_parser(value_parser!(SocketAddr))
+            .default_value("127.0.0.1:2020"),
+        )
+        .arg(
+            configurable_arg(
+                TOURNAMENT_CMD_ID,
+                ROUNDS_ARG_ID,
+                "The number of rounds in each game.",
+            )
+            .value_parser(value_parser!(u32).range(1..))
+            .default_value(&*State::DEFAULT_ROUNDS.to_string().leak()),
+        )
+        .arg(
+            configurable_arg(
+                TOURNAMENT_CMD_ID,
+                SWISS_ROUNDS_ARG_ID,
+                "The number of tournament rounds of the Swiss system. \
+                If not specified, it is the binary logarithm of the number of entrants, \
+                rounded up.",
+            )
+            .value_parser(value_parser!(u32).range(1..)),
+        )
+        .args(mcts_args(TOURNAMENT_CMD_ID))
+        .arg(book_arg(
+            TOURNAMENT_CMD_ID,
+            BOOK_ARG_ID,
+            "The opening book file all entrants consult first",
+        ))
+        .arg(book_randomness_arg(TOURNAMENT_CMD_ID))
+        .arg(
+            configurable_arg(
+                TOURNAMENT_CMD_ID,
+                SEED_ARG_ID,
+                "The seed from which the seeds of all AIs in all games are derived. \
+                If not specified, a seed is picked based on the current time \
+                and is printed with the results.",
+            )
+            .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            configurable_arg(
+                TOURNAMENT_CMD_ID,
+                FORMAT_ARG_ID,
+                "The format of the results, the CSV format contains only the crosstable.",
+            )
+            .value_parser(PossibleValuesParser::new(ReportFormat::NAMES).map(|name| {
+                name.parse::<ReportFormat>()
+                    .expect("the name must be valid")
+            }))
+            .default_value(ReportFormat::NAMES[0]),
+        )
+}
+
+fn analyze_command() -> Command {
+    Command::new(ANALYZE_CMD_ID)
+        .about(
+            "Print the game-theoretic result of a position, \
+            the values of all possible moves, and the principal variation.",
+        )
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .arg(
+            Arg::new(POSITION_ARG_ID)
+                .required(true)
                 .action(ArgAction::Set)
-                .value_parser(value_parser!(SocketAddr))
-                .default_value("127.0.0.1:2020")
+                .value_parser(|s: &str| s.parse::<Position>())
                 .help(
-                    "The TCP socket address to listen on for game clients \
-                    and web console requests.",
+                    "The position in the format \"<rows> [<mark to move>]\", \
+                    where rows from top to bottom are separated with \"/\", \
+                    and each cell is either \"X\", \"O\", or \".\" if empty, \
+                    e.g., \"X.O/.X./... O\". \
+                    If the mark to move is omitted, it is inferred with X moving first.",
                 ),
         )
+        .arg(
+            configurable_arg(
+                ANALYZE_CMD_ID,
+                TABLEBASE_ARG_ID,
+                &format!(
+                    "The tablebase file to look the position up in instead of solving it, \
+                    see `{TABLEBASE_CMD_ID} --help`."
+                ),
+            )
+            .value_parser(value_parser!(PathBuf)),
+        )
+}
+
+fn engine_command() -> Command {
+    Command::new(ENGINE_CMD_ID)
+        .about(
+            "Run the reference external engine, which reads commands from the standard input \
+            and writes replies to the standard output, one per line. \
+            The host starts a session with \"init <board size>\", to which the engine replies \
+            \"ready\". Then, for each move, the host sends \"position <position>\", \
+            with the position in the format of the `analyze` command, \
+            followed by \"go <milliseconds>\", to which the engine replies \"bestmove <cell>\" \
+            within the specified time, e.g., \"bestmove b2\". \
+            The host ends the session with \"quit\". \
+            The engine may also reply with \"info <text>\" at any time, which the host ignores.",
+        )
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .arg(
+            configurable_arg(
+                ENGINE_CMD_ID,
+                SEED_ARG_ID,
+                "The seed of the engine. \
+                If not specified, a seed is picked based on the current time.",
+            )
+            .value_parser(value_parser!(u64)),
+        )
+}
+
+fn train_command() -> Command {
+    Command::new(TRAIN_CMD_ID)
+        .about(
+            "Train the MENACE AI by playing games against itself, continuing from the latest \
+            epoch, if any. After each epoch, the AI is saved in the data directory, \
+            from which it may be chosen in the interactive mode, \
+            and the results of its games against the random AI are printed.",
+        )
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .arg(
+            configurable_arg(
+                TRAIN_CMD_ID,
+                EPOCHS_ARG_ID,
+                "The number of epochs to train.",
+            )
+            .value_parser(value_parser!(u32).range(1..))
+            .default_value("10"),
+        )
+        .arg(
+            configurable_arg(
+                TRAIN_CMD_ID,
+                GAMES_ARG_ID,
+                "The number of games in each epoch.",
+            )
+            .value_parser(value_parser!(u64).range(1..))
+            .default_value("500"),
+        )
+        .arg(
+            configurable_arg(
+                TRAIN_CMD_ID,
+                SEED_ARG_ID,
+                "The seed from which the seeds of all AIs in all games are derived. \
+                If not specified, a seed is picked based on the current time.",
+            )
+            .value_parser(value_parser!(u64)),
+        )
+}
+
+fn tablebase_command() -> Command {
+    Command::new(TABLEBASE_CMD_ID)
+        .about(format!(
+            "Generate a tablebase, which is a compact file with the game-theoretic results \
+            of all positions reachable on a board, and the numbers of plies until the results. \
+            Positions that are the same up to a symmetry of the board share an entry. \
+            The game is played on the {size}x{size} board, so tablebases of other sizes \
+            cannot be used by the AIs or by `{ANALYZE_CMD_ID}`.",
+            size = Board::SIZE
+        ))
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .arg(
+            configurable_arg(TABLEBASE_CMD_ID, SIZE_ARG_ID, "The size of the board.")
+                .value_parser(
+                    value_parser!(u8)
+                        .range(1..=i64::try_from(tablebase::Tablebase::MAX_SIZE).unwrap()),
+                )
+                .default_value(&*Board::SIZE.to_string().leak()),
+        )
+        .arg(
+            configurable_arg(
+                TABLEBASE_CMD_ID,
+                OUTPUT_ARG_ID,
+                "The file to write the tablebase to. \
+                If not specified, the tablebase is written to the data directory.",
+            )
+            .value_parser(value_parser!(PathBuf)),
+        )
+}
+
+fn book_command() -> Command {
+    Command::new(BOOK_CMD_ID)
+        .about(
+            "Build an opening book, which is a file with a line per position: \
+            the hexadecimal 64-bit XXH3 hash of the position in the format of the `analyze` \
+            command, followed by the moves with their weights, e.g., \"0123456789abcdef b2:3 a1:1\". \
+            An AI consulting the book picks the moves with the probabilities \
+            proportional to their weights.",
+        )
+        .disable_help_flag(true)
+        .arg(help_arg())
+        .arg(
+            configurable_arg(
+                BOOK_CMD_ID,
+                SOURCE_ARG_ID,
+                "Where the moves come from: \
+                the best moves as per the tablebase, all weighted equally, \
+                or the moves the AI makes when playing against itself, \
+                weighted by how often they are made in won and drawn rounds.",
+            )
+            .value_parser(
+                PossibleValuesParser::new(Source::NAMES)
+                    .map(|name| name.parse::<Source>().expect("the name must be valid")),
+            )
+            .default_value(Source::NAMES[0]),
+        )
+        .arg(
+            configurable_arg(
+                BOOK_CMD_ID,
+                PLIES_ARG_ID,
+                "The number of the first moves of a round covered by the book.",
+            )
+            .value_parser(value_parser!(u8).range(1..))
+            .default_value("4"),
+        )
+        .arg(
+            configurable_arg(BOOK_CMD_ID, AI_ARG_ID, "The AI playing against itself.")
+                .value_parser(
+                    PossibleValuesParser::new(AiKind::NAMES)
+                        .map(|name| name.parse::<AiKind>().expect("the name must be valid")),
+                )
+                .default_value(AiKind::NAMES[1]),
+        )
+        .arg(
+            configurable_arg(
+                BOOK_CMD_ID,
+                GAMES_ARG_ID,
+                "The number of games the AI plays against itself.",
+            )
+            .value_parser(value_parser!(u64).range(1..))
+            .default_value("1000"),
+        )
+        .arg(
+            configurable_arg(
+                BOOK_CMD_ID,
+                SEED_ARG_ID,
+                "The seed from which the seeds of all AIs in all games are derived. \
+                If not specified, a seed is picked based on the current time.",
+            )
+            .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            configurable_arg(
+                BOOK_CMD_ID,
+                OUTPUT_ARG_ID,
+                "The file to write the book to. \
+                If not specified, the book is written to the data directory.",
+            )
+            .value_parser(value_parser!(PathBuf)),
+        )
+}
+
+/// The sources of option values other than the command line.
+struct Sources<'a> {
+    env: &'a dyn Fn(&str) -> Option<OsString>,
+    read_file: &'a dyn Fn(&Path) -> io::Result<String>,
+}
+
+impl Sources<'_> {
+    fn system() -> Sources<'static> {
+        Sources {
+            env: &|name| env::var_os(name),
+            read_file: &|path| fs::read_to_string(path),
+        }
+    }
+}
+
+/// The configuration file parsed as a table of command tables,
+/// each containing the values of options by their names.
+#[derive(Debug, Default)]
+struct Config(Table);
+
+impl Config {
+    fn load(explicit_path: Option<&PathBuf>, sources: &Sources) -> Result<Self, Error> {
+        let (path, required) = if let Some(path) = explicit_path {
+            (path.clone(), true)
+        } else if let Some(path) = (sources.env)(&config_env_var()) {
+            (PathBuf::from(path), true)
+        } else if let Some(dir) = dirs::config_dir(sources.env) {
+            (dir.join(CONFIG_FILE_NAME), false)
+        } else {
+            return Ok(Self::default());
+        };
+        let content = match (sources.read_file)(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => {
+                return Ok(Self::default());
+            }
+            Err(e) => {
+                return Err(Self::err(ErrorKind::Io, &path, &e));
+            }
+        };
+        let table = toml::from_str::<Table>(&content)
+            .map_err(|e| Self::err(ErrorKind::InvalidValue, &path, &e))?;
+        let cmd = command();
+        for (cmd_id, cmd_table) in &table {
+            let configurable_arg_ids = cmd
+                .find_subcommand(cmd_id)
+                .ok_or_else(|| {
+                    Self::err(
+                        ErrorKind::UnknownArgument,
+                        &path,
+                        &format!("unknown command `{cmd_id}`"),
+                    )
+                })?
+                .get_arguments()
+                .filter(|arg| is_configurable(arg))
+                .map(|arg| arg.get_id().as_str())
+                .collect::<Vec<_>>();
+            let cmd_table = cmd_table.as_table().ok_or_else(|| {
+                Self::err(
+                    ErrorKind::InvalidValue,
+                    &path,
+                    &format!("`{cmd_id}` must be a table"),
+                )
+            })?;
+            for (arg_id, value) in cmd_table {
+                if !configurable_arg_ids.contains(&arg_id.as_str()) {
+                    return Err(Self::err(
+                        ErrorKind::UnknownArgument,
+                        &path,
+                        &format!("unknown option `{cmd_id}.{arg_id}`"),
+                    ));
+                }
+                if Self::to_arg_value(value).is_none() {
+                    return Err(Self::err(
+                        ErrorKind::InvalidValue,
+                        &path,
+                        &format!("`{cmd_id}.{arg_id}` must be a string, a number, or a boolean"),
+                    ));
+                }
+            }
+        }
+        Ok(Self(table))
+    }
+
+    fn get(&self, cmd_id: &str, arg_id: &str) -> Option<String> {
+        self.0
+            .get(cmd_id)
+            .and_then(Value::as_table)
+            .and_then(|cmd_table| cmd_table.get(arg_id))
+            .and_then(Self::to_arg_value)
+    }
+
+    fn to_arg_value(value: &Value) -> Option<String> {
+        match value {
+            Value::String(value) => Some(value.clone()),
+            Value::Integer(value) => Some(value.to_string()),
+            Value::Float(value) => Some(value.to_string()),
+            Value::Boolean(value) => Some(value.to_string()),
+            Value::Datetime(_) | Value::Array(_) | Value::Table(_) => None,
+        }
+    }
+
+    fn err(kind: ErrorKind, path: &Path, e: &dyn Display) -> Error {
+        Error::raw(
+            kind,
+            format!(
+                "invalid configuration file \"{path}\": {e}\n",
+                path = path.display(),
+                e = e.to_string().trim_end()
+            ),
+        )
+        .format(&mut command())
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum ParsedArgs {
-    Interactive,
-    Dedicated(DedicatedArgs),
+    Interactive(InteractiveArgs),
+    Play(PlayArgs),
+    Server(ServerArgs),
+    Simulate(SimulateArgs),
+    Tournament(TournamentArgs),
+    Analyze(AnalyzeArgs),
+    Engine(EngineArgs),
+    Train(TrainArgs),
+    Tablebase(TablebaseArgs),
+    Book(BookArgs),
 }
 
 impl ParsedArgs {
+    /// Takes into account the environment variables and the configuration file,
+    /// as described in the help.
+    ///
     /// # Errors
     ///
-    /// When [`Command::try_get_matches_from()`] errors.
+    /// When [`Command::try_get_matches_from()`] errors,
+    /// or the configuration file cannot be read or is invalid.
     ///
     /// # Panics
     ///
@@ -108,29 +927,488 @@ impl ParsedArgs {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        let arg_matches = command().try_get_matches_from(args)?;
-        if arg_matches.get_flag(DEDICATED_ARG_ID) {
-            let listen: SocketAddr = arg_matches
-                .get_one::<SocketAddr>(LISTEN_ARG_ID)
-                .map_or_else(
-                    || panic!("`{LISTEN_ARG_ID}` must be present"),
-                    ToOwned::to_owned,
-                );
-            Ok(Dedicated(DedicatedArgs { listen }))
+        Self::try_from_sources(args, &Sources::system())
+    }
+
+    /// Parses the command line `args`, then for each [`configurable_arg()`]
+    /// not specified in the command line, appends the value from the `sources`, if any,
+    /// to the command line, and parses it again.
+    fn try_from_sources<I, T>(args: I, sources: &Sources) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut args = args.into_iter().map(Into::into).collect::<Vec<OsString>>();
+        let mut cmd = command();
+        let arg_matches = cmd.try_get_matches_from_mut(&args)?;
+        let (cmd_id, cmd_matches) = if let Some((cmd_id, cmd_matches)) = arg_matches.subcommand() {
+            (cmd_id, Some(cmd_matches))
         } else {
-            Ok(Interactive)
+            args.push(OsString::from(INTERACTIVE_CMD_ID));
+            (INTERACTIVE_CMD_ID, None)
+        };
+        let config = Config::load(
+            cmd_matches
+                .unwrap_or(&arg_matches)
+                .get_one::<PathBuf>(CONFIG_ARG_ID),
+            sources,
+        )?;
+        let configurable_args = cmd
+            .find_subcommand(cmd_id)
+            .expect("the command must exist")
+            .get_arguments()
+            .filter(|arg| is_configurable(arg));
+        for arg in configurable_args {
+            let arg_id = arg.get_id().as_str();
+            let specified = cmd_matches.map_or(false, |cmd_matches| {
+                cmd_matches.value_source(arg_id) == Some(ValueSource::CommandLine)
+            });
+            if specified {
+                continue;
+            }
+            let value = (sources.env)(&env_var(cmd_id, arg_id))
+                .or_else(|| config.get(cmd_id, arg_id).map(OsString::from));
+            if let Some(value) = value {
+                let mut arg = OsString::from(format!("--{}=", arg.get_long().unwrap()));
+                arg.push(value);
+                args.push(arg);
+            }
         }
+        Ok(Self::from_matches(&command().try_get_matches_from(args)?))
     }
+
+    fn from_matches(arg_matches: &ArgMatches) -> Self {
+        match arg_matches.subcommand() {
+            Some((SERVER_CMD_ID, arg_matches)) => Server(ServerArgs {
+                listen: get(arg_matches, LISTEN_ARG_ID),
+                rating_period: get(arg_matches, RATING_PERIOD_ARG_ID),
+            }),
+            Some((SIMULATE_CMD_ID, arg_matches)) => {
+                Simulate(SimulateArgs::from_matches(arg_matches))
+            }
+            Some((TOURNAMENT_CMD_ID, arg_matches)) => {
+                Tournament(TournamentArgs::from_matches(arg_matches))
+            }
+            Some((ANALYZE_CMD_ID, arg_matches)) => Analyze(AnalyzeArgs {
+                position: get(arg_matches, POSITION_ARG_ID),
+                tablebase: arg_matches.get_one::<PathBuf>(TABLEBASE_ARG_ID).cloned(),
+            }),
+            Some((ENGINE_CMD_ID, arg_matches)) => Engine(EngineArgs {
+                seed: arg_matches.get_one::<u64>(SEED_ARG_ID).copied(),
+            }),
+            Some((TRAIN_CMD_ID, arg_matches)) => Train(TrainArgs {
+                epochs: get(arg_matches, EPOCHS_ARG_ID),
+                games: get(arg_matches, GAMES_ARG_ID),
+                seed: arg_matches.get_one::<u64>(SEED_ARG_ID).copied(),
+            }),
+            Some((TABLEBASE_CMD_ID, arg_matches)) => Tablebase(TablebaseArgs {
+                size: usize::from(get::<u8>(arg_matches, SIZE_ARG_ID)),
+                output: arg_matches.get_one::<PathBuf>(OUTPUT_ARG_ID).cloned(),
+            }),
+            Some((BOOK_CMD_ID, arg_matches)) => Book(BookArgs {
+                source: get(arg_matches, SOURCE_ARG_ID),
+                plies: usize::from(get::<u8>(arg_matches, PLIES_ARG_ID)),
+                ai: get(arg_matches, AI_ARG_ID),
+                games: get(arg_matches, GAMES_ARG_ID),
+                seed: arg_matches.get_one::<u64>(SEED_ARG_ID).copied(),
+                output: arg_matches.get_one::<PathBuf>(OUTPUT_ARG_ID).cloned(),
+            }),
+            Some((INTERACTIVE_CMD_ID, arg_matches)) => {
+                Interactive(InteractiveArgs::from_matches(arg_matches))
+            }
+            Some((PLAY_CMD_ID, arg_matches)) => Play(PlayArgs {
+                interactive: InteractiveArgs::from_matches(arg_matches),
+                rounds: get(arg_matches, ROUNDS_ARG_ID),
+                ai: get(arg_matches, AI_ARG_ID),
+            }),
+            Some((cmd_id, _)) => panic!("unexpected command `{cmd_id}`"),
+            None => panic!("a command must be present"),
+        }
+    }
+}
+
+fn get<T>(arg_matches: &ArgMatches, id: &str) -> T
+where
+    T: Clone + Send + Sync + 'static,
+{
+    arg_matches
+        .get_one::<T>(id)
+        .map_or_else(|| panic!("`{id}` must be present"), ToOwned::to_owned)
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub struct DedicatedArgs {
+pub struct InteractiveArgs {
+    seed: Option<u64>,
+    theme: String,
+    rating_period: RatingPeriod,
+    book: Option<PathBuf>,
+    book_randomness: u8,
+}
+
+impl InteractiveArgs {
+    fn from_matches(arg_matches: &ArgMatches) -> Self {
+        Self {
+            seed: arg_matches.get_one::<u64>(SEED_ARG_ID).copied(),
+            theme: get(arg_matches, THEME_ARG_ID),
+            rating_period: get(arg_matches, RATING_PERIOD_ARG_ID),
+            book: arg_matches.get_one::<PathBuf>(BOOK_ARG_ID).cloned(),
+            book_randomness: get(arg_matches, BOOK_RANDOMNESS_ARG_ID),
+        }
+    }
+
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// The name of the initial theme, which is not necessarily known,
+    /// as user-defined themes are loaded only in the interactive mode.
+    #[must_use]
+    pub fn theme(&self) -> &str {
+        &self.theme
+    }
+
+    #[must_use]
+    pub fn rating_period(&self) -> RatingPeriod {
+        self.rating_period
+    }
+
+    /// The opening book of the AI of Vs. AI games.
+    #[must_use]
+    pub fn book(&self) -> Option<&PathBuf> {
+        self.book.as_ref()
+    }
+
+    /// In percent.
+    #[must_use]
+    pub fn book_randomness(&self) -> u8 {
+        self.book_randomness
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct PlayArgs {
+    interactive: InteractiveArgs,
+    rounds: u32,
+    ai: AiKind,
+}
+
+impl PlayArgs {
+    #[must_use]
+    pub fn interactive(&self) -> &InteractiveArgs {
+        &self.interactive
+    }
+
+    #[must_use]
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub(crate) fn ai(&self) -> AiKind {
+        self.ai
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct ServerArgs {
     listen: SocketAddr,
+    rating_period: RatingPeriod,
 }
 
-impl DedicatedArgs {
+impl ServerArgs {
     #[must_use]
-    pub fn _listen(&self) -> SocketAddr {
+    pub fn listen(&self) -> SocketAddr {
         self.listen
     }
+
+    /// How the games hosted by the server are rated.
+    #[must_use]
+    pub fn rating_period(&self) -> RatingPeriod {
+        self.rating_period
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct SimulateArgs {
+    games: u64,
+    rounds: u32,
+    ais: [AiKind; 2],
+    engines: [Option<EngineCommand>; 2],
+    engine_time: Duration,
+    books: [Option<PathBuf>; 2],
+    book_randomness: u8,
+    mcts: MctsOpts,
+    seed: Option<u64>,
+    threads: Option<NonZeroUsize>,
+    format: ReportFormat,
+}
+
+impl SimulateArgs {
+    fn from_matches(arg_matches: &ArgMatches) -> Self {
+        Self {
+            games: get(arg_matches, GAMES_ARG_ID),
+            rounds: get(arg_matches, ROUNDS_ARG_ID),
+            ais: [get(arg_matches, AI_X_ARG_ID), get(arg_matches, AI_O_ARG_ID)],
+            engines: [ENGINE_X_ARG_ID, ENGINE_O_ARG_ID]
+                .map(|id| arg_matches.get_one::<EngineCommand>(id).cloned()),
+            engine_time: Duration::from_millis(get(arg_matches, ENGINE_TIME_ARG_ID)),
+            books: [BOOK_X_ARG_ID, BOOK_O_ARG_ID]
+                .map(|id| arg_matches.get_one::<PathBuf>(id).cloned()),
+            book_randomness: get(arg_matches, BOOK_RANDOMNESS_ARG_ID),
+            mcts: mcts_opts(arg_matches),
+            seed: arg_matches.get_one::<u64>(SEED_ARG_ID).copied(),
+            threads: arg_matches.get_one::<NonZeroUsize>(THREADS_ARG_ID).copied(),
+            format: get(arg_matches, FORMAT_ARG_ID),
+        }
+    }
+
+    #[must_use]
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    #[must_use]
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub(crate) fn ais(&self) -> [AiKind; 2] {
+        self.ais
+    }
+
+    /// The external engines indexed by [`PlayerId`](crate::game::PlayerId),
+    /// which play instead of the corresponding [`Self::ais()`].
+    pub(crate) fn engines(&self) -> &[Option<EngineCommand>; 2] {
+        &self.engines
+    }
+
+    #[must_use]
+    pub fn engine_time(&self) -> Duration {
+        self.engine_time
+    }
+
+    /// The opening books indexed by [`PlayerId`](crate::game::PlayerId).
+    #[must_use]
+    pub fn books(&self) -> &[Option<PathBuf>; 2] {
+        &self.books
+    }
+
+    /// In percent.
+    #[must_use]
+    pub fn book_randomness(&self) -> u8 {
+        self.book_randomness
+    }
+
+    pub(crate) fn mcts(&self) -> MctsOpts {
+        self.mcts
+    }
+
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    #[must_use]
+    pub fn threads(&self) -> Option<NonZeroUsize> {
+        self.threads
+    }
+
+    pub(crate) fn format(&self) -> ReportFormat {
+        self.format
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct TournamentArgs {
+    system: System,
+    entrants: Vec<Entrant>,
+    listen: SocketAddr,
+    rounds: u32,
+    swiss_rounds: Option<u32>,
+    mcts: MctsOpts,
+    book: Option<PathBuf>,
+    book_randomness: u8,
+    seed: Option<u64>,
+    format: ReportFormat,
+}
+
+impl TournamentArgs {
+    fn from_matches(arg_matches: &ArgMatches) -> Self {
+        Self {
+            system: get(arg_matches, SYSTEM_ARG_ID),
+            entrants: arg_matches
+                .get_many::<Entrant>(ENTRANTS_ARG_ID)
+                .expect("the argument must have a default value")
+                .cloned()
+                .collect(),
+            listen: get(arg_matches, LISTEN_ARG_ID),
+            rounds: get(arg_matches, ROUNDS_ARG_ID),
+            swiss_rounds: arg_matches.get_one::<u32>(SWISS_ROUNDS_ARG_ID).copied(),
+            mcts: mcts_opts(arg_matches),
+            book: arg_matches.get_one::<PathBuf>(BOOK_ARG_ID).cloned(),
+            book_randomness: get(arg_matches, BOOK_RANDOMNESS_ARG_ID),
+            seed: arg_matches.get_one::<u64>(SEED_ARG_ID).copied(),
+            format: get(arg_matches, FORMAT_ARG_ID),
+        }
+    }
+
+    pub(crate) fn system(&self) -> System {
+        self.system
+    }
+
+    pub(crate) fn entrants(&self) -> &[Entrant] {
+        &self.entrants
+    }
+
+    /// Where the clients of the human entrants connect to.
+    #[must_use]
+    pub fn listen(&self) -> SocketAddr {
+        self.listen
+    }
+
+    #[must_use]
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    #[must_use]
+    pub fn swiss_rounds(&self) -> Option<u32> {
+        self.swiss_rounds
+    }
+
+    pub(crate) fn mcts(&self) -> MctsOpts {
+        self.mcts
+    }
+
+    /// The opening book all entrants consult first.
+    #[must_use]
+    pub fn book(&self) -> Option<&PathBuf> {
+        self.book.as_ref()
+    }
+
+    /// In percent.
+    #[must_use]
+    pub fn book_randomness(&self) -> u8 {
+        self.book_randomness
+    }
+
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub(crate) fn format(&self) -> ReportFormat {
+        self.format
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct AnalyzeArgs {
+    position: Position,
+    tablebase: Option<PathBuf>,
+}
+
+impl AnalyzeArgs {
+    pub(crate) fn position(&self) -> &Position {
+        &self.position
+    }
+
+    #[must_use]
+    pub fn tablebase(&self) -> Option<&PathBuf> {
+        self.tablebase.as_ref()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct EngineArgs {
+    seed: Option<u64>,
+}
+
+impl EngineArgs {
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct TrainArgs {
+    epochs: u32,
+    games: u64,
+    seed: Option<u64>,
+}
+
+impl TrainArgs {
+    #[must_use]
+    pub fn epochs(&self) -> u32 {
+        self.epochs
+    }
+
+    #[must_use]
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct TablebaseArgs {
+    size: usize,
+    output: Option<PathBuf>,
+}
+
+impl TablebaseArgs {
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[must_use]
+    pub fn output(&self) -> Option<&PathBuf> {
+        self.output.as_ref()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct BookArgs {
+    source: Source,
+    plies: usize,
+    ai: AiKind,
+    games: u64,
+    seed: Option<u64>,
+    output: Option<PathBuf>,
+}
+
+impl BookArgs {
+    pub(crate) fn source(&self) -> Source {
+        self.source
+    }
+
+    #[must_use]
+    pub fn plies(&self) -> usize {
+        self.plies
+    }
+
+    pub(crate) fn ai(&self) -> AiKind {
+        self.ai
+    }
+
+    #[must_use]
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    #[must_use]
+    pub fn output(&self) -> Option<&PathBuf> {
+        self.output.as_ref()
+    }
 }
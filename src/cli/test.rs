@@ -9,26 +9,269 @@ fn command() {
 }
 
 mod ParsedArgs {
-    use std::net::ToSocketAddrs;
+    use core::{num::NonZeroUsize, time::Duration};
+    use std::{
+        collections::HashMap,
+        ffi::OsString,
+        io,
+        net::ToSocketAddrs,
+        path::{Path, PathBuf},
+    };
 
-    use clap::error::ErrorKind;
+    use clap::error::{Error, ErrorKind};
     use pretty_assertions_sorted::assert_eq;
     use test_case::test_case;
 
-    use crate::{cli::DedicatedArgs, Dedicated, ParsedArgs};
+    use crate::{
+        ai::{
+            mcts::{Budget, MctsOpts, Playout},
+            AiKind,
+        },
+        book::Source,
+        cli::{
+            AnalyzeArgs, BookArgs, EngineArgs, InteractiveArgs, PlayArgs, ServerArgs, SimulateArgs,
+            Sources, TablebaseArgs, TournamentArgs, TrainArgs,
+        },
+        rating::RatingPeriod,
+        sim::ReportFormat,
+        tournament::{Entrant, System},
+        Analyze, Book, Engine, Interactive, ParsedArgs, Play, Server, Simulate, Tablebase,
+        Tournament, Train,
+    };
+
+    /// Parses `args` with the environment variables `env`,
+    /// and the files `files` by their paths.
+    fn parse(
+        args: &[&str],
+        env: &[(&str, &str)],
+        files: &[(&str, &str)],
+    ) -> Result<ParsedArgs, Error> {
+        let env = env
+            .iter()
+            .map(|(name, value)| ((*name).to_owned(), OsString::from(value)))
+            .collect::<HashMap<_, _>>();
+        let files = files
+            .iter()
+            .map(|(path, content)| (Path::new(path).to_owned(), (*content).to_owned()))
+            .collect::<HashMap<_, _>>();
+        ParsedArgs::try_from_sources(
+            args,
+            &Sources {
+                env: &|name| env.get(name).cloned(),
+                read_file: &|path| {
+                    files
+                        .get(path)
+                        .cloned()
+                        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+                },
+            },
+        )
+    }
+
+    fn interactive(seed: Option<u64>, theme: &str, rating_period: RatingPeriod) -> ParsedArgs {
+        Interactive(InteractiveArgs {
+            seed,
+            theme: theme.to_owned(),
+            rating_period,
+            book: None,
+            book_randomness: 50,
+        })
+    }
+
+    fn play(seed: Option<u64>, rounds: u32, ai: AiKind) -> ParsedArgs {
+        Play(PlayArgs {
+            interactive: InteractiveArgs {
+                seed,
+                theme: "dark".to_owned(),
+                rating_period: RatingPeriod::Match,
+                book: None,
+                book_randomness: 50,
+            },
+            rounds,
+            ai,
+        })
+    }
+
+    fn server(listen: &str, rating_period: RatingPeriod) -> ParsedArgs {
+        Server(ServerArgs {
+            listen: listen.to_socket_addrs().unwrap().next().unwrap(),
+            rating_period,
+        })
+    }
 
     #[test_case(
         &[""],
-        &ParsedArgs::Interactive)]
+        &interactive(None, "dark", RatingPeriod::Match))]
+    #[test_case(
+        &["", "interactive"],
+        &interactive(None, "dark", RatingPeriod::Match))]
+    #[test_case(
+        &["", "interactive", "--seed", "42"],
+        &interactive(Some(42), "dark", RatingPeriod::Match))]
+    #[test_case(
+        &["", "interactive", "--theme", "my theme"],
+        &interactive(None, "my theme", RatingPeriod::Match))]
+    #[test_case(
+        &["", "interactive", "--rating-period", "round"],
+        &interactive(None, "dark", RatingPeriod::Round))]
+    #[test_case(
+        &["", "interactive", "--book", "book.txt", "--book-randomness", "0"],
+        &Interactive(InteractiveArgs {
+            seed: None,
+            theme: "dark".to_owned(),
+            rating_period: RatingPeriod::Match,
+            book: Some(PathBuf::from("book.txt")),
+            book_randomness: 0,
+        }))]
+    #[test_case(
+        &["", "play"],
+        &play(None, 5, AiKind::Random))]
+    #[test_case(
+        &["", "play", "--seed", "42", "--rounds", "3", "--ai", "tablebase"],
+        &play(Some(42), 3, AiKind::Tablebase))]
+    #[test_case(
+        &["", "server"],
+        &server("127.0.0.1:2020", RatingPeriod::Match))]
+    #[test_case(
+        &["", "server", "--listen", "[::]:2020", "--rating-period", "round"],
+        &server("[::]:2020", RatingPeriod::Round))]
+    #[test_case(
+        &["", "simulate"],
+        &Simulate(
+            SimulateArgs {
+                games: 1000,
+                rounds: 5,
+                ais: [AiKind::Random, AiKind::Random],
+                engines: [None, None],
+                engine_time: Duration::from_millis(100),
+                books: [None, None],
+                book_randomness: 50,
+                mcts: MctsOpts::DEFAULT,
+                seed: None,
+                threads: None,
+                format: ReportFormat::Text,
+            }
+        ))]
+    #[test_case(
+        &["", "simulate", "--games", "7", "--rounds", "3", "--ai-x", "random", "--seed", "42",
+            "--threads", "2", "--format", "csv", "--engine-o", " ./engine  --fast", "--engine-time", "50",
+            "--book-x", "book.txt", "--book-randomness", "0", "--mcts-iterations", "50",
+            "--mcts-playout", "random"],
+        &Simulate(
+            SimulateArgs {
+                games: 7,
+                rounds: 3,
+                ais: [AiKind::Random, AiKind::Random],
+                engines: [None, Some("./engine --fast".parse().unwrap())],
+                engine_time: Duration::from_millis(50),
+                books: [Some(PathBuf::from("book.txt")), None],
+                book_randomness: 0,
+                mcts: MctsOpts {
+                    budget: Budget::Iterations(50),
+                    playout: Playout::Random,
+                },
+                seed: Some(42),
+                threads: NonZeroUsize::new(2),
+                format: ReportFormat::Csv,
+            }
+        ))]
+    #[test_case(
+        &["", "tournament"],
+        &Tournament(
+            TournamentArgs {
+                system: System::RoundRobin,
+                entrants: vec![Entrant::Ai(AiKind::Random); 4],
+                listen: "127.0.0.1:2020".parse().unwrap(),
+                rounds: 5,
+                swiss_rounds: None,
+                mcts: MctsOpts::DEFAULT,
+                book: None,
+                book_randomness: 50,
+                seed: None,
+                format: ReportFormat::Text,
+            }
+        ))]
+    #[test_case(
+        &["", "tournament", "--system", "swiss", "--entrants", "random,@alice,mcts",
+            "--listen", "0.0.0.0:2021",
+            "--rounds", "3", "--swiss-rounds", "2", "--seed", "42", "--format", "json",
+            "--mcts-time", "20", "--book", "book.txt",
+            "--book-randomness", "0"],
+        &Tournament(
+            TournamentArgs {
+                system: System::Swiss,
+                entrants: vec![
+                    Entrant::Ai(AiKind::Random),
+                    Entrant::Human("alice".to_owned()),
+                    Entrant::Ai(AiKind::Mcts),
+                ],
+                listen: "0.0.0.0:2021".parse().unwrap(),
+                rounds: 3,
+                swiss_rounds: Some(2),
+                mcts: MctsOpts {
+                    budget: Budget::Time(Duration::from_millis(20)),
+                    playout: Playout::Heuristic,
+                },
+                book: Some(PathBuf::from("book.txt")),
+                book_randomness: 0,
+                seed: Some(42),
+                format: ReportFormat::Json,
+            }
+        ))]
+    #[test_case(
+        &["", "analyze", "X.O/.X./..."],
+        &Analyze(
+            AnalyzeArgs {
+                position: "X.O/.X./... O".parse().unwrap(),
+                tablebase: None,
+            }
+        ))]
     #[test_case(
-        &["", "--TODO-dedicated", "--TODO-listen", "[::]:2020"],
-        &Dedicated(
-            DedicatedArgs {
-                listen: "[::]:2020".to_socket_addrs().unwrap().next().unwrap()
+        &["", "analyze", "X.O/.X./...", "--tablebase", "3x3.ttb"],
+        &Analyze(
+            AnalyzeArgs {
+                position: "X.O/.X./... O".parse().unwrap(),
+                tablebase: Some(PathBuf::from("3x3.ttb")),
             }
         ))]
+    #[test_case(
+        &["", "book"],
+        &Book(BookArgs {
+            source: Source::Tablebase,
+            plies: 4,
+            ai: AiKind::Mcts,
+            games: 1000,
+            seed: None,
+            output: None,
+        }))]
+    #[test_case(
+        &["", "book", "--source", "self-play", "--plies", "2", "--ai", "random", "--games", "10",
+            "--seed", "42", "--output", "book.txt"],
+        &Book(BookArgs {
+            source: Source::SelfPlay,
+            plies: 2,
+            ai: AiKind::Random,
+            games: 10,
+            seed: Some(42),
+            output: Some(PathBuf::from("book.txt")),
+        }))]
+    #[test_case(
+        &["", "tablebase"],
+        &Tablebase(TablebaseArgs { size: 3, output: None }))]
+    #[test_case(
+        &["", "tablebase", "--size", "4", "--output", "4x4.ttb"],
+        &Tablebase(TablebaseArgs { size: 4, output: Some(PathBuf::from("4x4.ttb")) }))]
+    #[test_case(
+        &["", "engine", "--seed", "42"],
+        &Engine(EngineArgs { seed: Some(42) }))]
+    #[test_case(
+        &["", "train"],
+        &Train(TrainArgs { epochs: 10, games: 500, seed: None }))]
+    #[test_case(
+        &["", "train", "--epochs", "3", "--games", "100", "--seed", "42"],
+        &Train(TrainArgs { epochs: 3, games: 100, seed: Some(42) }))]
     fn from_iterator__Ok(args: &[&str], expected: &ParsedArgs) {
-        assert_eq!(ParsedArgs::try_from_iterator(args).unwrap(), *expected);
+        assert_eq!(parse(args, &[], &[]).unwrap(), *expected);
     }
 
     #[test_case(
@@ -44,20 +287,261 @@ mod ParsedArgs {
         &["", "--help"],
         ErrorKind::DisplayHelp)]
     #[test_case(
-        &["", "--TODO-dedicated", "--TODO-listen", "[::]"],
+        &["", "server", "--help"],
+        ErrorKind::DisplayHelp)]
+    #[test_case(
+        &["", "server", "--listen", "[::]"],
         ErrorKind::ValueValidation)]
     #[test_case(
         &["", "--unknown"],
         ErrorKind::UnknownArgument)]
     #[test_case(
-        &["", "--TODO-listen", "0.0.0.0:2020"],
+        &["", "--listen", "0.0.0.0:2020"],
+        ErrorKind::UnknownArgument)]
+    #[test_case(
+        &["", "--TODO-dedicated"],
+        ErrorKind::UnknownArgument)]
+    #[test_case(
+        &["", "unknown"],
+        ErrorKind::InvalidSubcommand)]
+    #[test_case(
+        &["", "--seed", "42"],
+        ErrorKind::UnknownArgument)]
+    #[test_case(
+        &["", "interactive", "--seed", "x"],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "play", "--rounds", "0"],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "play", "--ai", "unknown"],
+        ErrorKind::InvalidValue)]
+    #[test_case(
+        &["", "simulate", "--book-randomness", "101"],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "tournament", "--mcts-iterations", "50", "--mcts-time", "20"],
+        ErrorKind::ArgumentConflict)]
+    #[test_case(
+        &["", "tablebase", "--size", "5"],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "train", "--epochs", "0"],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "simulate", "--games", "0"],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "simulate", "--threads", "0"],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "simulate", "--ai-o", "unknown"],
+        ErrorKind::InvalidValue)]
+    #[test_case(
+        &["", "simulate", "--listen", "[::]:2020"],
+        ErrorKind::UnknownArgument)]
+    #[test_case(
+        &["", "simulate", "--engine-x", " "],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "simulate", "--mcts-iterations", "0"],
+        ErrorKind::ValueValidation)]
+    #[test_case(
+        &["", "tournament", "--mcts-playout", "unknown"],
+        ErrorKind::InvalidValue)]
+    #[test_case(
+        &["", "tournament", "--system", "unknown"],
+        ErrorKind::InvalidValue)]
+    #[test_case(
+        &["", "analyze"],
         ErrorKind::MissingRequiredArgument)]
+    #[test_case(
+        &["", "analyze", "XX./.../..."],
+        ErrorKind::ValueValidation)]
     fn from_iterator__Err(args: &[&str], expected: ErrorKind) {
         assert_eq!(
-            ParsedArgs::try_from_iterator(args)
-                .err()
-                .map(|e| e.kind())
-                .unwrap(),
+            parse(args, &[], &[]).err().map(|e| e.kind()).unwrap(),
+            expected
+        );
+    }
+
+    const HOME_CONFIG: &str = "/home/u/.config/tic-tac-toe/config.toml";
+
+    #[test_case(
+        &["", "server"],
+        &[],
+        &[],
+        "127.0.0.1:2020";
+        "default")]
+    #[test_case(
+        &["", "server"],
+        &[("HOME", "/home/u")],
+        &[(HOME_CONFIG, "server.listen = '0.0.0.0:1'")],
+        "0.0.0.0:1";
+        "home config")]
+    #[test_case(
+        &["", "server"],
+        &[("HOME", "/home/u"), ("XDG_CONFIG_HOME", "/xdg")],
+        &[(HOME_CONFIG, "server.listen = '0.0.0.0:1'"),
+            ("/xdg/tic-tac-toe/config.toml", "server.listen = '0.0.0.0:2'")],
+        "0.0.0.0:2";
+        "xdg config over home config")]
+    #[test_case(
+        &["", "server"],
+        &[("HOME", "/home/u"), ("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[(HOME_CONFIG, "server.listen = '0.0.0.0:1'"),
+            ("/c.toml", "server.listen = '0.0.0.0:3'")],
+        "0.0.0.0:3";
+        "env config over xdg config")]
+    #[test_case(
+        &["", "server", "--config", "/d.toml"],
+        &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[("/c.toml", "server.listen = '0.0.0.0:3'"),
+            ("/d.toml", "server.listen = '0.0.0.0:4'")],
+        "0.0.0.0:4";
+        "explicit config over env config")]
+    #[test_case(
+        &["", "--config", "/d.toml", "server"],
+        &[],
+        &[("/d.toml", "server.listen = '0.0.0.0:4'")],
+        "0.0.0.0:4";
+        "explicit config before command")]
+    #[test_case(
+        &["", "server"],
+        &[("HOME", "/home/u"), ("TIC_TAC_TOE_SERVER_LISTEN", "0.0.0.0:5")],
+        &[(HOME_CONFIG, "server.listen = '0.0.0.0:1'")],
+        "0.0.0.0:5";
+        "env over config")]
+    #[test_case(
+        &["", "server", "--listen", "0.0.0.0:6"],
+        &[("HOME", "/home/u"), ("TIC_TAC_TOE_SERVER_LISTEN", "0.0.0.0:5")],
+        &[(HOME_CONFIG, "server.listen = '0.0.0.0:1'")],
+        "0.0.0.0:6";
+        "command line over env")]
+    fn try_from_sources__precedence(
+        args: &[&str],
+        env: &[(&str, &str)],
+        files: &[(&str, &str)],
+        expected_listen: &str,
+    ) {
+        assert_eq!(
+            parse(args, env, files).unwrap(),
+            server(expected_listen, RatingPeriod::Match)
+        );
+    }
+
+    #[test]
+    fn try_from_sources__default_command_configured() {
+        assert_eq!(
+            parse(
+                &[""],
+                &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+                &[("/c.toml", "interactive.seed = 7")]
+            )
+            .unwrap(),
+            interactive(Some(7), "dark", RatingPeriod::Match)
+        );
+    }
+
+    #[test]
+    fn try_from_sources__config_and_env_combined() {
+        let parsed = parse(
+            &["", "simulate", "--rounds", "3"],
+            &[
+                ("TIC_TAC_TOE_CONFIG", "/c.toml"),
+                ("TIC_TAC_TOE_SIMULATE_THREADS", "3"),
+            ],
+            &[(
+                "/c.toml",
+                "[server]\nlisten = '0.0.0.0:1'\n[simulate]\ngames = 10\nformat = 'json'",
+            )],
+        );
+        assert_eq!(
+            parsed.unwrap(),
+            Simulate(SimulateArgs {
+                games: 10,
+                rounds: 3,
+                ais: [AiKind::Random, AiKind::Random],
+                engines: [None, None],
+                engine_time: Duration::from_millis(100),
+                books: [None, None],
+                book_randomness: 50,
+                mcts: MctsOpts::DEFAULT,
+                seed: None,
+                threads: NonZeroUsize::new(3),
+                format: ReportFormat::Json,
+            })
+        );
+    }
+
+    #[test_case(
+        &["", "--config", "/missing.toml"],
+        &[],
+        &[],
+        ErrorKind::Io;
+        "explicit config missing")]
+    #[test_case(
+        &[""],
+        &[("TIC_TAC_TOE_CONFIG", "/missing.toml")],
+        &[],
+        ErrorKind::Io;
+        "env config missing")]
+    #[test_case(
+        &[""],
+        &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[("/c.toml", "server.listen = ")],
+        ErrorKind::InvalidValue;
+        "invalid toml")]
+    #[test_case(
+        &[""],
+        &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[("/c.toml", "server.unknown = 1")],
+        ErrorKind::UnknownArgument;
+        "unknown option")]
+    #[test_case(
+        &[""],
+        &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[("/c.toml", "unknown.listen = 1")],
+        ErrorKind::UnknownArgument;
+        "unknown command")]
+    #[test_case(
+        &[""],
+        &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[("/c.toml", "[unknown]")],
+        ErrorKind::UnknownArgument;
+        "unknown empty command")]
+    #[test_case(
+        &[""],
+        &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[("/c.toml", "server = 1")],
+        ErrorKind::InvalidValue;
+        "command not table")]
+    #[test_case(
+        &[""],
+        &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[("/c.toml", "server.listen = [1]")],
+        ErrorKind::InvalidValue;
+        "array value")]
+    #[test_case(
+        &["", "server"],
+        &[("TIC_TAC_TOE_CONFIG", "/c.toml")],
+        &[("/c.toml", "server.listen = 'nowhere'")],
+        ErrorKind::ValueValidation;
+        "invalid config value")]
+    #[test_case(
+        &["", "server"],
+        &[("TIC_TAC_TOE_SERVER_LISTEN", "nowhere")],
+        &[],
+        ErrorKind::ValueValidation;
+        "invalid env value")]
+    fn try_from_sources__Err(
+        args: &[&str],
+        env: &[(&str, &str)],
+        files: &[(&str, &str)],
+        expected: ErrorKind,
+    ) {
+        assert_eq!(
+            parse(args, env, files).err().map(|e| e.kind()).unwrap(),
             expected
         );
     }
@@ -0,0 +1,175 @@
+//! The protocol of external engines, which decide moves for
+//! [`ExternalAi`](crate::ai::external::ExternalAi), and the reference engine, see [`run()`].
+//!
+//! The host writes [`Command`]s to the standard input of an engine, and the engine writes
+//! [`Reply`]s to its standard output, one per line. A session looks as follows:
+//!
+//! ```text
+//! host:   init 3
+//! engine: ready
+//! host:   position X../.../... O
+//! host:   go 1000
+//! engine: info searching
+//! engine: bestmove b2
+//! host:   quit
+//! ```
+//!
+//! The engine may write `info` at any time, and the host ignores it.
+
+use core::{
+    error::Error,
+    fmt::{Display, Formatter, Result},
+    str::FromStr,
+    sync::atomic::AtomicBool,
+    time::Duration,
+};
+use std::{
+    io,
+    io::{BufRead, Write},
+};
+
+use crate::{
+    ai,
+    ai::{
+        mcts::{Budget, Playout, Search},
+        worker::Decider,
+    },
+    cli::EngineArgs,
+    game::{Board, Cell},
+    solver::Position,
+};
+
+mod test;
+
+/// Sent by the host to an engine.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Command {
+    /// Starts a session, in which each [`Position`] has a board of the specified size.
+    /// The engine must reply with [`Reply::Ready`].
+    Init { board_size: usize },
+    /// Sets the position for the following [`Command::Go`]s.
+    Position(Position),
+    /// The engine must reply with [`Reply::BestMove`] in the set position within the time.
+    Go(Duration),
+    /// Ends the session, the engine must exit.
+    Quit,
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Init { board_size } => write!(f, "init {board_size}"),
+            Self::Position(position) => write!(f, "position {position}"),
+            Self::Go(time) => write!(f, "go {}", time.as_millis()),
+            Self::Quit => f.write_str("quit"),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    /// Parses the format described in [`Command::fmt()`].
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let (name, args) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+        match name {
+            "init" => args
+                .parse::<usize>()
+                .map(|board_size| Self::Init { board_size })
+                .map_err(|e| format!("invalid board size {args:?}: {e}")),
+            "position" => args.parse::<Position>().map(Self::Position),
+            "go" => args
+                .parse::<u64>()
+                .map(|millis| Self::Go(Duration::from_millis(millis)))
+                .map_err(|e| format!("invalid time {args:?}: {e}")),
+            "quit" if args.is_empty() => Ok(Self::Quit),
+            _ => Err(format!("unknown command {s:?}")),
+        }
+    }
+}
+
+/// Sent by an engine to the host.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Reply {
+    Ready,
+    BestMove(Cell),
+    /// Arbitrary text, e.g., for debugging.
+    Info(String),
+}
+
+impl Display for Reply {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Ready => f.write_str("ready"),
+            Self::BestMove(cell) => write!(f, "bestmove {cell}"),
+            Self::Info(text) => write!(f, "info {text}"),
+        }
+    }
+}
+
+impl FromStr for Reply {
+    type Err = String;
+
+    /// Parses the format described in [`Reply::fmt()`].
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let (name, args) = s.trim().split_once(' ').unwrap_or((s.trim(), ""));
+        match name {
+            "ready" if args.is_empty() => Ok(Self::Ready),
+            "bestmove" => args.parse::<Cell>().map(Self::BestMove),
+            "info" => Ok(Self::Info(args.to_owned())),
+            _ => Err(format!("unknown reply {s:?}")),
+        }
+    }
+}
+
+/// Runs the reference engine, which decides moves via [`Search`],
+/// on the standard input and output until [`Command::Quit`] or the end of the input.
+pub fn run(args: &EngineArgs) -> core::result::Result<(), Box<dyn Error>> {
+    serve(
+        io::stdin().lock(),
+        &mut io::stdout().lock(),
+        args.seed().unwrap_or_else(ai::time_based_seed),
+    )?;
+    Ok(())
+}
+
+/// Invalid commands are reported via [`Reply::Info`] and are otherwise ignored.
+fn serve(input: impl BufRead, output: &mut impl Write, seed: u64) -> io::Result<()> {
+    let mut search = None;
+    let mut position = None;
+    for line in input.lines() {
+        let reply = match line?.parse::<Command>() {
+            Ok(Command::Init { board_size }) if board_size == Board::SIZE => {
+                // the budget is set by each `Command::Go`
+                search = Some(Search::new(seed, Budget::Iterations(0), Playout::Heuristic));
+                position = None;
+                Some(Reply::Ready)
+            }
+            Ok(Command::Init { board_size }) => Some(Reply::Info(format!(
+                "unsupported board size {board_size}, expected {}",
+                Board::SIZE
+            ))),
+            Ok(Command::Position(new_position)) => {
+                position = Some(new_position);
+                None
+            }
+            Ok(Command::Go(time)) => Some(match (&mut search, &position) {
+                (Some(search), Some(position)) if !position.is_over() => {
+                    // leaves time for the host to receive the reply
+                    search.set_budget(Budget::Time(time / 2));
+                    Reply::BestMove(search.decide_cell(position, &AtomicBool::new(false)))
+                }
+                (None, _) => Reply::Info("expected `init` first".to_owned()),
+                (_, None) => Reply::Info("expected `position` first".to_owned()),
+                (_, Some(position)) => Reply::Info(format!("the position {position} is over")),
+            }),
+            Ok(Command::Quit) => return Ok(()),
+            Err(e) => Some(Reply::Info(e)),
+        };
+        if let Some(reply) = reply {
+            writeln!(output, "{reply}")?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Command {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::engine::Command;
+
+    #[test_case("init 3")]
+    #[test_case("position X../.../... O")]
+    #[test_case("go 100")]
+    #[test_case("quit")]
+    fn from_str__fmt(s: &str) {
+        assert_eq!(s.parse::<Command>().unwrap().to_string(), s);
+    }
+
+    #[test_case("init")]
+    #[test_case("init x")]
+    #[test_case("position XX./.../...")]
+    #[test_case("go -1")]
+    #[test_case("quit now")]
+    #[test_case("")]
+    #[test_case("unknown")]
+    fn from_str__Err(s: &str) {
+        assert!(s.parse::<Command>().is_err());
+    }
+}
+
+mod Reply {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::engine::Reply;
+
+    #[test_case("ready")]
+    #[test_case("bestmove b2")]
+    #[test_case("info searching 100 ms")]
+    fn from_str__fmt(s: &str) {
+        assert_eq!(s.parse::<Reply>().unwrap().to_string(), s);
+    }
+
+    #[test_case("ready now")]
+    #[test_case("bestmove")]
+    #[test_case("bestmove d4")]
+    #[test_case("unknown")]
+    fn from_str__Err(s: &str) {
+        assert!(s.parse::<Reply>().is_err());
+    }
+}
+
+mod serve {
+    use ntest::timeout;
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::engine;
+
+    #[test_case(
+        "init 3\nposition OO./XX./X.. O\ngo 20\nposition OO./X../..X X\ngo 20\nquit\ngo 20\n",
+        "ready\nbestmove c1\nbestmove c1\n";
+        "session")]
+    #[test_case(
+        "go 10\ninit 4\nhello\ninit 3\ngo 10\nposition XXX/OO./... O\ngo 10\n",
+        "info expected `init` first\n\
+        info unsupported board size 4, expected 3\n\
+        info unknown command \"hello\"\n\
+        ready\n\
+        info expected `position` first\n\
+        info the position XXX/OO./... O is over\n";
+        "invalid commands")]
+    #[timeout(1_000)]
+    fn serve(input: &str, expected: &str) {
+        let mut output = Vec::new();
+        engine::serve(input.as_bytes(), &mut output, 0).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+}
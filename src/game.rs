@@ -3,29 +3,42 @@ extern crate alloc;
 use alloc::{collections::VecDeque, rc::Rc};
 use core::{
     cell::RefCell,
-    fmt::{Debug, Display, Formatter, Result},
+    fmt::{Debug, Display, Formatter, Result, Write},
+    str::FromStr,
     time::Duration,
 };
-use std::{collections::HashSet, time::Instant};
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    ai::{Reason, Tempo},
     game::{
         Action::{Occupy, Ready, Surrender},
         Line::{D1, D2, H, V},
         Phase::{Beginning, Inround, Outround},
-        PlayerType::{Local, _Remote},
+        PlayerType::{Local, Remote},
     },
-    util::time::AdvanceableClock,
+    util::time::{AdvanceableClock, ClockSource, Time},
 };
 
 mod test;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Mark {
     X,
     O,
 }
 
+impl Mark {
+    pub fn player_id(self) -> PlayerId {
+        PlayerId::new(match self {
+            Self::X => 0,
+            Self::O => 1,
+        })
+    }
+}
+
 impl Display for Mark {
     fn fmt(&self, f: &mut Formatter) -> Result {
         f.write_str(match self {
@@ -35,6 +48,19 @@ impl Display for Mark {
     }
 }
 
+impl FromStr for Mark {
+    type Err = String;
+
+    /// Case-insensitive.
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        match s {
+            "X" | "x" => Ok(Self::X),
+            "O" | "o" => Ok(Self::O),
+            _ => Err(format!("unknown mark {s:?}, expected either X or O")),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct Player {
     pub id: PlayerId,
@@ -48,11 +74,7 @@ impl Player {
     }
 
     pub fn mark(&self) -> Mark {
-        match self.id.idx {
-            0 => Mark::X,
-            1 => Mark::O,
-            _ => panic!("{:?}", self.id),
-        }
+        self.id.mark()
     }
 }
 
@@ -76,6 +98,14 @@ impl PlayerId {
         );
         Self { idx }
     }
+
+    pub fn mark(self) -> Mark {
+        match self.idx {
+            0 => Mark::X,
+            1 => Mark::O,
+            _ => panic!("{self:?}"),
+        }
+    }
 }
 
 impl From<usize> for PlayerId {
@@ -93,7 +123,7 @@ impl PartialEq<usize> for PlayerId {
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum PlayerType {
     Local(LocalPlayerType),
-    _Remote,
+    Remote,
 }
 
 impl Display for PlayerType {
@@ -103,7 +133,7 @@ impl Display for PlayerType {
                 LocalPlayerType::Human => "local player",
                 LocalPlayerType::Ai => "AI",
             },
-            _Remote => "remote player",
+            Remote => "remote player",
         })
     }
 }
@@ -121,7 +151,9 @@ pub enum Phase {
     Outround,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Is serialized in the algebraic notation, see [`Cell::fmt()`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub struct Cell {
     x: usize,
     y: usize,
@@ -136,6 +168,16 @@ impl Cell {
         assert!(y < Board::SIZE, "{y:?}, {:?}", Board::SIZE);
         Self { x, y }
     }
+
+    /// The row.
+    pub fn x(self) -> usize {
+        self.x
+    }
+
+    /// The column.
+    pub fn y(self) -> usize {
+        self.y
+    }
 }
 
 impl From<(usize, usize)> for Cell {
@@ -145,19 +187,78 @@ impl From<(usize, usize)> for Cell {
     }
 }
 
-#[derive(Debug, Default, Eq, PartialEq)]
+impl Display for Cell {
+    /// Uses the algebraic notation: a column is denoted with a letter starting from `a`
+    /// on the left, a row is denoted with a number starting from `1` at the top.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{}{}",
+            char::from(b'a' + u8::try_from(self.y).unwrap()),
+            self.x + 1
+        )
+    }
+}
+
+impl FromStr for Cell {
+    type Err = String;
+
+    /// Parses the algebraic notation described in [`Cell::fmt()`].
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let err = || {
+            format!(
+                "invalid cell {s:?}, expected a column from a to {} followed by a row from 1 to {}",
+                char::from(b'a' + u8::try_from(Board::SIZE - 1).unwrap()),
+                Board::SIZE
+            )
+        };
+        let mut chars = s.chars();
+        let y = chars
+            .next()
+            .filter(char::is_ascii_lowercase)
+            .map(|column| usize::from(column as u8 - b'a'))
+            .ok_or_else(err)?;
+        let x = chars
+            .as_str()
+            .parse::<usize>()
+            .ok()
+            .and_then(|row| row.checked_sub(1))
+            .ok_or_else(err)?;
+        if x < Board::SIZE && y < Board::SIZE {
+            Ok(Self::new(x, y))
+        } else {
+            Err(err())
+        }
+    }
+}
+
+impl TryFrom<String> for Cell {
+    type Error = String;
+
+    fn try_from(s: String) -> core::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Cell> for String {
+    fn from(cell: Cell) -> Self {
+        cell.to_string()
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
 pub struct Board {
     cells: [[Option<PlayerId>; Board::SIZE]; Board::SIZE],
 }
 
 impl Board {
-    const SIZE: usize = 3;
+    pub const SIZE: usize = 3;
 
     fn new() -> Self {
         Self::default()
     }
 
-    fn set(&mut self, cell: &Cell, player_id: PlayerId) {
+    pub fn set(&mut self, cell: &Cell, player_id: PlayerId) {
         assert_eq!(self.cells[cell.x][cell.y], None, "{self:?}, {cell:?}");
         self.cells[cell.x][cell.y] = Option::from(player_id);
     }
@@ -177,6 +278,68 @@ impl Board {
     pub fn size(&self) -> usize {
         self.cells.len()
     }
+
+    /// Returns all cells, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = Cell> {
+        let size = self.size();
+        (0..size).flat_map(move |x| (0..size).map(move |y| Cell::new(x, y)))
+    }
+
+    pub fn count(&self, player_id: PlayerId) -> usize {
+        self.cells()
+            .filter(|cell| self.get(cell) == Some(player_id))
+            .count()
+    }
+}
+
+impl Display for Board {
+    /// Rows from top to bottom are separated with `/`,
+    /// each row is represented with a [`Mark`] or `.` for each cell from left to right,
+    /// e.g., `X.O/.X./...`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for (x, row) in self.cells.iter().enumerate() {
+            if x > 0 {
+                f.write_char('/')?;
+            }
+            for cell in row {
+                match cell {
+                    Some(player_id) => write!(f, "{}", player_id.mark())?,
+                    None => f.write_char('.')?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Board {
+    type Err = String;
+
+    /// Parses the notation described in [`Board::fmt()`], marks are case-insensitive.
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let mut board = Self::new();
+        let rows = s.split('/').collect::<Vec<_>>();
+        if rows.len() != Self::SIZE {
+            return Err(format!(
+                "invalid board {s:?}, expected {} rows separated with /",
+                Self::SIZE
+            ));
+        }
+        for (x, row) in rows.into_iter().enumerate() {
+            if row.chars().count() != Self::SIZE {
+                return Err(format!(
+                    "invalid row {row:?}, expected {} cells",
+                    Self::SIZE
+                ));
+            }
+            for (y, c) in row.chars().enumerate() {
+                if c != '.' {
+                    board.set(&Cell::new(x, y), c.to_string().parse::<Mark>()?.player_id());
+                }
+            }
+        }
+        Ok(board)
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -187,6 +350,19 @@ pub enum Line {
     D2,
 }
 
+impl Display for Line {
+    /// Uses the algebraic notation of [`Cell`]s, e.g., `column a`, `row 1`, `diagonal a1-c3`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let last = Board::SIZE - 1;
+        match self {
+            H(y) => write!(f, "column {}", char::from(b'a' + u8::try_from(*y).unwrap())),
+            V(x) => write!(f, "row {}", x + 1),
+            D1 => write!(f, "diagonal {}-{}", Cell::new(0, 0), Cell::new(last, last)),
+            D2 => write!(f, "diagonal {}-{}", Cell::new(0, last), Cell::new(last, 0)),
+        }
+    }
+}
+
 impl Line {
     pub fn contains(&self, cell: &Cell) -> bool {
         match self {
@@ -198,10 +374,56 @@ impl Line {
     }
 }
 
-#[derive(Debug, Eq)]
+/// A [`Cell`] occupied by a player.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Move {
+    pub player_id: PlayerId,
+    pub cell: Cell,
+    /// The time since the previous move, or since the start of the round if there is none.
+    pub think_time: Duration,
+    /// The time since the start of the round.
+    pub round_time: Duration,
+    /// Why an [`Ai`] has made the move, if it explains its moves.
+    pub reason: Option<Reason>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Win(PlayerId, Line),
+    /// The player who has surrendered.
+    Surrender(PlayerId),
+    Draw,
+}
+
+/// What has happened in a round.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RoundLog {
+    /// As per [`State::clock`].
+    pub start: Time,
+    pub moves: Vec<Move>,
+    /// [`None`] if the round has not ended.
+    pub outcome: Option<Outcome>,
+    /// Is [`Duration::ZERO`] if the round has not ended.
+    pub duration: Duration,
+}
+
+impl RoundLog {
+    fn new(start: Time) -> Self {
+        Self {
+            start,
+            moves: Vec::new(),
+            outcome: None,
+            duration: Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct State {
-    /// Must be initialized as a result of the first invocation of [`World::advance()`].
-    pub clock: Option<AdvanceableClock>,
+    /// Is advanced by [`World::advance()`].
+    /// Uses [`RealClockSource`](crate::util::time::RealClockSource)
+    /// unless replaced via [`World::with_clock_source()`].
+    pub clock: AdvanceableClock,
     pub board: Board,
     pub players: [Player; State::PLAYER_COUNT],
     pub phase: Phase,
@@ -210,6 +432,8 @@ pub struct State {
     pub step: u32,
     pub required_ready: HashSet<PlayerId>,
     pub win_line: Option<Line>,
+    /// Contains an item for each round up to and including [`State::round`].
+    pub log: Vec<RoundLog>,
 }
 
 impl State {
@@ -225,8 +449,10 @@ impl State {
             assert_eq!(player.id, idx);
         }
         let required_ready = players.iter().map(|p| p.id).collect::<HashSet<PlayerId>>();
+        let clock = AdvanceableClock::real();
+        let log = vec![RoundLog::new(clock.now())];
         Self {
-            clock: None,
+            clock,
             board: Board::new(),
             players,
             phase: Beginning,
@@ -235,12 +461,22 @@ impl State {
             step: 0,
             required_ready,
             win_line: None,
+            log,
         }
     }
 
     pub fn turn(&self) -> PlayerId {
         PlayerId::from(usize::try_from(self.step + self.round).unwrap() % self.players.len())
     }
+
+    /// The [`RoundLog`] of the current round.
+    pub fn round_log(&self) -> &RoundLog {
+        self.log.last().unwrap()
+    }
+
+    fn round_log_mut(&mut self) -> &mut RoundLog {
+        self.log.last_mut().unwrap()
+    }
 }
 
 impl PartialEq<Self> for State {
@@ -254,13 +490,17 @@ impl PartialEq<Self> for State {
             && self.step == other.step
             && self.required_ready == other.required_ready
             && self.win_line == other.win_line
+            && self.log == other.log
     }
 }
 
+impl Eq for State {}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Action {
     Ready,
-    Occupy(Cell),
+    /// Occupies the cell, optionally explaining why.
+    Occupy(Cell, Option<Reason>),
     Surrender,
 }
 
@@ -290,6 +530,10 @@ impl DefaultActionQueue {
     pub fn add(&self, action: Action) {
         self.actions.borrow_mut().push_back(action);
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.borrow().is_empty()
+    }
 }
 
 impl ActionQueue for DefaultActionQueue {
@@ -359,7 +603,7 @@ where
             );
             match action {
                 Surrender => Self::surrender(state),
-                Occupy(cell) => Self::occupy(state, &cell),
+                Occupy(cell, reason) => Self::occupy(state, &cell, reason),
                 Ready => panic!("unexpected action: {state:?}, {action:?}"),
             }
             if state.turn() != player_id || state.phase != Inround {
@@ -388,12 +632,29 @@ where
         assert_eq!(state.phase, Inround);
         let idx_other_player = (state.turn().idx + 1) % state.players.len();
         state.players[idx_other_player].wins += 1;
+        state.round_log_mut().outcome = Some(Outcome::Surrender(state.turn()));
         Self::end_round(state);
     }
 
-    fn occupy(state: &mut State, cell: &Cell) {
+    fn occupy(state: &mut State, cell: &Cell, reason: Option<Reason>) {
         assert_eq!(state.phase, Inround);
         state.board.set(cell, state.turn());
+        let now = state.clock.now();
+        let player_id = state.turn();
+        let round_log = state.round_log_mut();
+        let round_time = now.v.saturating_sub(round_log.start.v);
+        round_log.moves.push(Move {
+            player_id,
+            cell: *cell,
+            think_time: round_time.saturating_sub(
+                round_log
+                    .moves
+                    .last()
+                    .map_or(Duration::ZERO, |last| last.round_time),
+            ),
+            round_time,
+            reason,
+        });
         if let Some(win_line) = Self::check_win(&state.board, cell) {
             Self::win(state, win_line);
         } else if Self::last_step(state.step, &state.board) {
@@ -405,12 +666,15 @@ where
 
     fn start_round(state: &mut State) {
         match state.phase {
-            Beginning => {}
+            Beginning => {
+                state.round_log_mut().start = state.clock.now();
+            }
             Outround => {
                 state.step = 0;
                 state.round += 1;
                 state.board.clear();
                 state.win_line = None;
+                state.log.push(RoundLog::new(state.clock.now()));
             }
             Inround => panic!("{state:?}"),
         }
@@ -419,6 +683,9 @@ where
 
     fn end_round(state: &mut State) {
         state.phase = Outround;
+        let now = state.clock.now();
+        let round_log = state.round_log_mut();
+        round_log.duration = now.v.saturating_sub(round_log.start.v);
         if !Self::is_game_over(state) {
             state
                 .required_ready
@@ -428,7 +695,7 @@ where
 
     /// Returns [`None`] iff the `last_occupied` [`Cell`] does not result in a win codition,
     /// otherwise returns the winning [`Line`].
-    fn check_win(board: &Board, last_occupied: &Cell) -> Option<Line> {
+    pub fn check_win(board: &Board, last_occupied: &Cell) -> Option<Line> {
         let mut h_match = 0;
         let mut v_match = 0;
         let mut d1_match = 0;
@@ -467,10 +734,12 @@ where
     fn win(state: &mut State, win_line: Line) {
         state.players[state.turn().idx].wins += 1;
         state.win_line = Some(win_line);
+        state.round_log_mut().outcome = Some(Outcome::Win(state.turn(), win_line));
         Self::end_round(state);
     }
 
     fn draw(state: &mut State) {
+        state.round_log_mut().outcome = Some(Outcome::Draw);
         Self::end_round(state);
     }
 
@@ -485,6 +754,13 @@ pub trait Ai: Debug {
     fn act(&mut self, state: &State);
 
     fn set_base_act_delay(&mut self, delay: Duration);
+
+    /// Changes how the delays of the actions depend on the positions.
+    fn set_tempo(&mut self, tempo: Tempo);
+
+    /// Makes [`Self::act()`] wait for the decision instead of returning before it is ready,
+    /// for the [`Ai`]s that decide on a worker thread, which is useful in headless games.
+    fn set_blocking(&mut self, _blocking: bool) {}
 }
 
 #[derive(Debug)]
@@ -517,12 +793,17 @@ where
         Self { state, logic, ais }
     }
 
+    /// Replaces the source of [`State::clock`], which must be done before the first
+    /// invocation of [`Self::advance()`].
+    #[must_use]
+    pub fn with_clock_source(mut self, source: Box<dyn ClockSource>) -> Self {
+        self.state.clock = AdvanceableClock::new(source);
+        self
+    }
+
     pub fn advance(&mut self) {
-        if self.state.clock.is_none() {
-            self.state.clock = Some(AdvanceableClock::new(Instant::now()));
-        }
         // there is no behavior requiring a fixed time step, so we use a variable one
-        self.state.clock.as_mut().unwrap().advance_to_real_now();
+        self.state.clock.advance();
         for ai in &mut self.ais {
             ai.act(&self.state);
         }
@@ -1,11 +1,11 @@
 #![cfg(test)]
 #![allow(non_snake_case)]
 
-use core::cell::RefCell;
+use core::{cell::RefCell, time::Duration};
 use std::collections::HashSet;
 
 use crate::{
-    game::{Action, ActionQueue, Board, Phase::Inround},
+    game::{Action, ActionQueue, Board, Move, Phase::Inround},
     Human, Local, Player, PlayerId, State,
 };
 
@@ -30,10 +30,72 @@ mod Line {
     fn contains(line: Line, cell: Cell, expected: bool) {
         assert_eq!(line.contains(&cell), expected);
     }
+
+    #[test_case(Line::H(1), "column b")]
+    #[test_case(Line::V(2), "row 3")]
+    #[test_case(Line::D1, "diagonal a1-c3")]
+    #[test_case(Line::D2, "diagonal c1-a3")]
+    fn display(line: Line, expected: &str) {
+        assert_eq!(line.to_string(), expected);
+    }
+}
+
+mod Cell {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::game::Cell;
+
+    #[test_case((0, 0).into(), "a1")]
+    #[test_case((0, 2).into(), "c1")]
+    #[test_case((2, 1).into(), "b3")]
+    fn display_from_str(cell: Cell, s: &str) {
+        assert_eq!(cell.to_string(), s);
+        assert_eq!(s.parse::<Cell>().unwrap(), cell);
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("a"; "no row")]
+    #[test_case("1"; "no column")]
+    #[test_case("a0"; "row too small")]
+    #[test_case("a4"; "row too big")]
+    #[test_case("d1"; "column too big")]
+    #[test_case("A1"; "uppercase column")]
+    #[test_case("a1 "; "trailing space")]
+    fn from_str__Err(s: &str) {
+        assert!(s.parse::<Cell>().is_err(), "{s:?}");
+    }
+}
+
+mod Board_notation {
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::game::Board;
+
+    #[test]
+    fn display_from_str() {
+        let board = Board {
+            cells: [
+                [Some(0.into()), None, Some(1.into())],
+                [None, Some(0.into()), None],
+                [None, None, None],
+            ],
+        };
+        assert_eq!(board.to_string(), "X.O/.X./...");
+        assert_eq!("x.o/.X./...".parse::<Board>().unwrap(), board);
+    }
+
+    #[test]
+    fn from_str__Err() {
+        for s in ["", "X.O/.X.", "X.O/.X./..", "X.O/.X./..Z"] {
+            assert!(s.parse::<Board>().is_err(), "{s:?}");
+        }
+    }
 }
 
 mod Logic_single_action {
     use alloc::rc::Rc;
+    use core::time::Duration;
     use std::collections::HashSet;
 
     use pretty_assertions_sorted::{assert_eq, assert_eq_sorted};
@@ -41,12 +103,15 @@ mod Logic_single_action {
 
     use crate::{
         game::{
-            test::{required_ready_from_players, state_with_board, VecActionQueue},
+            test::{moves, required_ready_from_players, state_with_board, VecActionQueue},
             Action::{Occupy, Ready, Surrender},
             ActionQueue, Board, Cell, Line,
             Line::{D1, D2, H, V},
+            Outcome,
             Phase::{Beginning, Inround, Outround},
+            RoundLog,
         },
+        util::time::Time,
         DefaultActionQueue, Logic, PlayerId,
     };
 
@@ -82,7 +147,7 @@ mod Logic_single_action {
         Logic::new([
             Rc::new(VecActionQueue::new(
                 PlayerId::new(0),
-                vec![Some(Occupy((1, 2).into()))],
+                vec![Some(Occupy((1, 2).into(), None))],
             )),
             Rc::new(VecActionQueue::new(PlayerId::new(1), vec![])),
         ])
@@ -96,6 +161,7 @@ mod Logic_single_action {
                 ],
             });
             expected_state.step = 1;
+            expected_state.log[0].moves = moves(&[(0, (1, 2))]);
             expected_state
         });
     }
@@ -114,6 +180,7 @@ mod Logic_single_action {
             expected_state.players[1].wins = 1;
             expected_state.phase = Outround;
             expected_state.required_ready = expected_required_ready;
+            expected_state.log[0].outcome = Some(Outcome::Surrender(PlayerId::new(0)));
             expected_state
         });
     }
@@ -214,7 +281,7 @@ mod Logic_single_action {
         Logic::new([
             Rc::new(VecActionQueue::new(
                 PlayerId::new(0),
-                vec![Some(Occupy((2, 2).into()))],
+                vec![Some(Occupy((2, 2).into(), None))],
             )),
             Rc::new(VecActionQueue::new(PlayerId::new(1), vec![])),
         ])
@@ -232,6 +299,8 @@ mod Logic_single_action {
             expected_state.step = 4;
             expected_state.required_ready = expected_required_ready;
             expected_state.win_line = Some(D1);
+            expected_state.log[0].moves = moves(&[(0, (2, 2))]);
+            expected_state.log[0].outcome = Some(Outcome::Win(PlayerId::new(0), D1));
             expected_state
         });
     }
@@ -253,7 +322,7 @@ mod Logic_single_action {
         Logic::new([
             Rc::new(VecActionQueue::new(
                 PlayerId::new(0),
-                vec![Some(Occupy((2, 2).into()))],
+                vec![Some(Occupy((2, 2).into(), None))],
             )),
             Rc::new(VecActionQueue::new(PlayerId::new(1), vec![])),
         ])
@@ -269,6 +338,8 @@ mod Logic_single_action {
             expected_state.phase = Outround;
             expected_state.step = 8;
             expected_state.required_ready = expected_required_ready;
+            expected_state.log[0].moves = moves(&[(0, (2, 2))]);
+            expected_state.log[0].outcome = Some(Outcome::Draw);
             expected_state
         });
     }
@@ -301,6 +372,9 @@ mod Logic_single_action {
             expected_state.round = 1;
             expected_state.step = 0;
             expected_state
+                .log
+                .push(RoundLog::new(Time::new(Duration::ZERO)));
+            expected_state
         });
     }
 
@@ -332,7 +406,7 @@ mod Logic_single_action {
         let expected_required_ready = required_ready_from_players(&state.players);
         let act_queue_p0 = Rc::new(VecActionQueue::new(
             PlayerId::new(0),
-            vec![Some(Surrender), Some(Occupy((0, 0).into()))],
+            vec![Some(Surrender), Some(Occupy((0, 0).into(), None))],
         ));
         Logic::new([
             Rc::clone(&act_queue_p0),
@@ -344,6 +418,7 @@ mod Logic_single_action {
             expected_state.players[1].wins = 1;
             expected_state.phase = Outround;
             expected_state.required_ready = expected_required_ready;
+            expected_state.log[0].outcome = Some(Outcome::Surrender(PlayerId::new(0)));
             expected_state
         });
         assert_ne!(act_queue_p0.pop(), None);
@@ -357,10 +432,11 @@ mod Logic_multiple_actions {
 
     use crate::{
         game::{
-            test::{required_ready_from_players, state_with_board, VecActionQueue},
+            test::{moves, required_ready_from_players, state_with_board, VecActionQueue},
             Action::{Occupy, Ready},
             Board,
             Line::D2,
+            Outcome,
             Phase::{Beginning, Outround},
         },
         DefaultActionQueue, Logic, PlayerId,
@@ -378,18 +454,18 @@ mod Logic_multiple_actions {
         let act_queue_p0 = {
             let queue = DefaultActionQueue::new(PlayerId::new(0));
             queue.add(Ready);
-            queue.add(Occupy((1, 1).into()));
-            queue.add(Occupy((0, 0).into()));
-            queue.add(Occupy((0, 2).into()));
-            queue.add(Occupy((2, 0).into()));
+            queue.add(Occupy((1, 1).into(), None));
+            queue.add(Occupy((0, 0).into(), None));
+            queue.add(Occupy((0, 2).into(), None));
+            queue.add(Occupy((2, 0).into(), None));
             Rc::new(queue)
         };
         let act_queue_p1 = {
             let queue = DefaultActionQueue::new(PlayerId::new(1));
             queue.add(Ready);
-            queue.add(Occupy((1, 2).into()));
-            queue.add(Occupy((2, 2).into()));
-            queue.add(Occupy((0, 1).into()));
+            queue.add(Occupy((1, 2).into(), None));
+            queue.add(Occupy((2, 2).into(), None));
+            queue.add(Occupy((0, 1).into(), None));
             Rc::new(queue)
         };
         let actions_cnt = act_queue_p0.actions.borrow().len() + act_queue_p1.actions.borrow().len();
@@ -411,6 +487,16 @@ mod Logic_multiple_actions {
             expected_state.step = 6;
             expected_state.required_ready = expected_required_ready;
             expected_state.win_line = Some(D2);
+            expected_state.log[0].moves = moves(&[
+                (0, (1, 1)),
+                (1, (1, 2)),
+                (0, (0, 0)),
+                (1, (2, 2)),
+                (0, (0, 2)),
+                (1, (0, 1)),
+                (0, (2, 0)),
+            ]);
+            expected_state.log[0].outcome = Some(Outcome::Win(PlayerId::new(0), D2));
             expected_state
         });
         act_queue_p0.add(Ready);
@@ -432,23 +518,23 @@ mod Logic_multiple_actions {
             vec![
                 None,
                 None,
-                Some(Occupy((0, 0).into())),
-                Some(Occupy((1, 0).into())),
+                Some(Occupy((0, 0).into(), None)),
+                Some(Occupy((1, 0).into(), None)),
                 None,
-                Some(Occupy((0, 2).into())),
-                Some(Occupy((2, 1).into())),
+                Some(Occupy((0, 2).into(), None)),
+                Some(Occupy((2, 1).into(), None)),
             ],
         ));
         let act_queue_p1 = Rc::new(VecActionQueue::new(
             PlayerId::new(1),
             vec![
-                Some(Occupy((1, 1).into())),
-                Some(Occupy((1, 2).into())),
-                Some(Occupy((2, 0).into())),
+                Some(Occupy((1, 1).into(), None)),
+                Some(Occupy((1, 2).into(), None)),
+                Some(Occupy((2, 0).into(), None)),
                 None,
                 None,
-                Some(Occupy((0, 1).into())),
-                Some(Occupy((2, 2).into())),
+                Some(Occupy((0, 1).into(), None)),
+                Some(Occupy((2, 2).into(), None)),
                 None,
             ],
         ));
@@ -469,6 +555,18 @@ mod Logic_multiple_actions {
             expected_state.round = 1;
             expected_state.step = 8;
             expected_state.required_ready = expected_required_ready;
+            expected_state.log[0].moves = moves(&[
+                (1, (1, 1)),
+                (0, (0, 0)),
+                (1, (1, 2)),
+                (0, (1, 0)),
+                (1, (2, 0)),
+                (0, (0, 2)),
+                (1, (0, 1)),
+                (0, (2, 1)),
+                (1, (2, 2)),
+            ]);
+            expected_state.log[0].outcome = Some(Outcome::Draw);
             expected_state
         });
     }
@@ -538,6 +636,20 @@ fn state_with_board(board: Board) -> State {
     state
 }
 
+/// Creates [`Move`]s made instantly.
+fn moves(moves: &[(usize, (usize, usize))]) -> Vec<Move> {
+    moves
+        .iter()
+        .map(|&(player_idx, cell)| Move {
+            player_id: PlayerId::new(player_idx),
+            cell: cell.into(),
+            think_time: Duration::ZERO,
+            round_time: Duration::ZERO,
+            reason: None,
+        })
+        .collect()
+}
+
 fn required_ready_from_players(players: &[Player]) -> HashSet<PlayerId> {
     players.iter().map(|p| p.id).collect::<HashSet<PlayerId>>()
 }
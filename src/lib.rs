@@ -53,15 +53,26 @@ use crate::{
         State, World,
     },
     process::ExitSignal,
-    ParsedArgs::{Dedicated, Interactive},
+    ParsedArgs::{
+        Analyze, Book, Engine, Interactive, Play, Server, Simulate, Tablebase, Tournament, Train,
+    },
 };
 
 mod ai;
+mod book;
 pub mod cli;
+mod engine;
 mod game;
 pub mod process;
+mod profile;
+mod rating;
+mod save;
 mod server;
+mod sim;
+mod solver;
 mod test;
+mod tournament;
+mod train;
 mod tui;
 mod util;
 
@@ -70,7 +81,15 @@ mod util;
 /// When the application must be terminated.
 pub fn run(args: &ParsedArgs, exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
     match args {
-        Dedicated(args) => server::run(args, exit_signal),
-        Interactive => tui::run(exit_signal),
+        Server(args) => server::run(args, exit_signal),
+        Interactive(args) => tui::run(args, exit_signal),
+        Play(args) => tui::play(args, exit_signal),
+        Simulate(args) => sim::run(args, exit_signal),
+        Tournament(args) => tournament::run(args, exit_signal),
+        Analyze(args) => solver::run(args),
+        Engine(args) => engine::run(args),
+        Train(args) => train::run(args, exit_signal),
+        Tablebase(args) => solver::tablebase::run(args),
+        Book(args) => book::run(args),
     }
 }
@@ -0,0 +1,320 @@
+//! Local player profiles, see [`Profiles`].
+
+use core::fmt::{self, Display, Formatter, Write};
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::{personality::Personality, AiKind},
+    game::{Mark, Outcome, PlayerId, State},
+    util::{csv, dirs},
+};
+
+mod test;
+
+/// The profiles of the local human players, one of which may be active,
+/// that is, the one to which the results of games are attributed.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Profiles {
+    /// The [`Profile::name`] of the active profile.
+    pub active: Option<String>,
+    pub profiles: Vec<Profile>,
+}
+
+impl Profiles {
+    const FILE_NAME: &'static str = "profiles.json";
+    const EXPORT_DIR: &'static str = "exports";
+    const CSV_HEADER: &'static str =
+        "profile,preferred_mark,opponent,games,rounds,wins,draws,losses,\
+        current_streak,longest_win_streak,longest_loss_streak";
+
+    /// Returns the path of the profiles file inside [`dirs::data_dir()`].
+    pub fn path(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::data_dir(env).map(|dir| dir.join(Self::FILE_NAME))
+    }
+
+    /// Returns the directory with exported profiles inside [`dirs::data_dir()`].
+    pub fn export_dir(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::data_dir(env).map(|dir| dir.join(Self::EXPORT_DIR))
+    }
+
+    /// Returns no profiles if the file at `path` does not exist.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Creates the parent directory of `path` if needed.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_json())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Profile> {
+        self.profiles
+            .iter_mut()
+            .find(|profile| profile.name == name)
+    }
+
+    pub fn active(&self) -> Option<&Profile> {
+        self.active.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// Adds a new profile and makes it active.
+    ///
+    /// # Errors
+    ///
+    /// If the `name` is blank or is already taken.
+    pub fn add(&mut self, name: &str, preferred_mark: Mark) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("The name of a profile must not be blank.".to_owned());
+        }
+        if self.get(name).is_some() {
+            return Err(format!("The profile `{name}` already exists."));
+        }
+        self.profiles
+            .push(Profile::new(name.to_owned(), preferred_mark));
+        self.active = Some(name.to_owned());
+        Ok(())
+    }
+
+    /// Creates a new file in `dir`, creating the directory if needed, and returns its path.
+    pub fn export(&self, dir: &Path, format: ExportFormat) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let (content, ext) = match format {
+            ExportFormat::Csv => (self.to_csv(), "csv"),
+            ExportFormat::Json => (self.to_json(), "json"),
+        };
+        let path = dir.join(format!("profiles-{secs}.{ext}"));
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Returns a row for each opponent of each profile,
+    /// the columns of a profile as a whole are repeated in each of its rows.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", Self::CSV_HEADER);
+        for profile in &self.profiles {
+            let streaks = &profile.streaks;
+            let current_streak = streaks
+                .current
+                .map(|(result, len)| format!("{len} {result}"))
+                .unwrap_or_default();
+            for record in &profile.records {
+                writeln!(
+                    csv,
+                    "{},{},{},{},{},{},{},{},{current_streak},{},{}",
+                    csv::field(&profile.name),
+                    profile.preferred_mark,
+                    csv::field(&record.opponent.to_string()),
+                    record.games,
+                    record.rounds,
+                    record.wins,
+                    record.draws,
+                    record.losses,
+                    streaks.longest_wins,
+                    streaks.longest_losses
+                )
+                .unwrap();
+            }
+        }
+        csv
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// The mark the player plays with when possible.
+    pub preferred_mark: Mark,
+    /// A record for each opponent the player has finished a game against.
+    pub records: Vec<Record>,
+    pub streaks: Streaks,
+}
+
+impl Profile {
+    fn new(name: String, preferred_mark: Mark) -> Self {
+        Self {
+            name,
+            preferred_mark,
+            records: Vec::new(),
+            streaks: Streaks::default(),
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.records.iter().map(|record| record.games).sum()
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.records.iter().map(|record| record.rounds).sum()
+    }
+
+    /// Records the results of the finished game in which the player was `player_id`.
+    pub fn record_game(&mut self, game_state: &State, player_id: PlayerId, opponent: Opponent) {
+        let idx = self
+            .records
+            .iter()
+            .position(|record| record.opponent == opponent)
+            .unwrap_or_else(|| {
+                self.records.push(Record::new(opponent));
+                self.records.len() - 1
+            });
+        let record = &mut self.records[idx];
+        record.games += 1;
+        for outcome in game_state
+            .log
+            .iter()
+            .filter_map(|round_log| round_log.outcome)
+        {
+            let result = RoundResult::new(outcome, player_id);
+            record.rounds += 1;
+            match result {
+                RoundResult::Win => record.wins += 1,
+                RoundResult::Draw => record.draws += 1,
+                RoundResult::Loss => record.losses += 1,
+            }
+            self.streaks.add(result);
+        }
+    }
+}
+
+/// Who a [`Profile`] has played against.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Opponent {
+    Human,
+    Ai(AiKind),
+    /// [`MenaceAi`](crate::ai::menace::MenaceAi) trained for the number of epochs.
+    Menace(u32),
+    /// [`PersonalityAi`](crate::ai::personality::PersonalityAi).
+    Personality(Personality),
+}
+
+impl Display for Opponent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Human => f.write_str("human"),
+            Self::Ai(kind) => write!(f, "AI ({kind})"),
+            Self::Menace(epoch) => write!(f, "AI (MENACE, epoch {epoch})"),
+            Self::Personality(personality) => write!(f, "AI ({personality})"),
+        }
+    }
+}
+
+/// The results of the rounds played against an [`Opponent`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub opponent: Opponent,
+    pub games: u32,
+    pub rounds: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Record {
+    fn new(opponent: Opponent) -> Self {
+        Self {
+            opponent,
+            games: 0,
+            rounds: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundResult {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl RoundResult {
+    /// Returns the result for the `player_id`.
+    pub fn new(outcome: Outcome, player_id: PlayerId) -> Self {
+        match outcome {
+            Outcome::Win(winner, _) if winner == player_id => Self::Win,
+            Outcome::Surrender(loser) if loser != player_id => Self::Win,
+            Outcome::Draw => Self::Draw,
+            Outcome::Win(..) | Outcome::Surrender(_) => Self::Loss,
+        }
+    }
+
+    /// As in chess: 1 for a win, 0.5 for a draw, 0 for a loss.
+    pub fn score(self) -> f64 {
+        match self {
+            Self::Win => 1.0,
+            Self::Draw => 0.5,
+            Self::Loss => 0.0,
+        }
+    }
+}
+
+impl Display for RoundResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Win => "win",
+            Self::Draw => "draw",
+            Self::Loss => "loss",
+        })
+    }
+}
+
+/// The rounds with the same result in a row, across all opponents.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Streaks {
+    /// The result of the latest rounds and the number of them.
+    pub current: Option<(RoundResult, u32)>,
+    pub longest_wins: u32,
+    pub longest_losses: u32,
+}
+
+impl Streaks {
+    fn add(&mut self, result: RoundResult) {
+        let len = match self.current {
+            Some((current, len)) if current == result => len + 1,
+            _ => 1,
+        };
+        self.current = Some((result, len));
+        match result {
+            RoundResult::Win => self.longest_wins = self.longest_wins.max(len),
+            RoundResult::Loss => self.longest_losses = self.longest_losses.max(len),
+            RoundResult::Draw => {}
+        }
+    }
+}
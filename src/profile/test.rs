@@ -0,0 +1,118 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Profiles {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        ai::{personality::Personality, AiKind},
+        game::{Mark, PlayerId},
+        profile::{Opponent, Profiles},
+        test::{state, temp_dir},
+    };
+
+    fn profiles() -> Profiles {
+        let mut profiles = Profiles::default();
+        profiles.add("a, \"b\"", Mark::O).unwrap();
+        profiles.get_mut("a, \"b\"").unwrap().record_game(
+            &state(&["W0", "D", "S0"]),
+            PlayerId::new(1),
+            Opponent::Ai(AiKind::Random),
+        );
+        profiles.add("c", Mark::X).unwrap();
+        profiles.get_mut("c").unwrap().record_game(
+            &state(&["W1"]),
+            PlayerId::new(0),
+            Opponent::Personality(Personality::Careless),
+        );
+        profiles
+    }
+
+    #[test]
+    fn add() {
+        let profiles = profiles();
+        assert_eq!(profiles.active.as_deref(), Some("c"));
+        assert_eq!(profiles.active().unwrap().preferred_mark, Mark::X);
+        assert_eq!(profiles.get("a, \"b\"").unwrap().preferred_mark, Mark::O);
+    }
+
+    #[test_case(" ", "The name of a profile must not be blank.")]
+    #[test_case(" c ", "The profile `c` already exists.")]
+    fn add__err(name: &str, expected: &str) {
+        assert_eq!(profiles().add(name, Mark::X).unwrap_err(), expected);
+    }
+
+    #[test]
+    fn write_read() {
+        let dir = temp_dir("profiles");
+        let path = dir.join("profiles.json");
+        assert_eq!(Profiles::read(&path).unwrap(), Profiles::default());
+        let profiles = profiles();
+        profiles.write(&path).unwrap();
+        let read = Profiles::read(&path);
+        assert_eq!(read.unwrap(), profiles);
+    }
+
+    #[test]
+    fn to_csv() {
+        assert_eq!(
+            profiles().to_csv(),
+            "profile,preferred_mark,opponent,games,rounds,wins,draws,losses,\
+                current_streak,longest_win_streak,longest_loss_streak\n\
+            \"a, \"\"b\"\"\",O,AI (random),1,3,1,1,1,1 win,1,1\n\
+            c,X,AI (careless),1,1,0,0,1,1 loss,0,1\n"
+        );
+    }
+}
+
+mod Profile {
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::{
+        ai::AiKind,
+        game::{Mark, PlayerId},
+        profile::{Opponent, Profile, Record, RoundResult, Streaks},
+        test::state,
+    };
+
+    #[test]
+    fn record_game() {
+        let mut profile = Profile::new("a".to_owned(), Mark::X);
+        let ai = Opponent::Ai(AiKind::Random);
+        let p0 = PlayerId::new(0);
+        profile.record_game(&state(&["W0", "W0", "S1", "D", "W1"]), p0, ai);
+        profile.record_game(&state(&["S0", "W1", "D"]), p0, ai);
+        profile.record_game(&state(&["W0", "W0"]), p0, Opponent::Human);
+        assert_eq!(
+            profile.records,
+            vec![
+                Record {
+                    opponent: ai,
+                    games: 2,
+                    rounds: 8,
+                    wins: 3,
+                    draws: 2,
+                    losses: 3,
+                },
+                Record {
+                    opponent: Opponent::Human,
+                    games: 1,
+                    rounds: 2,
+                    wins: 2,
+                    draws: 0,
+                    losses: 0,
+                }
+            ]
+        );
+        assert_eq!((profile.games(), profile.rounds()), (3, 10));
+        assert_eq!(
+            profile.streaks,
+            Streaks {
+                current: Some((RoundResult::Win, 2)),
+                longest_wins: 3,
+                longest_losses: 3,
+            }
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Elo ratings of players and AIs, see [`Ratings`].
+
+use alloc::collections::BTreeMap;
+use core::str::FromStr;
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{PlayerId, State},
+    profile::RoundResult,
+    util::dirs,
+};
+
+mod test;
+
+/// What a single update of ratings is based on.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RatingPeriod {
+    /// Each round updates the ratings.
+    Round,
+    /// A game updates the ratings once, based on the average score of its rounds.
+    Match,
+}
+
+impl RatingPeriod {
+    pub const NAMES: [&'static str; 2] = ["round", "match"];
+}
+
+impl FromStr for RatingPeriod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "round" => Ok(Self::Round),
+            "match" => Ok(Self::Match),
+            _ => Err(format!(
+                "unknown rating period {s:?}, expected one of {:?}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub value: f64,
+    /// The number of updates the rating is based on.
+    pub updates: u32,
+}
+
+impl Rating {
+    /// The rating of those who have not been rated yet.
+    pub const INITIAL: Self = Self {
+        value: 1500.0,
+        updates: 0,
+    };
+}
+
+/// The ratings by the names of the rated players and AIs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ratings {
+    pub v: BTreeMap<String, Rating>,
+}
+
+impl Ratings {
+    const FILE_NAME: &'static str = "ratings.json";
+    /// The maximum change of a rating in a single update.
+    const K: f64 = 32.0;
+
+    /// Returns the path of the ratings file inside [`dirs::data_dir()`].
+    pub fn path(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::data_dir(env).map(|dir| dir.join(Self::FILE_NAME))
+    }
+
+    /// Returns no ratings if the file at `path` does not exist.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Creates the parent directory of `path` if needed.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn get(&self, name: &str) -> Rating {
+        self.v.get(name).copied().unwrap_or(Rating::INITIAL)
+    }
+
+    /// Updates the ratings of the players, whose `names` are indexed by [`PlayerId`],
+    /// based on the finished rounds of the game, and returns the changes of the ratings.
+    pub fn update(
+        &mut self,
+        names: [&str; 2],
+        game_state: &State,
+        period: RatingPeriod,
+    ) -> [f64; 2] {
+        let before = names.map(|name| self.get(name).value);
+        let scores = game_state
+            .log
+            .iter()
+            .filter_map(|round_log| round_log.outcome)
+            .map(|outcome| RoundResult::new(outcome, PlayerId::new(0)).score())
+            .collect::<Vec<_>>();
+        match period {
+            RatingPeriod::Round => {
+                for score in scores {
+                    self.rate(names, score);
+                }
+            }
+            RatingPeriod::Match => {
+                if !scores.is_empty() {
+                    #[allow(clippy::cast_precision_loss)]
+                    let score = scores.iter().sum::<f64>() / scores.len() as f64;
+                    self.rate(names, score);
+                }
+            }
+        }
+        [0, 1].map(|idx| self.get(names[idx]).value - before[idx])
+    }
+
+    /// Returns the names and ratings, the highest rating first.
+    pub fn leaderboard(&self) -> Vec<(&str, Rating)> {
+        let mut leaderboard = self
+            .v
+            .iter()
+            .map(|(name, &rating)| (name.as_str(), rating))
+            .collect::<Vec<_>>();
+        leaderboard.sort_by(|(_, a), (_, b)| b.value.total_cmp(&a.value));
+        leaderboard
+    }
+
+    /// The `score` is that of the first of the `names`.
+    fn rate(&mut self, names: [&str; 2], score: f64) {
+        let ratings = names.map(|name| self.get(name));
+        let expected = 1.0 / (1.0 + 10_f64.powf((ratings[1].value - ratings[0].value) / 400.0));
+        let change = Self::K * (score - expected);
+        for (idx, change) in [change, -change].into_iter().enumerate() {
+            self.v.insert(
+                names[idx].to_owned(),
+                Rating {
+                    value: ratings[idx].value + change,
+                    updates: ratings[idx].updates + 1,
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,70 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Ratings {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        rating::{Rating, RatingPeriod, Ratings},
+        test::{state, temp_dir},
+    };
+
+    fn rounded(v: f64) -> String {
+        format!("{v:.2}")
+    }
+
+    #[test_case(&["W0"], RatingPeriod::Round, ["16.00", "-16.00"])]
+    #[test_case(&["S0"], RatingPeriod::Round, ["-16.00", "16.00"])]
+    #[test_case(&["D"], RatingPeriod::Round, ["0.00", "0.00"])]
+    #[test_case(&["W0", "W0"], RatingPeriod::Round, ["30.53", "-30.53"])]
+    #[test_case(&["W0", "W0"], RatingPeriod::Match, ["16.00", "-16.00"])]
+    #[test_case(&["W0", "W1", "D"], RatingPeriod::Match, ["0.00", "0.00"])]
+    fn update(outcomes: &[&str], period: RatingPeriod, expected: [&str; 2]) {
+        let mut ratings = Ratings::default();
+        let changes = ratings.update(["a", "b"], &state(outcomes), period);
+        assert_eq!(changes.map(rounded), expected);
+        assert_eq!(
+            rounded(ratings.get("a").value),
+            rounded(Rating::INITIAL.value + changes[0])
+        );
+    }
+
+    #[test]
+    fn update__favorite() {
+        let mut ratings = Ratings::default();
+        ratings.update(["a", "b"], &state(&["W0"; 10]), RatingPeriod::Round);
+        let changes = ratings.update(["a", "b"], &state(&["W0"]), RatingPeriod::Round);
+        assert!(changes[0] < 16.0 && changes[0] > 0.0);
+        let changes = ratings.update(["a", "b"], &state(&["W1"]), RatingPeriod::Round);
+        assert!(changes[1] > 16.0);
+        assert_eq!(ratings.get("a").updates, 12);
+    }
+
+    #[test]
+    fn leaderboard() {
+        let mut ratings = Ratings::default();
+        ratings.update(["a", "b"], &state(&["W1"]), RatingPeriod::Round);
+        ratings.update(["c", "a"], &state(&["D"]), RatingPeriod::Round);
+        assert_eq!(
+            ratings
+                .leaderboard()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            vec!["b", "c", "a"]
+        );
+    }
+
+    #[test]
+    fn write_read() {
+        let dir = temp_dir("ratings");
+        let path = dir.join("ratings.json");
+        assert_eq!(Ratings::read(&path).unwrap(), Ratings::default());
+        let mut ratings = Ratings::default();
+        ratings.update(["a", "b"], &state(&["W0"]), RatingPeriod::Match);
+        ratings.write(&path).unwrap();
+        let read = Ratings::read(&path);
+        assert_eq!(read.unwrap(), ratings);
+    }
+}
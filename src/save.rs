@@ -0,0 +1,344 @@
+//! Saved games, see [`SavedGame`].
+
+use alloc::rc::Rc;
+use core::{
+    cell::RefCell,
+    convert::Infallible,
+    fmt,
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+use std::{
+    env,
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai,
+    ai::{
+        book::BookOpts,
+        mcts::MctsOpts,
+        menace::{Menace, MenaceAi},
+        replay::ReplayAi,
+        AiKind, Reason,
+    },
+    game::{
+        Cell, DefaultActionQueue, LocalPlayerType::Ai, Logic, Outcome, Player, PlayerId,
+        PlayerType::Local, State, World,
+    },
+    profile::Opponent,
+    util::dirs,
+};
+
+mod test;
+
+/// Everything needed to replay a game exactly.
+///
+/// A game is replayed from its [`Self::log`], which stores the moves of all players
+/// and the commentary on them, see [`Reason`].
+/// A game without the log is played by the AIs from the [`Self::seed`] instead,
+/// which reproduces the moves unless the AIs have changed since the game was saved,
+/// so such a game cannot have human players.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// The seed from which the seeds of the [`Ai`](crate::game::Ai)s
+    /// are derived via [`ai::player_seeds()`].
+    pub seed: u64,
+    pub rounds: u32,
+    /// Indexed by [`PlayerId`].
+    pub players: [SavedPlayer; 2],
+    /// The rounds as they have been played, see [`Self::with_log()`],
+    /// or nothing if the game is to be played by the AIs.
+    pub log: Vec<SavedRound>,
+}
+
+impl SavedGame {
+    const DIR: &'static str = "saves";
+    const EXTENSION: &'static str = "json";
+
+    /// A game to be played by the `ais` with the default options, indexed by [`PlayerId`].
+    pub const fn new(seed: u64, rounds: u32, ais: [AiKind; 2]) -> Self {
+        let [ai0, ai1] = ais;
+        Self {
+            seed,
+            rounds,
+            players: [SavedPlayer::ai(ai0), SavedPlayer::ai(ai1)],
+            log: Vec::new(),
+        }
+    }
+
+    /// The same game with the `mcts` options for all players.
+    #[must_use]
+    pub fn with_mcts(mut self, mcts: MctsOpts) -> Self {
+        for player in &mut self.players {
+            player.mcts = mcts;
+        }
+        self
+    }
+
+    /// The same game with the [`Self::log`] of the rounds played in the `state`.
+    #[must_use]
+    pub fn with_log(self, state: &State) -> Self {
+        let log = state
+            .log
+            .iter()
+            .map(|round_log| SavedRound {
+                moves: round_log
+                    .moves
+                    .iter()
+                    .map(|move_| SavedMove {
+                        cell: move_.cell,
+                        reason: move_.reason,
+                        think_time: move_.think_time,
+                    })
+                    .collect(),
+                surrendered: matches!(round_log.outcome, Some(Outcome::Surrender(_))),
+            })
+            .collect();
+        Self { log, ..self }
+    }
+
+    pub fn has_humans(&self) -> bool {
+        self.players
+            .iter()
+            .any(|player| player.kind == Opponent::Human)
+    }
+
+    /// Returns the directory with saved games inside [`dirs::data_dir()`].
+    pub fn dir(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::data_dir(env).map(|dir| dir.join(Self::DIR))
+    }
+
+    /// Creates a new file in `dir`, creating the directory if needed, and returns its path.
+    pub fn write(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let path = dir.join(format!(
+            "{secs}-{seed}.{ext}",
+            seed = self.seed,
+            ext = Self::EXTENSION
+        ));
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Returns the paths of the saved games in `dir`, the most recently saved first.
+    /// If `dir` does not exist, returns nothing.
+    pub fn list(dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut paths = entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        paths.retain(|path| path.extension().map_or(false, |ext| ext == Self::EXTENSION));
+        // the file names start with the number of seconds since the Unix epoch
+        paths.sort_unstable_by_key(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.split('-').next())
+                .and_then(|secs| secs.parse::<u64>().ok())
+        });
+        paths.reverse();
+        Ok(paths)
+    }
+
+    /// Creates a [`World`] in which the game is replayed from the [`Self::log`],
+    /// or played by the AIs if there is no log.
+    ///
+    /// # Errors
+    ///
+    /// If there is no log and a player is human, or an AI cannot be created,
+    /// described for the user.
+    pub fn world(&self) -> Result<World<DefaultActionQueue>, String> {
+        if self.log.is_empty() {
+            let books = self
+                .players
+                .iter()
+                .map(|player| player.book.as_ref().map(SavedBook::read).transpose())
+                .collect::<Result<Vec<_>, _>>()?;
+            self.try_world_with(|player_id, seed, action_queue| {
+                self.players[player_id.idx].create_ai(
+                    seed,
+                    action_queue,
+                    books[player_id.idx].as_ref(),
+                )
+            })
+        } else {
+            let log = Rc::<[SavedRound]>::from(self.log.as_slice());
+            Ok(self.world_with(|_, seed, action_queue| {
+                Box::new(ReplayAi::new(seed, action_queue, Rc::clone(&log)))
+            }))
+        }
+    }
+
+    /// Like [`Self::world()`], but the [`Ai`](crate::game::Ai)s are created by `create_ai`
+    /// from their [`PlayerId`]s, seeds, and action queues.
+    pub fn world_with(
+        &self,
+        create_ai: impl Fn(PlayerId, u64, Rc<DefaultActionQueue>) -> Box<dyn crate::game::Ai>,
+    ) -> World<DefaultActionQueue> {
+        match self.try_world_with(|player_id, seed, action_queue| {
+            Ok::<_, Infallible>(create_ai(player_id, seed, action_queue))
+        }) {
+            Ok(world) => world,
+            Err(e) => match e {},
+        }
+    }
+
+    fn try_world_with<E>(
+        &self,
+        create_ai: impl Fn(PlayerId, u64, Rc<DefaultActionQueue>) -> Result<Box<dyn crate::game::Ai>, E>,
+    ) -> Result<World<DefaultActionQueue>, E> {
+        let p0 = Player::new(PlayerId::new(0), Local(Ai));
+        let p1 = Player::new(PlayerId::new(1), Local(Ai));
+        let p0_act_queue = Rc::new(DefaultActionQueue::new(p0.id));
+        let p1_act_queue = Rc::new(DefaultActionQueue::new(p1.id));
+        let seeds = ai::player_seeds(self.seed);
+        let ais = vec![
+            create_ai(p0.id, seeds[0], Rc::clone(&p0_act_queue))?,
+            create_ai(p1.id, seeds[1], Rc::clone(&p1_act_queue))?,
+        ];
+        Ok(World::new(
+            State::new([p0, p1], self.rounds),
+            Logic::new([p0_act_queue, p1_act_queue]),
+            ais,
+        ))
+    }
+}
+
+/// A player of a [`SavedGame`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    /// Is [`Opponent::Human`] for a human player.
+    pub kind: Opponent,
+    /// The opening book the AI consults first, if any.
+    pub book: Option<SavedBook>,
+    /// Used only by [`AiKind::Mcts`].
+    pub mcts: MctsOpts,
+}
+
+impl SavedPlayer {
+    pub const HUMAN: Self = Self {
+        kind: Opponent::Human,
+        book: None,
+        mcts: MctsOpts::DEFAULT,
+    };
+
+    /// The `kind` of AI with the default options.
+    pub const fn ai(kind: AiKind) -> Self {
+        Self {
+            kind: Opponent::Ai(kind),
+            book: None,
+            mcts: MctsOpts::DEFAULT,
+        }
+    }
+
+    /// Creates the AI, which consults the `book` first, if any,
+    /// as read from the [`Self::book`], see [`SavedBook::read()`].
+    ///
+    /// # Errors
+    ///
+    /// If the player is human, or the AI is [`Opponent::Menace`] that cannot be read,
+    /// described for the user.
+    pub fn create_ai(
+        &self,
+        seed: u64,
+        action_queue: Rc<DefaultActionQueue>,
+        book: Option<&BookOpts>,
+    ) -> Result<Box<dyn crate::game::Ai>, String> {
+        let ai: Box<dyn crate::game::Ai> = match self.kind {
+            Opponent::Human => {
+                return Err("A human player cannot be replayed without the moves.".to_owned())
+            }
+            Opponent::Ai(kind) => kind.create_with(seed, Rc::clone(&action_queue), self.mcts),
+            Opponent::Personality(personality) => {
+                personality.create(seed, Rc::clone(&action_queue))
+            }
+            Opponent::Menace(epoch) => {
+                let path = Menace::dir(&|name| env::var_os(name))
+                    .map(|dir| Menace::path(&dir, epoch))
+                    .ok_or(
+                        "Cannot locate MENACE, neither $XDG_DATA_HOME nor $HOME is specified.",
+                    )?;
+                let menace = Menace::read(&path)
+                    .map_err(|e| format!("Failed to read {}: {e}.", path.display()))?;
+                Box::new(MenaceAi::new(
+                    seed,
+                    Rc::clone(&action_queue),
+                    Rc::new(RefCell::new(menace)),
+                ))
+            }
+        };
+        Ok(match book {
+            Some(book) => book.consulted_by(ai, seed, action_queue),
+            None => ai,
+        })
+    }
+}
+
+impl Display for SavedPlayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.book.is_some() {
+            write!(f, "{} with a book", self.kind)
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+/// Refers to the opening book of a [`SavedPlayer`], which is read again when the game is played.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedBook {
+    pub path: PathBuf,
+    /// In percent, see [`BookOpts::randomness`].
+    pub randomness: u8,
+}
+
+impl SavedBook {
+    /// # Errors
+    ///
+    /// See [`BookOpts::read()`].
+    pub fn read(&self) -> Result<BookOpts, String> {
+        BookOpts::read(&self.path, self.randomness)
+    }
+}
+
+impl From<&BookOpts> for SavedBook {
+    fn from(book: &BookOpts) -> Self {
+        Self {
+            path: book.path.clone(),
+            randomness: book.randomness,
+        }
+    }
+}
+
+/// A round of a [`SavedGame`] as it has been played.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedRound {
+    pub moves: Vec<SavedMove>,
+    /// Whether the player whose turn it has been after the [`Self::moves`] has surrendered.
+    pub surrendered: bool,
+}
+
+/// See [`Move`](crate::game::Move).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedMove {
+    pub cell: Cell,
+    pub reason: Option<Reason>,
+    pub think_time: Duration,
+}
@@ -0,0 +1,106 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod SavedGame {
+    use core::time::Duration;
+    use std::path::PathBuf;
+
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::{
+        ai::AiKind,
+        game::{Logic, Outcome},
+        save::{SavedGame, SavedMove, SavedPlayer, SavedRound},
+        sim,
+        test::temp_dir,
+        DefaultActionQueue, PlayerId,
+    };
+
+    const SAVED_GAME: SavedGame = SavedGame::new(42, 3, [AiKind::Random, AiKind::Random]);
+
+    #[test]
+    fn write_list_read() {
+        let dir = temp_dir("saves");
+        let (_, world) = sim::play_world(SAVED_GAME.world().unwrap());
+        let saved_game = SAVED_GAME.with_log(world.state());
+        assert_eq!(SavedGame::list(&dir).unwrap(), Vec::<PathBuf>::new());
+        let path = saved_game.write(&dir).unwrap();
+        assert_eq!(SavedGame::list(&dir).unwrap(), vec![path.clone()]);
+        assert_eq!(SavedGame::read(&path).unwrap(), saved_game);
+    }
+
+    #[test]
+    fn world__replays_exactly() {
+        let play = |delay_changes: usize| {
+            let mut world = SAVED_GAME.world().unwrap();
+            // the delays must not affect the moves
+            for _ in 0..delay_changes {
+                for ai in world.ais() {
+                    ai.set_base_act_delay(Duration::from_millis(1));
+                }
+            }
+            for ai in world.ais() {
+                ai.set_base_act_delay(Duration::ZERO);
+            }
+            let mut boards = Vec::new();
+            while !Logic::<DefaultActionQueue>::is_game_over(world.state()) {
+                world.advance();
+                boards.push(world.state().board.to_string());
+            }
+            let wins = [0, 1].map(|idx| world.state().players[idx].wins);
+            // so is the commentary
+            let reasons = world
+                .state()
+                .log
+                .iter()
+                .flat_map(|round_log| round_log.moves.iter().map(|mv| mv.reason))
+                .collect::<Vec<_>>();
+            (boards, wins, reasons)
+        };
+        assert_eq!(play(0), play(3));
+    }
+
+    #[test]
+    fn world__replays_log_of_any_players() {
+        let (outcomes, world) = sim::play_world(SAVED_GAME.world().unwrap());
+        let mut saved_game = SAVED_GAME.with_log(world.state());
+        saved_game.players = [SavedPlayer::HUMAN, SavedPlayer::ai(AiKind::Tablebase)];
+        let (replayed_outcomes, replayed_world) = sim::play_world(saved_game.world().unwrap());
+        assert_eq!(replayed_outcomes, outcomes);
+        assert_eq!(
+            saved_game.clone().with_log(replayed_world.state()),
+            saved_game
+        );
+    }
+
+    #[test]
+    fn world__replays_surrender() {
+        let saved_game = SavedGame {
+            rounds: 1,
+            players: [SavedPlayer::HUMAN, SavedPlayer::HUMAN],
+            log: vec![SavedRound {
+                moves: vec![SavedMove {
+                    cell: "b2".parse().unwrap(),
+                    reason: None,
+                    think_time: Duration::from_secs(1),
+                }],
+                surrendered: true,
+            }],
+            ..SAVED_GAME
+        };
+        let (_, world) = sim::play_world(saved_game.world().unwrap());
+        assert_eq!(
+            world.state().round_log().outcome,
+            Some(Outcome::Surrender(PlayerId::new(1)))
+        );
+    }
+
+    #[test]
+    fn world__rejects_humans_without_log() {
+        let saved_game = SavedGame {
+            players: [SavedPlayer::HUMAN, SavedPlayer::ai(AiKind::Random)],
+            ..SAVED_GAME
+        };
+        assert!(saved_game.world().is_err());
+    }
+}
@@ -1,8 +1,540 @@
 use alloc::sync::Arc;
-use core::error::Error;
+use core::{
+    error::Error,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use std::{
+    env,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc,
+        mpsc::{Receiver, RecvTimeoutError, Sender},
+        Mutex,
+    },
+    thread,
+};
 
-use crate::{cli::DedicatedArgs, process::ExitSignal};
+use crate::{
+    ai::{self, book::BookOpts},
+    cli::ServerArgs,
+    game::{Action, PlayerId, State},
+    process::ExitSignal,
+    profile::Opponent,
+    rating::RatingPeriod,
+    save::SavedGame,
+    server::{
+        account::Accounts,
+        host::HostedGame,
+        protocol::{Reply, Request, Session},
+    },
+};
 
-pub fn run(_args: &DedicatedArgs, _exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
-    todo!()
+mod account;
+mod console;
+mod host;
+mod protocol;
+
+/// How often the exit signal is checked while waiting for connections, clients and actions.
+const EXIT_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Serves each connection on a separate thread until the `exit_signal` is received.
+/// A connection starting with an HTTP request is served by the [`console`],
+/// otherwise it is a game client speaking the [`protocol`].
+/// Each game between the clients is hosted on a separate thread, see [`HostedGame`].
+pub fn run(args: &ServerArgs, exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
+    let server = Server::new(Pairing::Lobby(args.rating_period()))?;
+    let listener = Server::bind(args.listen())?;
+    server.accept(&listener, &|| exit_signal.is_received())
+}
+
+/// Hosts the games of a tournament entered by humans, whose clients connect to it
+/// as to the dedicated server, see [`run()`], and request to play each of their games.
+/// The games are not rated, as the accounts have no ratings against AIs.
+#[derive(Debug)]
+pub struct TournamentServer {
+    server: Arc<Server>,
+    /// Stops serving connections once set, i.e., once the tournament is over.
+    stopped: Arc<AtomicBool>,
+}
+
+impl TournamentServer {
+    /// Serves the connections on a separate thread until dropped or the `exit_signal` is received.
+    /// Only the clients logged in to the accounts named `names` may request to play.
+    pub fn start(
+        listen: SocketAddr,
+        names: &[String],
+        exit_signal: &Arc<ExitSignal>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let server = Server::new(Pairing::Tournament(names.to_vec()))?;
+        let accounts = server.accounts.lock().unwrap();
+        if let Some(name) = names.iter().find(|&name| accounts.get(name).is_none()) {
+            return Err(format!("The account `{name}` does not exist.").into());
+        }
+        drop(accounts);
+        let listener = Server::bind(listen)?;
+        let stopped = Arc::new(AtomicBool::new(false));
+        {
+            let server = Arc::clone(&server);
+            let stopped = Arc::clone(&stopped);
+            let exit_signal = Arc::clone(exit_signal);
+            thread::spawn(move || {
+                let stop = || stopped.load(Ordering::SeqCst) || exit_signal.is_received();
+                if let Err(e) = server.accept(&listener, &stop) {
+                    eprintln!("The tournament server has failed: {e}");
+                }
+            });
+        }
+        Ok(Self { server, stopped })
+    }
+
+    /// Plays the `game` once the clients of its human players are waiting for it,
+    /// whose accounts are named by `names` indexed by [`PlayerId`], the titles of the AIs
+    /// shown to their opponents, while the AIs consult the `book` first.
+    /// Returns the numbers of the rounds won by the players,
+    /// or [`None`] if `interrupted` before the game is over.
+    pub fn play(
+        &self,
+        game: &SavedGame,
+        names: [&str; 2],
+        book: Option<&BookOpts>,
+        interrupted: &dyn Fn() -> bool,
+    ) -> Option<[u32; 2]> {
+        let humans = [0, 1].map(|idx| game.players[idx].kind == Opponent::Human);
+        let (players, actions) = self.server.take_waiting(names, humans, interrupted)?;
+        let seeds = ai::player_seeds(game.seed);
+        let hosted_game = HostedGame::with_ais(game.rounds, |player_id, action_queue| {
+            (!humans[player_id.idx]).then(|| {
+                game.players[player_id.idx]
+                    .create_ai(seeds[player_id.idx], action_queue, book)
+                    .expect("the AIs should be possible to create")
+            })
+        });
+        let hosted_game = Server::host(hosted_game, &players, &actions, interrupted)?;
+        for (_, client) in &players {
+            if let Some(client) = client {
+                client.notify(&Reply::GameOver {
+                    rating: None,
+                    rating_change: None,
+                });
+            }
+        }
+        let state = hosted_game.state();
+        Some([state.players[0].wins, state.players[1].wins])
+    }
+}
+
+impl Drop for TournamentServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Who plays whom on a [`Server`].
+#[derive(Debug)]
+enum Pairing {
+    /// The clients waiting for a game play each other as soon as there are two of them,
+    /// and the games are rated for the period.
+    Lobby(RatingPeriod),
+    /// The clients of the accounts entering a tournament, which are named,
+    /// wait until they are paired by it, see [`TournamentServer`].
+    Tournament(Vec<String>),
+}
+
+/// A player of a game hosted by a [`Server`], either the name of an account and its client,
+/// or the title of an AI.
+type HostedPlayer = (String, Option<Arc<Client>>);
+
+/// The actions of the players of a game, or [`None`] if a player has left.
+type PlayerAction = (PlayerId, Option<Action>);
+
+#[derive(Debug)]
+struct Server {
+    accounts: Mutex<Accounts>,
+    /// Where the [`Self::accounts`] are written when they change.
+    path: PathBuf,
+    pairing: Pairing,
+    /// The names of the accounts of the clients waiting for a game, and the clients.
+    waiting: Mutex<Vec<(String, Arc<Client>)>>,
+}
+
+impl Server {
+    fn new(pairing: Pairing) -> Result<Arc<Self>, Box<dyn Error>> {
+        let path = Accounts::path(&|name| env::var_os(name))
+            .ok_or("Cannot locate accounts, neither $XDG_DATA_HOME nor $HOME is specified.")?;
+        let accounts = Accounts::read(&path)
+            .map_err(|e| format!("Failed to read {}: {e}.", path.display()))?;
+        Ok(Arc::new(Self {
+            accounts: Mutex::new(accounts),
+            path,
+            pairing,
+            waiting: Mutex::new(Vec::new()),
+        }))
+    }
+
+    fn bind(listen: SocketAddr) -> Result<TcpListener, Box<dyn Error>> {
+        let listener =
+            TcpListener::bind(listen).map_err(|e| format!("Failed to listen on {listen}: {e}."))?;
+        listener.set_nonblocking(true)?;
+        Ok(listener)
+    }
+
+    /// Serves each connection on a separate thread until `stop` returns `true`.
+    fn accept(
+        self: &Arc<Self>,
+        listener: &TcpListener,
+        stop: &dyn Fn() -> bool,
+    ) -> Result<(), Box<dyn Error>> {
+        while !stop() {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let server = Arc::clone(self);
+                    thread::spawn(move || {
+                        // a failed connection affects only its client
+                        if let Err(e) = server.serve(stream) {
+                            eprintln!("A connection has failed: {e}");
+                        }
+                    });
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(EXIT_CHECK_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    fn serve(self: &Arc<Self>, stream: TcpStream) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        if line.starts_with("GET ") || line.contains(" HTTP/") {
+            let response = console::respond(&line, &self.accounts.lock().unwrap());
+            return (&stream).write_all(response.as_bytes());
+        }
+        let client = Arc::new(Client {
+            writer: Mutex::new(stream),
+            seat: Mutex::new(None),
+        });
+        let served = self.serve_client(&client, reader, line);
+        self.leave(&client);
+        served
+    }
+
+    /// Serves the client speaking the [`protocol`], whose first request is the `line`.
+    fn serve_client(
+        self: &Arc<Self>,
+        client: &Arc<Client>,
+        mut reader: impl BufRead,
+        mut line: String,
+    ) -> io::Result<()> {
+        let mut session = Session::default();
+        loop {
+            let reply = match serde_json::from_str::<Request>(line.trim_end()) {
+                Ok(request) if request.is_game() => match session.name() {
+                    Some(name) => self.handle_game(&request, name, client),
+                    None => Some(Reply::Error {
+                        message: Session::LOGIN_FIRST_ERR.to_owned(),
+                    }),
+                },
+                Ok(request) => {
+                    let registers = matches!(request, Request::Register { .. });
+                    let Some(reply) = session.handle(request, &self.accounts) else {
+                        return Ok(());
+                    };
+                    if registers && matches!(reply, Reply::LoggedIn { .. }) {
+                        write_accounts(&self.accounts.lock().unwrap(), &self.path)?;
+                    }
+                    Some(reply)
+                }
+                Err(e) => Some(Reply::Error {
+                    message: format!("Invalid request: {e}."),
+                }),
+            };
+            if let Some(reply) = reply {
+                client.send(&reply)?;
+            }
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handles the `request` about a game of the `client` logged in to the account named `name`,
+    /// and returns the reply, unless it is sent by the game.
+    fn handle_game(
+        self: &Arc<Self>,
+        request: &Request,
+        name: &str,
+        client: &Arc<Client>,
+    ) -> Option<Reply> {
+        let error = |message: &str| {
+            Some(Reply::Error {
+                message: message.to_owned(),
+            })
+        };
+        let action = match request {
+            Request::Play => return self.play(name, client),
+            Request::Occupy { cell } => Action::Occupy(*cell, None),
+            Request::Surrender => Action::Surrender,
+            _ => panic!("the request must be about a game: {request:?}"),
+        };
+        match &*client.seat.lock().unwrap() {
+            // a failure means that the game is over
+            Some(seat) => seat
+                .actions
+                .send((seat.player_id, Some(action)))
+                .err()
+                .and_then(|_| error("The game is over.")),
+            None => error("You are not in a game."),
+        }
+    }
+
+    /// Makes the `client` wait for a game, which in the [`Pairing::Lobby`] starts immediately
+    /// if another client is waiting.
+    fn play(self: &Arc<Self>, name: &str, client: &Arc<Client>) -> Option<Reply> {
+        let error = |message: String| Some(Reply::Error { message });
+        if client.seat.lock().unwrap().is_some() {
+            return error("You are already in a game.".to_owned());
+        }
+        let mut waiting = self.waiting.lock().unwrap();
+        if waiting
+            .iter()
+            .any(|(_, waiting_client)| Arc::ptr_eq(waiting_client, client))
+        {
+            return error("You are already waiting for a game.".to_owned());
+        }
+        if waiting.iter().any(|(waiting_name, _)| waiting_name == name) {
+            return error(format!(
+                "The account `{name}` is already waiting for a game."
+            ));
+        }
+        match &self.pairing {
+            Pairing::Lobby(rating_period) => {
+                let Some((opponent_name, opponent)) = waiting.pop() else {
+                    waiting.push((name.to_owned(), Arc::clone(client)));
+                    return Some(Reply::Waiting);
+                };
+                // the client that has waited plays X
+                let players = [
+                    (opponent_name, Some(opponent)),
+                    (name.to_owned(), Some(Arc::clone(client))),
+                ];
+                let actions = Self::seat(&players);
+                let (server, rating_period) = (Arc::clone(self), *rating_period);
+                thread::spawn(move || {
+                    let game = HostedGame::new(State::DEFAULT_ROUNDS);
+                    if let Some(game) = Self::host(game, &players, &actions, &|| false) {
+                        server.record(&game, &players, rating_period);
+                    }
+                });
+                None
+            }
+            Pairing::Tournament(names) if names.iter().all(|entrant| entrant != name) => error(
+                format!("The account `{name}` has not entered the tournament."),
+            ),
+            Pairing::Tournament(_) => {
+                waiting.push((name.to_owned(), Arc::clone(client)));
+                Some(Reply::Waiting)
+            }
+        }
+    }
+
+    /// Waits until the clients of the players that are `humans`, whose accounts are named by
+    /// `names`, are waiting for a game, and seats them, see [`Self::seat()`].
+    /// Returns [`None`] if `interrupted` first.
+    fn take_waiting(
+        &self,
+        names: [&str; 2],
+        humans: [bool; 2],
+        interrupted: &dyn Fn() -> bool,
+    ) -> Option<([HostedPlayer; 2], Receiver<PlayerAction>)> {
+        loop {
+            {
+                let mut waiting = self.waiting.lock().unwrap();
+                let is_waiting = |idx: usize| waiting.iter().any(|(name, _)| name == names[idx]);
+                if (0..2).all(|idx| !humans[idx] || is_waiting(idx)) {
+                    let players = [0, 1].map(|idx| {
+                        let client = humans[idx].then(|| {
+                            let position = waiting
+                                .iter()
+                                .position(|(name, _)| name == names[idx])
+                                .expect("the client should be waiting");
+                            waiting.remove(position).1
+                        });
+                        (names[idx].to_owned(), client)
+                    });
+                    let actions = Self::seat(&players);
+                    return Some((players, actions));
+                }
+            }
+            if interrupted() {
+                return None;
+            }
+            thread::sleep(EXIT_CHECK_INTERVAL);
+        }
+    }
+
+    /// Seats the clients of the `players` indexed by [`PlayerId`],
+    /// and returns the receiver of their actions.
+    fn seat(players: &[HostedPlayer; 2]) -> Receiver<PlayerAction> {
+        let (sender, receiver) = mpsc::channel();
+        for (idx, (_, client)) in players.iter().enumerate() {
+            if let Some(client) = client {
+                *client.seat.lock().unwrap() = Some(Seat {
+                    player_id: PlayerId::new(idx),
+                    actions: sender.clone(),
+                });
+            }
+        }
+        receiver
+    }
+
+    /// Plays the `game` of the seated `players` indexed by [`PlayerId`] with their `actions`,
+    /// and returns it once it is over, or [`None`] if `interrupted` first.
+    fn host(
+        mut game: HostedGame,
+        players: &[HostedPlayer; 2],
+        actions: &Receiver<PlayerAction>,
+        interrupted: &dyn Fn() -> bool,
+    ) -> Option<HostedGame> {
+        let notify = |idx: usize, reply: &Reply| {
+            if let Some(client) = &players[idx].1 {
+                client.notify(reply);
+            }
+        };
+        for idx in [0, 1] {
+            notify(
+                idx,
+                &Reply::GameStarted {
+                    opponent: players[1 - idx].0.clone(),
+                    mark: PlayerId::new(idx).mark(),
+                    rounds: game.state().rounds,
+                },
+            );
+        }
+        let mut replies = game.start();
+        let over = loop {
+            for (idx, replies) in replies.iter().enumerate() {
+                for reply in replies {
+                    notify(idx, reply);
+                }
+            }
+            if game.is_over() {
+                break true;
+            }
+            replies = Default::default();
+            // the clients hold the senders until the game is over
+            match actions.recv_timeout(EXIT_CHECK_INTERVAL) {
+                Ok((player_id, Some(action))) => match game.act(player_id, action) {
+                    Ok(game_replies) => replies = game_replies,
+                    Err(message) => replies[player_id.idx].push(Reply::Error { message }),
+                },
+                Ok((player_id, None)) => replies = game.leave(player_id),
+                Err(RecvTimeoutError::Timeout) if interrupted() => break false,
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => panic!("the clients should be seated"),
+            }
+        };
+        for (idx, (_, client)) in players.iter().enumerate() {
+            if let Some(client) = client {
+                *client.seat.lock().unwrap() = None;
+            }
+            if !over {
+                notify(
+                    idx,
+                    &Reply::Error {
+                        message: "The game has been interrupted.".to_owned(),
+                    },
+                );
+            }
+        }
+        over.then_some(game)
+    }
+
+    /// Rates the `game` that is over, adds it to the histories of the accounts of the `players`,
+    /// and tells them about it.
+    fn record(&self, game: &HostedGame, players: &[HostedPlayer; 2], rating_period: RatingPeriod) {
+        let mut accounts = self.accounts.lock().unwrap();
+        let recorded = accounts.record_game(
+            [players[0].0.as_str(), players[1].0.as_str()],
+            game.state(),
+            rating_period,
+        );
+        for (idx, (name, client)) in players.iter().enumerate() {
+            let Some(client) = client else {
+                continue;
+            };
+            client.notify(&match &recorded {
+                Ok(rating_changes) => Reply::GameOver {
+                    rating: Some(accounts.rating(name)),
+                    rating_change: Some(rating_changes[idx]),
+                },
+                Err(message) => Reply::Error {
+                    message: message.clone(),
+                },
+            });
+        }
+        if recorded.is_ok() {
+            if let Err(e) = write_accounts(&accounts, &self.path) {
+                eprintln!("A game has not been saved: {e}");
+            }
+        }
+    }
+
+    /// Stops the `client` waiting for a game, or leaves its game, if any.
+    fn leave(&self, client: &Arc<Client>) {
+        let mut waiting = self.waiting.lock().unwrap();
+        waiting.retain(|(_, waiting_client)| !Arc::ptr_eq(waiting_client, client));
+        if let Some(seat) = client.seat.lock().unwrap().take() {
+            // a failure means that the game is over
+            let _ = seat.actions.send((seat.player_id, None));
+        }
+    }
+}
+
+/// A client speaking the [`protocol`], which may be sent replies by its connection and its game.
+#[derive(Debug)]
+struct Client {
+    writer: Mutex<TcpStream>,
+    seat: Mutex<Option<Seat>>,
+}
+
+impl Client {
+    fn send(&self, reply: &Reply) -> io::Result<()> {
+        writeln!(
+            self.writer.lock().unwrap(),
+            "{}",
+            serde_json::to_string(reply)?
+        )
+    }
+
+    /// Like [`Self::send()`], but a failure is left to the connection of the client to handle,
+    /// which also fails.
+    fn notify(&self, reply: &Reply) {
+        drop(self.send(reply));
+    }
+}
+
+/// The place of a [`Client`] in a game.
+#[derive(Debug)]
+struct Seat {
+    player_id: PlayerId,
+    actions: Sender<PlayerAction>,
+}
+
+fn write_accounts(accounts: &Accounts, path: &Path) -> io::Result<()> {
+    accounts.write(path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("Failed to write {}: {e}.", path.display()),
+        )
+    })
 }
@@ -0,0 +1,210 @@
+//! Registered accounts of the players of the dedicated server, see [`Accounts`].
+
+use alloc::collections::BTreeMap;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::{
+    ffi::OsString,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{PlayerId, State},
+    profile::RoundResult,
+    rating::{Rating, RatingPeriod, Ratings},
+    util::dirs,
+};
+
+mod test;
+
+/// The accounts by their names, and the ratings of the accounts that have finished games.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Accounts {
+    accounts: BTreeMap<String, Account>,
+    ratings: Ratings,
+}
+
+impl Accounts {
+    const FILE_NAME: &'static str = "accounts.json";
+    /// Is the same regardless of whether the name or the password is wrong,
+    /// so that it does not reveal which names are registered.
+    pub const LOGIN_ERR: &'static str = "Invalid name or password.";
+
+    /// Returns the path of the accounts file inside [`dirs::data_dir()`].
+    pub fn path(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::data_dir(env).map(|dir| dir.join(Self::FILE_NAME))
+    }
+
+    /// Returns no accounts if the file at `path` does not exist.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Creates the parent directory of `path` if needed.
+    /// The file is created readable and writable only by its owner, as it contains password hashes.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        options
+            .open(path)?
+            .write_all(serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Account> {
+        self.accounts.get(name)
+    }
+
+    /// Adds an account without games named `name` with the surrounding whitespace removed.
+    ///
+    /// # Errors
+    ///
+    /// If the `name` is blank or is already taken.
+    pub fn insert(&mut self, name: &str, password: Password) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("The name of an account must not be blank.".to_owned());
+        }
+        if self.get(name).is_some() {
+            return Err(format!("The account `{name}` already exists."));
+        }
+        let account = Account {
+            password,
+            history: Vec::new(),
+        };
+        self.accounts.insert(name.to_owned(), account);
+        Ok(())
+    }
+
+    /// Returns the password of the account named `name` with the surrounding whitespace removed,
+    /// to [`Password::verify()`] it without borrowing the accounts.
+    ///
+    /// # Errors
+    ///
+    /// If there is no such account, with the same description as that of a wrong password.
+    pub fn password(&self, name: &str) -> Result<Password, String> {
+        self.get(name.trim())
+            .map(|account| account.password.clone())
+            .ok_or_else(|| Self::LOGIN_ERR.to_owned())
+    }
+
+    /// Returns [`Rating::INITIAL`] for accounts that have not finished games.
+    pub fn rating(&self, name: &str) -> Rating {
+        self.ratings.get(name)
+    }
+
+    /// Rates the finished game of the accounts, whose `names` are indexed by [`PlayerId`],
+    /// adds it to their histories, and returns the changes of their ratings.
+    ///
+    /// # Errors
+    ///
+    /// If any of the `names` is not registered.
+    pub fn record_game(
+        &mut self,
+        names: [&str; 2],
+        game_state: &State,
+        period: RatingPeriod,
+    ) -> Result<[f64; 2], String> {
+        if let Some(name) = names.iter().find(|&&name| self.get(name).is_none()) {
+            return Err(format!("The account `{name}` does not exist."));
+        }
+        let rating_changes = self.ratings.update(names, game_state, period);
+        for idx in [0, 1] {
+            let player_id = PlayerId::new(idx);
+            let results = game_state
+                .log
+                .iter()
+                .filter_map(|round_log| round_log.outcome)
+                .map(|outcome| RoundResult::new(outcome, player_id))
+                .collect();
+            self.accounts
+                .get_mut(names[idx])
+                .expect("the account must exist")
+                .history
+                .push(GameRecord {
+                    opponent: names[1 - idx].to_owned(),
+                    results,
+                    rating_change: rating_changes[idx],
+                });
+        }
+        Ok(rating_changes)
+    }
+
+    /// Returns the names and ratings of all the accounts, the highest rating first.
+    pub fn leaderboard(&self) -> Vec<(&str, Rating)> {
+        let mut leaderboard = self
+            .accounts
+            .keys()
+            .map(|name| (name.as_str(), self.rating(name)))
+            .collect::<Vec<_>>();
+        leaderboard.sort_by(|(_, a), (_, b)| b.value.total_cmp(&a.value));
+        leaderboard
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+    #[serde(rename = "password_hash")]
+    password: Password,
+    /// The finished games, the oldest first.
+    pub history: Vec<GameRecord>,
+}
+
+/// The hash of a password in the
+/// [PHC string format](https://github.com/P-H-C/phc-string-format/blob/master/phc-sf-spec.md).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Password(String);
+
+impl Password {
+    /// Hashes the `password`, which is slow on purpose, as is [`Self::verify()`],
+    /// so neither should be called while holding a lock shared by the clients of the server.
+    ///
+    /// # Errors
+    ///
+    /// If the `password` is empty, or it cannot be hashed.
+    pub fn new(password: &str) -> Result<Self, String> {
+        if password.is_empty() {
+            return Err("The password must not be empty.".to_owned());
+        }
+        Argon2::default()
+            .hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
+            .map(|hash| Self(hash.to_string()))
+            .map_err(|e| format!("Failed to hash the password: {e}."))
+    }
+
+    /// # Errors
+    ///
+    /// If the `password` is wrong, with the same description as that of an unknown name,
+    /// see [`Accounts::password()`].
+    pub fn verify(&self, password: &str) -> Result<(), String> {
+        PasswordHash::new(&self.0)
+            .and_then(|hash| Argon2::default().verify_password(password.as_bytes(), &hash))
+            .map_err(|_| Accounts::LOGIN_ERR.to_owned())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// The name of the account of the opponent.
+    pub opponent: String,
+    /// The results of the finished rounds.
+    pub results: Vec<RoundResult>,
+    pub rating_change: f64,
+}
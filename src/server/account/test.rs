@@ -0,0 +1,129 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Accounts {
+    #[cfg(unix)]
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        profile::RoundResult,
+        rating::{Rating, RatingPeriod},
+        server::account::{Accounts, Password},
+        test::{state, temp_dir},
+    };
+
+    fn accounts() -> Accounts {
+        let mut accounts = Accounts::default();
+        accounts.insert("a", Password::new("pa").unwrap()).unwrap();
+        accounts
+            .insert(" b ", Password::new("pb").unwrap())
+            .unwrap();
+        accounts
+    }
+
+    #[test_case(" ", "The name of an account must not be blank.")]
+    #[test_case(" a", "The account `a` already exists.")]
+    fn insert__err(name: &str, expected: &str) {
+        let password = Password::new("p").unwrap();
+        assert_eq!(accounts().insert(name, password).unwrap_err(), expected);
+    }
+
+    #[test_case("a", "pa", true)]
+    #[test_case("b ", "pb", true)]
+    #[test_case("a", "pb", false)]
+    #[test_case("c", "pa", false)]
+    fn password(name: &str, password: &str, expected: bool) {
+        let login = accounts()
+            .password(name)
+            .and_then(|hash| hash.verify(password));
+        assert_eq!(login.is_ok(), expected, "{login:?}");
+        if let Err(e) = login {
+            assert_eq!(e, "Invalid name or password.");
+        }
+    }
+
+    #[test]
+    fn record_game() {
+        let mut accounts = accounts();
+        let changes = accounts
+            .record_game(["a", "b"], &state(&["W0", "D"]), RatingPeriod::Match)
+            .unwrap();
+        assert!(changes[0] > 0.0);
+        let history = &accounts.get("b").unwrap().history;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].opponent, "a");
+        assert_eq!(
+            history[0].results,
+            vec![RoundResult::Loss, RoundResult::Draw]
+        );
+        assert_eq!(
+            accounts.get("a").unwrap().history[0].results,
+            vec![RoundResult::Win, RoundResult::Draw]
+        );
+    }
+
+    #[test]
+    fn record_game__err() {
+        assert_eq!(
+            accounts()
+                .record_game(["a", "c"], &state(&["W0"]), RatingPeriod::Match)
+                .unwrap_err(),
+            "The account `c` does not exist."
+        );
+    }
+
+    #[test]
+    fn leaderboard() {
+        let mut accounts = accounts();
+        accounts.insert("c", Password::new("pc").unwrap()).unwrap();
+        accounts
+            .record_game(["c", "a"], &state(&["W1"]), RatingPeriod::Round)
+            .unwrap();
+        let leaderboard = accounts.leaderboard();
+        assert_eq!(
+            leaderboard
+                .iter()
+                .map(|&(name, _)| name)
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(leaderboard[1].1.updates, Rating::INITIAL.updates);
+    }
+
+    #[test]
+    fn write_read() {
+        let dir = temp_dir("accounts");
+        let path = dir.join("accounts.json");
+        assert_eq!(Accounts::read(&path).unwrap(), Accounts::default());
+        let mut accounts = accounts();
+        accounts
+            .record_game(["a", "b"], &state(&["S1"]), RatingPeriod::Match)
+            .unwrap();
+        accounts.write(&path).unwrap();
+        let read = Accounts::read(&path).unwrap();
+        assert_eq!(read, accounts);
+        assert!(read.password("a").unwrap().verify("pa").is_ok());
+        #[cfg(unix)]
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+    }
+}
+
+mod Password {
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::server::account::Password;
+
+    #[test]
+    fn new__err() {
+        assert_eq!(
+            Password::new("").unwrap_err(),
+            "The password must not be empty."
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! The web console of the dedicated server, see [`respond()`].
+
+use core::fmt::Write;
+
+use crate::server::account::Accounts;
+
+mod test;
+
+/// Returns the HTTP response to the request whose first line is the `request_line`,
+/// which is the leaderboard page for `GET /`, and an error otherwise.
+pub fn respond(request_line: &str, accounts: &Accounts) -> String {
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/")) => ("200 OK", leaderboard_page(accounts)),
+        (Some("GET"), _) => ("404 Not Found", "Not found.".to_owned()),
+        _ => ("405 Method Not Allowed", "Only GET is allowed.".to_owned()),
+    };
+    format!(
+        "HTTP/1.1 {status}\r\n\
+        Content-Type: text/html; charset=utf-8\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\
+        \r\n\
+        {body}",
+        body.len()
+    )
+}
+
+fn leaderboard_page(accounts: &Accounts) -> String {
+    let mut page = "<!DOCTYPE html>\n\
+        <html><head><meta charset=\"utf-8\"><title>Leaderboard</title></head><body>\n\
+        <h1>Leaderboard</h1>\n\
+        <table>\n\
+        <tr><th>#</th><th>Name</th><th>Rating</th><th>Games</th></tr>\n"
+        .to_owned();
+    for (idx, (name, rating)) in accounts.leaderboard().into_iter().enumerate() {
+        let games = accounts
+            .get(name)
+            .map_or(0, |account| account.history.len());
+        writeln!(
+            page,
+            "<tr><td>{}</td><td>{}</td><td>{:.0}</td><td>{games}</td></tr>",
+            idx + 1,
+            escape(name),
+            rating.value
+        )
+        .unwrap();
+    }
+    page.push_str("</table>\n</body></html>\n");
+    page
+}
+
+/// Escapes the characters special in HTML.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,34 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod respond {
+    use test_case::test_case;
+
+    use crate::server::{
+        account::{Accounts, Password},
+        console,
+    };
+
+    fn accounts() -> Accounts {
+        let mut accounts = Accounts::default();
+        accounts.insert("<b>", Password::new("p").unwrap()).unwrap();
+        accounts
+    }
+
+    #[test]
+    fn leaderboard() {
+        let response = console::respond("GET / HTTP/1.1\r\n", &accounts());
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+        assert!(
+            response.contains("<tr><td>1</td><td>&lt;b&gt;</td><td>1500</td><td>0</td></tr>"),
+            "{response}"
+        );
+    }
+
+    #[test_case("GET /unknown HTTP/1.1", "HTTP/1.1 404 Not Found\r\n")]
+    #[test_case("POST / HTTP/1.1", "HTTP/1.1 405 Method Not Allowed\r\n")]
+    fn error(request_line: &str, expected_status_line: &str) {
+        let response = console::respond(request_line, &accounts());
+        assert!(response.starts_with(expected_status_line), "{response}");
+    }
+}
@@ -0,0 +1,187 @@
+//! Games hosted by the dedicated server, see [`HostedGame`].
+
+use alloc::rc::Rc;
+use core::time::Duration;
+
+use crate::{
+    game::{
+        Action, Ai, LocalPlayerType, Logic, Outcome,
+        Phase::Inround,
+        Player, PlayerId,
+        PlayerType::{Local, Remote},
+        State, World,
+    },
+    server::protocol::Reply,
+    DefaultActionQueue,
+};
+
+mod test;
+
+/// A game between two clients of the server, or a client and an [`Ai`].
+/// The clients are readied up automatically, and surrender whenever it is their turn
+/// once they have left. The [`Ai`]s act without delays, see [`Self::with_ais()`].
+#[derive(Debug)]
+pub struct HostedGame {
+    world: World<DefaultActionQueue>,
+    /// Indexed by [`PlayerId`].
+    action_queues: [Rc<DefaultActionQueue>; 2],
+    /// Indexed by [`PlayerId`].
+    left: [bool; 2],
+    /// The number of the rounds whose outcomes have been reported.
+    reported_rounds: usize,
+    /// The number of the moves of the round after the reported ones that have been reported.
+    reported_moves: usize,
+}
+
+impl HostedGame {
+    pub fn new(rounds: u32) -> Self {
+        Self::with_ais(rounds, |_, _| None)
+    }
+
+    /// Like [`Self::new()`], but each player for whom `create_ai` returns an [`Ai`]
+    /// from their [`PlayerId`] and action queue is played by it.
+    pub fn with_ais(
+        rounds: u32,
+        mut create_ai: impl FnMut(PlayerId, Rc<DefaultActionQueue>) -> Option<Box<dyn Ai>>,
+    ) -> Self {
+        let action_queues = [0, 1].map(|idx| Rc::new(DefaultActionQueue::new(PlayerId::new(idx))));
+        let mut ais = Vec::new();
+        let players = [0, 1].map(|idx| {
+            let player_id = PlayerId::new(idx);
+            match create_ai(player_id, Rc::clone(&action_queues[idx])) {
+                Some(mut ai) => {
+                    ai.set_base_act_delay(Duration::ZERO);
+                    ai.set_blocking(true);
+                    ais.push(ai);
+                    Player::new(player_id, Local(LocalPlayerType::Ai))
+                }
+                None => Player::new(player_id, Remote),
+            }
+        });
+        Self {
+            world: World::new(
+                State::new(players, rounds),
+                Logic::new(action_queues.clone()),
+                ais,
+            ),
+            action_queues,
+            left: [false; 2],
+            reported_rounds: 0,
+            reported_moves: 0,
+        }
+    }
+
+    /// Readies up the clients, and returns the replies to the players indexed by [`PlayerId`]
+    /// about the moves the [`Ai`]s have made, if any.
+    pub fn start(&mut self) -> [Vec<Reply>; 2] {
+        self.advance()
+    }
+
+    pub fn state(&self) -> &State {
+        self.world.state()
+    }
+
+    pub fn is_over(&self) -> bool {
+        Logic::<DefaultActionQueue>::is_game_over(self.state())
+    }
+
+    /// Makes the `action`, which must not be [`Action::Ready`], of the player,
+    /// and returns the replies to the players indexed by [`PlayerId`].
+    ///
+    /// # Errors
+    ///
+    /// If the action is not allowed, described for the player.
+    pub fn act(&mut self, player_id: PlayerId, action: Action) -> Result<[Vec<Reply>; 2], String> {
+        let state = self.state();
+        if self.is_over() {
+            return Err("The game is over.".to_owned());
+        }
+        if state.phase != Inround || state.turn() != player_id {
+            return Err("It is not your turn.".to_owned());
+        }
+        match action {
+            Action::Occupy(cell, _) if state.board.get(&cell).is_some() => {
+                return Err(format!("The cell {cell} is occupied."));
+            }
+            Action::Ready => panic!("the players are readied up automatically"),
+            Action::Occupy(..) | Action::Surrender => {}
+        }
+        self.action_queues[player_id.idx].add(action);
+        Ok(self.advance())
+    }
+
+    /// The player leaves, after which they surrender whenever it is their turn.
+    /// Returns the replies to the players indexed by [`PlayerId`].
+    pub fn leave(&mut self, player_id: PlayerId) -> [Vec<Reply>; 2] {
+        self.left[player_id.idx] = true;
+        self.advance()
+    }
+
+    fn is_remote(&self, player_id: PlayerId) -> bool {
+        self.state().players[player_id.idx].typ == Remote
+    }
+
+    /// Advances the game until it waits for a client, and returns the replies to the players
+    /// about what has happened, indexed by [`PlayerId`].
+    fn advance(&mut self) -> [Vec<Reply>; 2] {
+        let mut replies = [Vec::new(), Vec::new()];
+        loop {
+            let state = self.world.state();
+            let before = (state.round, state.step, state.phase);
+            if self.is_over() {
+                break;
+            }
+            let ai_turn = state.phase == Inround && !self.is_remote(state.turn());
+            if state.phase == Inround {
+                if self.left[state.turn().idx] && !ai_turn {
+                    self.action_queues[state.turn().idx].add(Action::Surrender);
+                }
+            } else {
+                for &player_id in &state.required_ready {
+                    if self.is_remote(player_id) {
+                        self.action_queues[player_id.idx].add(Action::Ready);
+                    }
+                }
+            }
+            self.world.advance();
+            for reply in self.report() {
+                for player_replies in &mut replies {
+                    player_replies.push(reply.clone());
+                }
+            }
+            let state = self.world.state();
+            // the AIs may take more than one advance to act
+            if (state.round, state.step, state.phase) == before && !ai_turn {
+                break;
+            }
+        }
+        replies
+    }
+
+    /// Returns the replies about the moves and the outcomes that have not been reported yet.
+    fn report(&mut self) -> Vec<Reply> {
+        let mut replies = Vec::new();
+        while let Some(round_log) = self.world.state().log.get(self.reported_rounds) {
+            for move_ in &round_log.moves[self.reported_moves..] {
+                replies.push(Reply::Moved {
+                    mark: move_.player_id.mark(),
+                    cell: move_.cell,
+                });
+            }
+            self.reported_moves = round_log.moves.len();
+            let Some(outcome) = round_log.outcome else {
+                break;
+            };
+            replies.push(Reply::RoundOver {
+                winner: match outcome {
+                    Outcome::Win(player_id, _) => Some(player_id.mark()),
+                    Outcome::Surrender(player_id) => Some(PlayerId::new(1 - player_id.idx).mark()),
+                    Outcome::Draw => None,
+                },
+            });
+            self.reported_rounds += 1;
+            self.reported_moves = 0;
+        }
+        replies
+    }
+}
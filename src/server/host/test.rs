@@ -0,0 +1,114 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod HostedGame {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        ai::AiKind,
+        game::{Action, Mark, PlayerId},
+        save::SavedPlayer,
+        server::{host::HostedGame, protocol::Reply},
+    };
+
+    fn occupy(cell: &str) -> Action {
+        Action::Occupy(cell.parse().unwrap(), None)
+    }
+
+    fn moved(mark: Mark, cell: &str) -> Reply {
+        Reply::Moved {
+            mark,
+            cell: cell.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn act() {
+        let mut game = HostedGame::new(1);
+        assert_eq!(game.start(), [Vec::new(), Vec::new()]);
+        let x = PlayerId::new(0);
+        let o = PlayerId::new(1);
+        for (player_id, cell) in [(x, "a1"), (o, "b1"), (x, "a2"), (o, "b2")] {
+            let expected = vec![moved(player_id.mark(), cell)];
+            assert_eq!(
+                game.act(player_id, occupy(cell)).unwrap(),
+                [expected.clone(), expected]
+            );
+        }
+        assert!(!game.is_over());
+        let expected = vec![
+            moved(Mark::X, "a3"),
+            Reply::RoundOver {
+                winner: Some(Mark::X),
+            },
+        ];
+        assert_eq!(
+            game.act(x, occupy("a3")).unwrap(),
+            [expected.clone(), expected]
+        );
+        assert!(game.is_over());
+        assert_eq!(game.act(o, occupy("c3")).unwrap_err(), "The game is over.");
+    }
+
+    #[test_case(1, "a1", "It is not your turn.")]
+    #[test_case(0, "b2", "The cell b2 is occupied.")]
+    fn act__err(player_idx: usize, cell: &str, expected: &str) {
+        let mut game = HostedGame::new(1);
+        game.start();
+        game.act(PlayerId::new(0), occupy("b2")).unwrap();
+        game.act(PlayerId::new(1), occupy("a1")).unwrap();
+        assert_eq!(
+            game.act(PlayerId::new(player_idx), occupy(cell))
+                .unwrap_err(),
+            expected
+        );
+    }
+
+    #[test]
+    fn leave() {
+        let mut game = HostedGame::new(2);
+        game.start();
+        assert_eq!(game.leave(PlayerId::new(1)), [Vec::new(), Vec::new()]);
+        let x_won = Reply::RoundOver {
+            winner: Some(Mark::X),
+        };
+        // the player who has left starts the second round
+        let expected = vec![moved(Mark::X, "b2"), x_won.clone(), x_won];
+        assert_eq!(
+            game.act(PlayerId::new(0), occupy("b2")).unwrap(),
+            [expected.clone(), expected]
+        );
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn with_ais() {
+        let mut game = HostedGame::with_ais(1, |player_id, action_queue| {
+            (player_id == 0).then(|| {
+                SavedPlayer::ai(AiKind::Random)
+                    .create_ai(42, action_queue, None)
+                    .unwrap()
+            })
+        });
+        let replies = game.start();
+        assert!(matches!(
+            replies[1][..],
+            [Reply::Moved { mark: Mark::X, .. }]
+        ));
+        let o = PlayerId::new(1);
+        let occupied = (0..9)
+            .map(|idx| format!("{}{}", char::from(b'a' + idx % 3), idx / 3 + 1))
+            .find(|cell| game.act(o, occupy(cell)).is_ok())
+            .unwrap();
+        assert_eq!(game.state().board.get(&occupied.parse().unwrap()), Some(o));
+        assert_eq!(game.state().turn(), o);
+        assert_eq!(
+            game.leave(o)[0].last(),
+            Some(&Reply::RoundOver {
+                winner: Some(Mark::X)
+            })
+        );
+        assert!(game.is_over());
+    }
+}
@@ -0,0 +1,184 @@
+//! The protocol between game clients and the dedicated server, see [`Session`].
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{Cell, Mark},
+    rating::Rating,
+    server::account::{Accounts, GameRecord, Password},
+};
+
+mod test;
+
+/// A request of a client, sent as a JSON object on a single line,
+/// e.g., `{"login":{"name":"a","password":"p"}}`, or `"leaderboard"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Request {
+    /// Registers an account and logs in to it.
+    Register {
+        name: String,
+        password: String,
+    },
+    Login {
+        name: String,
+        password: String,
+    },
+    /// The finished games of the account the client is logged in to, the oldest first.
+    History,
+    Leaderboard,
+    /// Waits for a client logged in to another account to request a game too,
+    /// and starts a game with it, see [`Reply::GameStarted`]. On a tournament server,
+    /// waits for the next game of the account in the tournament instead.
+    Play,
+    /// Occupies the `cell` in the game of the client, e.g., `{"occupy":{"cell":"b2"}}`.
+    Occupy {
+        cell: Cell,
+    },
+    /// Surrenders the current round of the game of the client.
+    Surrender,
+    /// Ends the session, which is not replied to.
+    /// If the client is in a game, it surrenders the rest of the game.
+    Quit,
+}
+
+impl Request {
+    /// Whether the request is about the game of the client, which is handled by the server,
+    /// rather than by a [`Session`].
+    pub fn is_game(&self) -> bool {
+        matches!(self, Self::Play | Self::Occupy { .. } | Self::Surrender)
+    }
+}
+
+/// A reply of the server to a [`Request`], sent the same way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reply {
+    LoggedIn {
+        name: String,
+        rating: Rating,
+    },
+    History {
+        games: Vec<GameRecord>,
+    },
+    /// The highest rating first.
+    Leaderboard {
+        entries: Vec<LeaderboardEntry>,
+    },
+    /// There is no opponent for [`Request::Play`] yet.
+    Waiting,
+    /// The game of the client has started, in which it plays the `mark`.
+    GameStarted {
+        opponent: String,
+        mark: Mark,
+        rounds: u32,
+    },
+    /// A move in the game of the client, which is sent to both players.
+    Moved {
+        mark: Mark,
+        cell: Cell,
+    },
+    /// The `winner` is [`None`] if the round is drawn.
+    RoundOver {
+        winner: Option<Mark>,
+    },
+    /// The game of the client has ended, and has been rated unless it is a tournament game,
+    /// in which case both are [`None`].
+    GameOver {
+        rating: Option<Rating>,
+        rating_change: Option<f64>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub rating: Rating,
+}
+
+/// The state of the connection of a client, which must start with
+/// [`Request::Register`] or [`Request::Login`], the handshake.
+#[derive(Debug, Default)]
+pub struct Session {
+    /// The name of the account the client is logged in to.
+    name: Option<String>,
+}
+
+impl Session {
+    pub const LOGIN_FIRST_ERR: &'static str = "Log in or register first.";
+
+    /// The name of the account the client is logged in to, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the reply to the `request`, or [`None`] if the session has ended.
+    /// The `accounts` are changed only by a successful [`Request::Register`],
+    /// and are not locked while passwords are hashed or verified.
+    ///
+    /// # Panics
+    ///
+    /// If the `request` [is about a game](Request::is_game).
+    pub fn handle(&mut self, request: Request, accounts: &Mutex<Accounts>) -> Option<Reply> {
+        let reply = match (request, &self.name) {
+            (request @ (Request::Play | Request::Occupy { .. } | Request::Surrender), _) => {
+                panic!("the requests about games are handled by the server: {request:?}")
+            }
+            (Request::Quit, _) => return None,
+            (Request::Register { name, password }, _) => {
+                Password::new(&password).and_then(|password| {
+                    let mut accounts = accounts.lock().unwrap();
+                    accounts.insert(&name, password)?;
+                    Ok(self.logged_in(name.trim(), &accounts))
+                })
+            }
+            (
+                Request::Login {
+                    name,
+                    password: given,
+                },
+                _,
+            ) => {
+                let password = accounts.lock().unwrap().password(&name);
+                password
+                    .and_then(|password| password.verify(&given))
+                    .map(|()| self.logged_in(name.trim(), &accounts.lock().unwrap()))
+            }
+            (_, None) => Err(Self::LOGIN_FIRST_ERR.to_owned()),
+            (Request::History, Some(name)) => Ok(Reply::History {
+                games: accounts
+                    .lock()
+                    .unwrap()
+                    .get(name)
+                    .map(|account| account.history.clone())
+                    .unwrap_or_default(),
+            }),
+            (Request::Leaderboard, Some(_)) => Ok(Reply::Leaderboard {
+                entries: accounts
+                    .lock()
+                    .unwrap()
+                    .leaderboard()
+                    .into_iter()
+                    .map(|(name, rating)| LeaderboardEntry {
+                        name: name.to_owned(),
+                        rating,
+                    })
+                    .collect(),
+            }),
+        };
+        Some(reply.unwrap_or_else(|message| Reply::Error { message }))
+    }
+
+    fn logged_in(&mut self, name: &str, accounts: &Accounts) -> Reply {
+        self.name = Some(name.to_owned());
+        Reply::LoggedIn {
+            name: name.to_owned(),
+            rating: accounts.rating(name),
+        }
+    }
+}
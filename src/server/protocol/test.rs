@@ -0,0 +1,127 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Session {
+    use std::sync::Mutex;
+
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        rating::{Rating, RatingPeriod},
+        server::{
+            account::{Accounts, Password},
+            protocol::{LeaderboardEntry, Reply, Request, Session},
+        },
+        test::state,
+    };
+
+    fn accounts() -> Mutex<Accounts> {
+        let mut accounts = Accounts::default();
+        accounts.insert("a", Password::new("pa").unwrap()).unwrap();
+        accounts.insert("b", Password::new("pb").unwrap()).unwrap();
+        accounts
+            .record_game(["a", "b"], &state(&["W0"]), RatingPeriod::Match)
+            .unwrap();
+        Mutex::new(accounts)
+    }
+
+    fn request(json: &str) -> Request {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn error(message: &str) -> Reply {
+        Reply::Error {
+            message: message.to_owned(),
+        }
+    }
+
+    #[test_case(r#""history""#)]
+    #[test_case(r#""leaderboard""#)]
+    fn handle__before_handshake(json: &str) {
+        assert_eq!(
+            Session::default().handle(request(json), &accounts()),
+            Some(error("Log in or register first."))
+        );
+    }
+
+    #[test]
+    fn handle__login() {
+        let accounts = accounts();
+        let rating = |name| accounts.lock().unwrap().rating(name);
+        let mut session = Session::default();
+        assert_eq!(
+            session.handle(
+                request(r#"{"login":{"name":"a","password":"pb"}}"#),
+                &accounts
+            ),
+            Some(error("Invalid name or password."))
+        );
+        assert_eq!(
+            session.handle(
+                request(r#"{"login":{"name":" a ","password":"pa"}}"#),
+                &accounts
+            ),
+            Some(Reply::LoggedIn {
+                name: "a".to_owned(),
+                rating: rating("a"),
+            })
+        );
+        assert_eq!(
+            session.handle(request(r#""history""#), &accounts),
+            Some(Reply::History {
+                games: accounts.lock().unwrap().get("a").unwrap().history.clone(),
+            })
+        );
+        assert_eq!(
+            session.handle(request(r#""leaderboard""#), &accounts),
+            Some(Reply::Leaderboard {
+                entries: vec![
+                    LeaderboardEntry {
+                        name: "a".to_owned(),
+                        rating: rating("a"),
+                    },
+                    LeaderboardEntry {
+                        name: "b".to_owned(),
+                        rating: rating("b"),
+                    },
+                ],
+            })
+        );
+        assert_eq!(session.handle(Request::Quit, &accounts), None);
+    }
+
+    #[test]
+    fn handle__register() {
+        let accounts = accounts();
+        let mut session = Session::default();
+        assert_eq!(
+            session.handle(
+                request(r#"{"register":{"name":"a","password":"p"}}"#),
+                &accounts
+            ),
+            Some(error("The account `a` already exists."))
+        );
+        assert_eq!(
+            session.handle(
+                request(r#"{"register":{"name":"c","password":"pc"}}"#),
+                &accounts
+            ),
+            Some(Reply::LoggedIn {
+                name: "c".to_owned(),
+                rating: Rating::INITIAL,
+            })
+        );
+        assert!(accounts
+            .lock()
+            .unwrap()
+            .password("c")
+            .unwrap()
+            .verify("pc")
+            .is_ok());
+        assert_eq!(
+            session.handle(request(r#""history""#), &accounts),
+            Some(Reply::History { games: Vec::new() })
+        );
+    }
+}
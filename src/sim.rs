@@ -0,0 +1,555 @@
+//! Headless games between [`Ai`](crate::game::Ai)s, see [`run()`].
+
+use alloc::{rc::Rc, sync::Arc};
+use core::{cmp::Ordering, error::Error, str::FromStr, time::Duration};
+use std::{io, io::Write, thread};
+
+use oorandom::Rand64;
+use serde::Serialize;
+
+use crate::{
+    ai,
+    ai::{
+        book::BookOpts,
+        external::{EngineCommand, ExternalAi},
+        mcts::MctsOpts,
+        AiKind, Reason,
+    },
+    cli::SimulateArgs,
+    game::{Ai, DefaultActionQueue, Logic, Phase::Outround, PlayerId, World},
+    process::ExitSignal,
+    save::SavedGame,
+    util::{csv, time::ManualClockSource},
+};
+
+mod test;
+
+/// The two-sided 95% quantile of the standard normal distribution.
+const Z_95: f64 = 1.96;
+/// The simulated time between consecutive [`World::advance()`](crate::game::World::advance)s,
+/// which is the same as between the frames of the user interface.
+/// The results do not depend on it, as the [`Ai`](crate::game::Ai)s act without delays.
+const TIME_STEP: Duration = Duration::from_nanos(1_000_000_000 / 30);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReportFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+impl ReportFormat {
+    pub const NAMES: [&'static str; 3] = ["text", "json", "csv"];
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!(
+                "unknown format {s:?}, expected one of {:?}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+/// Plays the games specified by `args` and prints the [`Report`] to the standard output.
+/// If the `exit_signal` is received, the games that have not been started are skipped,
+/// and the report covers only the completed ones.
+pub fn run(args: &SimulateArgs, exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
+    let books = args.books().clone().map(|path| {
+        path.map(|path| BookOpts::read(&path, args.book_randomness()))
+            .transpose()
+    });
+    let simulation = Simulation {
+        ais: args.ais(),
+        engines: args.engines().clone(),
+        engine_time: args.engine_time(),
+        books: [books[0].clone()?, books[1].clone()?],
+        mcts: args.mcts(),
+        games: args.games(),
+        rounds: args.rounds(),
+        seed: args.seed().unwrap_or_else(ai::time_based_seed),
+    };
+    let threads = args
+        .threads()
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, usize::from);
+    let tally = simulation.play(threads, || exit_signal.is_received());
+    let report = Report::new(&simulation, &tally);
+    let mut out = io::stdout().lock();
+    match args.format() {
+        ReportFormat::Text => report.write_text(&mut out)?,
+        ReportFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &report)?;
+            writeln!(out)?;
+        }
+        ReportFormat::Csv => report.write_csv(&mut out)?,
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Simulation {
+    /// The [`AiKind`]s indexed by [`PlayerId`].
+    ais: [AiKind; 2],
+    /// The external engines indexed by [`PlayerId`], which play instead of the [`Self::ais`].
+    engines: [Option<EngineCommand>; 2],
+    engine_time: Duration,
+    /// The opening books indexed by [`PlayerId`], which the [`Self::ais`]
+    /// or the [`Self::engines`] consult first.
+    books: [Option<BookOpts>; 2],
+    mcts: MctsOpts,
+    games: u64,
+    rounds: u32,
+    /// The seed from which [`Self::game_seeds()`] are derived.
+    seed: u64,
+}
+
+impl Simulation {
+    /// The results do not depend on the number of `threads`.
+    fn play<F>(&self, threads: usize, interrupted: F) -> Tally
+    where
+        F: Fn() -> bool + Sync,
+    {
+        let game_seeds = self.game_seeds();
+        let interrupted = &interrupted;
+        thread::scope(|scope| {
+            (0..threads)
+                .map(|thread_idx| {
+                    let game_seeds = &game_seeds;
+                    scope.spawn(move || {
+                        let mut tally = Tally::default();
+                        for &seed in game_seeds.iter().skip(thread_idx).step_by(threads) {
+                            if interrupted() {
+                                break;
+                            }
+                            let (outcomes, engine_failures) = self.play_game(seed);
+                            tally.add_game(&outcomes, engine_failures);
+                        }
+                        tally
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().expect("a simulation thread should not panic"))
+                .fold(Tally::default(), Tally::merged)
+        })
+    }
+
+    fn ai_name(&self, player_id: PlayerId) -> String {
+        let name = self.engines[player_id.idx].as_ref().map_or_else(
+            || self.ais[player_id.idx].to_string(),
+            |command| format!("engine `{command}`"),
+        );
+        if self.books[player_id.idx].is_some() {
+            format!("{name} with a book")
+        } else {
+            name
+        }
+    }
+
+    /// Returns the outcomes of the rounds, and the numbers of the moves played randomly
+    /// because an external engine has failed, indexed by [`PlayerId`].
+    fn play_game(&self, seed: u64) -> (Vec<RoundOutcome>, [u64; 2]) {
+        let game = SavedGame::new(seed, self.rounds, self.ais);
+        let (outcomes, world) = play_world(game.world_with(|player_id, seed, action_queue| {
+            let ai: Box<dyn Ai> = match &self.engines[player_id.idx] {
+                Some(command) => Box::new(ExternalAi::new(
+                    seed,
+                    Rc::clone(&action_queue),
+                    command.clone(),
+                    self.engine_time,
+                )),
+                None => {
+                    self.ais[player_id.idx].create_with(seed, Rc::clone(&action_queue), self.mcts)
+                }
+            };
+            match &self.books[player_id.idx] {
+                Some(book) => book.consulted_by(ai, seed, action_queue),
+                None => ai,
+            }
+        }));
+        let mut engine_failures = [0; 2];
+        for round_log in &world.state().log {
+            for move_ in &round_log.moves {
+                if move_.reason == Some(Reason::EngineFailed) {
+                    engine_failures[move_.player_id.idx] += 1;
+                }
+            }
+        }
+        (outcomes, engine_failures)
+    }
+
+    /// Returns the seed of each game, which is the same seed
+    /// that may be specified for a game in the interactive mode.
+    fn game_seeds(&self) -> Vec<u64> {
+        let mut rng = Rand64::new(u128::from(self.seed));
+        (0..self.games).map(|_| rng.rand_u64()).collect()
+    }
+}
+
+/// Plays the game headlessly by its AIs, with both AIs consulting the `book` first, if any,
+/// instead of the books of the players. Returns the outcomes of its rounds.
+///
+/// # Panics
+///
+/// If an AI cannot be created, see [`SavedPlayer::create_ai()`](crate::save::SavedPlayer::create_ai).
+pub fn play_game(game: &SavedGame, book: Option<&BookOpts>) -> Vec<RoundOutcome> {
+    play_world(game.world_with(|player_id, seed, action_queue| {
+        game.players[player_id.idx]
+            .create_ai(seed, action_queue, book)
+            .expect("the AIs should be possible to create")
+    }))
+    .0
+}
+
+/// Plays the game in the `world`, which must not have been advanced, headlessly,
+/// and returns the outcomes of its rounds, and the `world` with the final state.
+pub fn play_world(
+    world: World<DefaultActionQueue>,
+) -> (Vec<RoundOutcome>, World<DefaultActionQueue>) {
+    let clock_source = ManualClockSource::new();
+    let mut world = world.with_clock_source(Box::new(clock_source.clone()));
+    for ai in world.ais() {
+        ai.set_base_act_delay(Duration::ZERO);
+        ai.set_blocking(true);
+    }
+    let enough_iterations = {
+        let state = world.state();
+        u32::try_from(state.board.size().pow(2) + 1).unwrap() * state.rounds
+    };
+    let mut outcomes = Vec::with_capacity(usize::try_from(world.state().rounds).unwrap());
+    let mut wins = [0; 2];
+    for _ in 0..enough_iterations {
+        if Logic::<DefaultActionQueue>::is_game_over(world.state()) {
+            break;
+        }
+        clock_source.advance(TIME_STEP);
+        world.advance();
+        let state = world.state();
+        if state.phase == Outround && outcomes.len() == usize::try_from(state.round).unwrap() {
+            let winner = state
+                .players
+                .iter()
+                .find(|player| player.wins != wins[player.id.idx])
+                .map(|player| player.id);
+            if let Some(winner) = winner {
+                wins[winner.idx] += 1;
+            }
+            outcomes.push(RoundOutcome {
+                first_mover: PlayerId::from(
+                    usize::try_from(state.round).unwrap() % state.players.len(),
+                ),
+                winner,
+            });
+        }
+    }
+    assert!(
+        Logic::<DefaultActionQueue>::is_game_over(world.state()),
+        "{world:?}"
+    );
+    (outcomes, world)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RoundOutcome {
+    pub first_mover: PlayerId,
+    /// [`None`] iff the round is a draw.
+    pub winner: Option<PlayerId>,
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+struct Tally {
+    games: u64,
+    /// Indexed by [`PlayerId`].
+    game_wins: [u64; 2],
+    game_draws: u64,
+    rounds: u64,
+    /// Indexed by [`PlayerId`].
+    round_wins: [u64; 2],
+    round_draws: u64,
+    first_mover_wins: u64,
+    second_mover_wins: u64,
+    /// Indexed by [`PlayerId`], see [`Simulation::play_game()`].
+    engine_failures: [u64; 2],
+}
+
+impl Tally {
+    fn add_game(&mut self, outcomes: &[RoundOutcome], engine_failures: [u64; 2]) {
+        let mut game_wins = [0u64; 2];
+        for outcome in outcomes {
+            self.rounds += 1;
+            match outcome.winner {
+                Some(winner) => {
+                    game_wins[winner.idx] += 1;
+                    if winner == outcome.first_mover {
+                        self.first_mover_wins += 1;
+                    } else {
+                        self.second_mover_wins += 1;
+                    }
+                }
+                None => self.round_draws += 1,
+            }
+        }
+        self.games += 1;
+        self.engine_failures[0] += engine_failures[0];
+        self.engine_failures[1] += engine_failures[1];
+        self.round_wins[0] += game_wins[0];
+        self.round_wins[1] += game_wins[1];
+        match game_wins[0].cmp(&game_wins[1]) {
+            Ordering::Greater => self.game_wins[0] += 1,
+            Ordering::Less => self.game_wins[1] += 1,
+            Ordering::Equal => self.game_draws += 1,
+        }
+    }
+
+    fn merged(self, other: Self) -> Self {
+        Self {
+            games: self.games + other.games,
+            game_wins: [
+                self.game_wins[0] + other.game_wins[0],
+                self.game_wins[1] + other.game_wins[1],
+            ],
+            game_draws: self.game_draws + other.game_draws,
+            rounds: self.rounds + other.rounds,
+            round_wins: [
+                self.round_wins[0] + other.round_wins[0],
+                self.round_wins[1] + other.round_wins[1],
+            ],
+            round_draws: self.round_draws + other.round_draws,
+            first_mover_wins: self.first_mover_wins + other.first_mover_wins,
+            second_mover_wins: self.second_mover_wins + other.second_mover_wins,
+            engine_failures: [
+                self.engine_failures[0] + other.engine_failures[0],
+                self.engine_failures[1] + other.engine_failures[1],
+            ],
+        }
+    }
+}
+
+/// A point estimate with its 95% confidence interval.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+struct Estimate {
+    value: f64,
+    low: f64,
+    high: f64,
+}
+
+impl Estimate {
+    /// Estimates the probability of an outcome that happened `k` times in `n` trials
+    /// using the [Wilson score interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Wilson_score_interval).
+    #[allow(clippy::cast_precision_loss)]
+    fn proportion(k: u64, n: u64) -> Self {
+        if n == 0 {
+            return Self {
+                value: 0.0,
+                low: 0.0,
+                high: 1.0,
+            };
+        }
+        let n = n as f64;
+        let p = k as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let denominator = 1.0 + z2 / n;
+        let center = (p + z2 / (2.0 * n)) / denominator;
+        let half_width = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+        Self {
+            value: p,
+            low: (center - half_width).max(0.0),
+            high: (center + half_width).min(1.0),
+        }
+    }
+
+    /// Estimates `P(a) - P(b)`, where `a`, `b` are mutually exclusive outcomes
+    /// that happened `k_a`, `k_b` times respectively in `n` trials,
+    /// using the normal approximation.
+    #[allow(clippy::cast_precision_loss)]
+    fn difference(k_a: u64, k_b: u64, n: u64) -> Self {
+        if n == 0 {
+            return Self {
+                value: 0.0,
+                low: -1.0,
+                high: 1.0,
+            };
+        }
+        let n = n as f64;
+        let p_a = k_a as f64 / n;
+        let p_b = k_b as f64 / n;
+        let d = p_a - p_b;
+        let half_width = Z_95 * ((p_a + p_b - d * d) / n).sqrt();
+        Self {
+            value: d,
+            low: (d - half_width).max(-1.0),
+            high: (d + half_width).min(1.0),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+struct Count {
+    count: u64,
+    rate: Estimate,
+}
+
+impl Count {
+    fn new(k: u64, n: u64) -> Self {
+        Self {
+            count: k,
+            rate: Estimate::proportion(k, n),
+        }
+    }
+}
+
+/// The rates of rounds are the rates among all rounds,
+/// the rates of games are the rates among all games.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Report {
+    seed: u64,
+    /// The [`AiKind`] or the external engine playing X.
+    ai_x: String,
+    ai_o: String,
+    games: u64,
+    rounds_per_game: u32,
+    rounds: u64,
+    rounds_won_by_x: Count,
+    rounds_drawn: Count,
+    rounds_won_by_o: Count,
+    /// The probability of the first mover winning a round
+    /// minus the probability of the second mover winning a round.
+    first_mover_advantage: Estimate,
+    games_won_by_x: Count,
+    games_tied: Count,
+    games_won_by_o: Count,
+    /// The number of the moves played randomly because the external engine playing X has failed.
+    engine_failures_x: u64,
+    engine_failures_o: u64,
+}
+
+impl Report {
+    fn new(simulation: &Simulation, tally: &Tally) -> Self {
+        Self {
+            seed: simulation.seed,
+            ai_x: simulation.ai_name(PlayerId::new(0)),
+            ai_o: simulation.ai_name(PlayerId::new(1)),
+            games: tally.games,
+            rounds_per_game: simulation.rounds,
+            rounds: tally.rounds,
+            rounds_won_by_x: Count::new(tally.round_wins[0], tally.rounds),
+            rounds_drawn: Count::new(tally.round_draws, tally.rounds),
+            rounds_won_by_o: Count::new(tally.round_wins[1], tally.rounds),
+            first_mover_advantage: Estimate::difference(
+                tally.first_mover_wins,
+                tally.second_mover_wins,
+                tally.rounds,
+            ),
+            games_won_by_x: Count::new(tally.game_wins[0], tally.games),
+            games_tied: Count::new(tally.game_draws, tally.games),
+            games_won_by_o: Count::new(tally.game_wins[1], tally.games),
+            engine_failures_x: tally.engine_failures[0],
+            engine_failures_o: tally.engine_failures[1],
+        }
+    }
+
+    fn write_text(&self, out: &mut impl Write) -> io::Result<()> {
+        fn write_count(out: &mut impl Write, name: &str, count: &Count) -> io::Result<()> {
+            writeln!(
+                out,
+                "  {name:<7}{count:>10}  {value:>6.2}%  [{low:.2}%, {high:.2}%]",
+                count = count.count,
+                value = count.rate.value * 100.0,
+                low = count.rate.low * 100.0,
+                high = count.rate.high * 100.0,
+            )
+        }
+
+        writeln!(
+            out,
+            "X: {}, O: {}, seed: {}.",
+            self.ai_x, self.ai_o, self.seed
+        )?;
+        writeln!(out, "Rounds (95% confidence intervals):")?;
+        writeln!(out, "  {:<7}{:>10}", "total", self.rounds)?;
+        write_count(out, "X won", &self.rounds_won_by_x)?;
+        write_count(out, "drawn", &self.rounds_drawn)?;
+        write_count(out, "O won", &self.rounds_won_by_o)?;
+        writeln!(
+            out,
+            "  first-mover advantage: {value:+.4}  [{low:+.4}, {high:+.4}]",
+            value = self.first_mover_advantage.value,
+            low = self.first_mover_advantage.low,
+            high = self.first_mover_advantage.high,
+        )?;
+        writeln!(out, "Games of {} rounds:", self.rounds_per_game)?;
+        writeln!(out, "  {:<7}{:>10}", "total", self.games)?;
+        write_count(out, "X won", &self.games_won_by_x)?;
+        write_count(out, "tied", &self.games_tied)?;
+        write_count(out, "O won", &self.games_won_by_o)?;
+        if self.engine_failures_x + self.engine_failures_o > 0 {
+            writeln!(
+                out,
+                "Random moves after engine failures: X {}, O {}.",
+                self.engine_failures_x, self.engine_failures_o
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        let counts = [
+            ("rounds_won_by_x", &self.rounds_won_by_x),
+            ("rounds_drawn", &self.rounds_drawn),
+            ("rounds_won_by_o", &self.rounds_won_by_o),
+            ("games_won_by_x", &self.games_won_by_x),
+            ("games_tied", &self.games_tied),
+            ("games_won_by_o", &self.games_won_by_o),
+        ];
+        let mut header = vec![
+            "seed".to_owned(),
+            "ai_x".to_owned(),
+            "ai_o".to_owned(),
+            "games".to_owned(),
+            "rounds_per_game".to_owned(),
+            "rounds".to_owned(),
+            "engine_failures_x".to_owned(),
+            "engine_failures_o".to_owned(),
+        ];
+        let mut record = vec![
+            self.seed.to_string(),
+            csv::field(&self.ai_x),
+            csv::field(&self.ai_o),
+            self.games.to_string(),
+            self.rounds_per_game.to_string(),
+            self.rounds.to_string(),
+            self.engine_failures_x.to_string(),
+            self.engine_failures_o.to_string(),
+        ];
+        let estimates = counts
+            .iter()
+            .map(|(name, count)| {
+                header.push((*name).to_owned());
+                record.push(count.count.to_string());
+                (format!("{name}_rate"), count.rate)
+            })
+            .chain([(
+                "first_mover_advantage".to_owned(),
+                self.first_mover_advantage,
+            )])
+            .collect::<Vec<_>>();
+        for (name, estimate) in estimates {
+            header.extend([name.clone(), format!("{name}_low"), format!("{name}_high")]);
+            record.extend([
+                estimate.value.to_string(),
+                estimate.low.to_string(),
+                estimate.high.to_string(),
+            ]);
+        }
+        writeln!(out, "{}", header.join(","))?;
+        writeln!(out, "{}", record.join(","))
+    }
+}
@@ -0,0 +1,141 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Simulation {
+    use ntest::timeout;
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::{
+        ai::{external::ExternalAi, mcts::MctsOpts, AiKind},
+        sim::Simulation,
+    };
+
+    fn simulation() -> Simulation {
+        Simulation {
+            ais: [AiKind::Random, AiKind::Random],
+            engines: [None, None],
+            engine_time: ExternalAi::DEFAULT_TIME_LIMIT,
+            books: [None, None],
+            mcts: MctsOpts::DEFAULT,
+            games: 200,
+            rounds: 3,
+            seed: 42,
+        }
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn play__independent_of_threads() {
+        let simulation = simulation();
+        let tally = simulation.play(1, || false);
+        assert_eq!(tally.games, simulation.games);
+        assert_eq!(
+            tally.rounds,
+            simulation.games * u64::from(simulation.rounds)
+        );
+        assert_eq!(
+            tally.round_wins[0] + tally.round_wins[1] + tally.round_draws,
+            tally.rounds
+        );
+        assert_eq!(
+            tally.first_mover_wins + tally.second_mover_wins,
+            tally.round_wins[0] + tally.round_wins[1]
+        );
+        assert_eq!(
+            tally.game_wins[0] + tally.game_wins[1] + tally.game_draws,
+            tally.games
+        );
+        assert_eq!(simulation.play(3, || false), tally);
+    }
+
+    #[test]
+    fn play__interrupted() {
+        assert_eq!(simulation().play(2, || true).games, 0);
+    }
+}
+
+mod Tally {
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::{
+        game::PlayerId,
+        sim::{RoundOutcome, Tally},
+    };
+
+    #[test]
+    fn add_game() {
+        let mut tally = Tally::default();
+        tally.add_game(
+            &[
+                RoundOutcome {
+                    first_mover: PlayerId::new(0),
+                    winner: Some(PlayerId::new(0)),
+                },
+                RoundOutcome {
+                    first_mover: PlayerId::new(1),
+                    winner: None,
+                },
+                RoundOutcome {
+                    first_mover: PlayerId::new(0),
+                    winner: Some(PlayerId::new(1)),
+                },
+            ],
+            [0, 2],
+        );
+        tally.add_game(
+            &[RoundOutcome {
+                first_mover: PlayerId::new(0),
+                winner: Some(PlayerId::new(1)),
+            }],
+            [0, 1],
+        );
+        assert_eq!(
+            tally,
+            Tally {
+                games: 2,
+                game_wins: [0, 1],
+                game_draws: 1,
+                rounds: 4,
+                round_wins: [1, 2],
+                round_draws: 1,
+                first_mover_wins: 1,
+                second_mover_wins: 2,
+                engine_failures: [0, 3],
+            }
+        );
+    }
+}
+
+mod Estimate {
+    use test_case::test_case;
+
+    use crate::sim::Estimate;
+
+    #[test_case(0, 0, (0.0, 0.0, 1.0))]
+    #[test_case(0, 10, (0.0, 0.0, 0.2775))]
+    #[test_case(5, 10, (0.5, 0.2366, 0.7634))]
+    #[test_case(10, 10, (1.0, 0.7225, 1.0))]
+    fn proportion(k: u64, n: u64, (value, low, high): (f64, f64, f64)) {
+        assert_approx_eq(Estimate::proportion(k, n), Estimate { value, low, high });
+    }
+
+    #[test_case(0, 0, 0, (0.0, -1.0, 1.0))]
+    #[test_case(50, 50, 100, (0.0, -0.1960, 0.1960))]
+    #[test_case(60, 30, 100, (0.3, 0.1236, 0.4764))]
+    fn difference(k_a: u64, k_b: u64, n: u64, (value, low, high): (f64, f64, f64)) {
+        assert_approx_eq(
+            Estimate::difference(k_a, k_b, n),
+            Estimate { value, low, high },
+        );
+    }
+
+    fn assert_approx_eq(actual: Estimate, expected: Estimate) {
+        let eq = |a: f64, b: f64| (a - b).abs() < 1e-4;
+        assert!(
+            eq(actual.value, expected.value)
+                && eq(actual.low, expected.low)
+                && eq(actual.high, expected.high),
+            "{actual:?}, {expected:?}"
+        );
+    }
+}
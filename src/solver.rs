@@ -0,0 +1,381 @@
+//! Exhaustive search over the game rules, see [`Solver`].
+
+use core::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+use std::{collections::HashMap, io, io::Write};
+
+use xxhash_rust::xxh3::Xxh3Builder;
+
+use crate::{
+    cli::AnalyzeArgs,
+    game::{Board, Cell, DefaultActionQueue, Line, Logic, Mark, PlayerId},
+    solver::tablebase::Tablebase,
+};
+
+pub mod tablebase;
+mod test;
+
+/// Prints the [`Analysis`] of the position specified by `args` to the standard output.
+pub fn run(args: &AnalyzeArgs) -> Result<(), Box<dyn Error>> {
+    let position = args.position();
+    let analysis = match args.tablebase() {
+        Some(path) => {
+            let tablebase = Tablebase::read_playable(path)
+                .map_err(|e| format!("Failed to read {}: {e}.", path.display()))?;
+            tablebase
+                .analyze(position)
+                .ok_or("The position is not in the tablebase.")?
+        }
+        None => Solver::new().analyze(position),
+    };
+    let mut out = io::stdout().lock();
+    writeln!(out, "Position: {position}.")?;
+    let board = position.board();
+    write!(out, "  ")?;
+    for cell in board.cells().take(board.size()) {
+        write!(out, " {}", cell.to_string().remove(0))?;
+    }
+    writeln!(out)?;
+    for x in 0..board.size() {
+        write!(out, "{:>2}", x + 1)?;
+        for y in 0..board.size() {
+            match board.get(&Cell::new(x, y)) {
+                Some(player_id) => write!(out, " {}", player_id.mark())?,
+                None => write!(out, " .")?,
+            }
+        }
+        writeln!(out)?;
+    }
+    let mover = position.turn().mark();
+    if let Some((winner, _)) = position.winner() {
+        writeln!(out, "Result: {} has won.", winner.mark())?;
+        return Ok(());
+    } else if position.is_over() {
+        writeln!(out, "Result: draw.")?;
+        return Ok(());
+    }
+    let opponent = position.opponent().mark();
+    match analysis.value {
+        Value::Win(plies) => writeln!(out, "Result: {mover} wins in {}.", Plies(plies))?,
+        Value::Draw => writeln!(out, "Result: draw.")?,
+        Value::Loss(plies) => writeln!(out, "Result: {opponent} wins in {}.", Plies(plies))?,
+    }
+    writeln!(out, "Moves of {mover}, best first:")?;
+    for (cell, value) in &analysis.moves {
+        writeln!(out, "  {cell}  {value}")?;
+    }
+    write!(out, "Principal variation:")?;
+    for cell in &analysis.principal_variation {
+        write!(out, " {cell}")?;
+    }
+    writeln!(out, ".")?;
+    Ok(())
+}
+
+/// A [`Board`] and the [`PlayerId`] of the player whose turn it is.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Position {
+    board: Board,
+    turn: PlayerId,
+}
+
+impl Position {
+    pub fn new(board: Board, turn: PlayerId) -> Self {
+        Self { board, turn }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn turn(&self) -> PlayerId {
+        self.turn
+    }
+
+    pub fn opponent(&self) -> PlayerId {
+        PlayerId::new(1 - self.turn.idx)
+    }
+
+    /// Returns the player who has occupied a [`Line`], if any.
+    pub fn winner(&self) -> Option<(PlayerId, Line)> {
+        self.board.cells().find_map(|cell| {
+            self.board.get(&cell).and_then(|player_id| {
+                Logic::<DefaultActionQueue>::check_win(&self.board, &cell)
+                    .map(|line| (player_id, line))
+            })
+        })
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some()
+            || self
+                .board
+                .cells()
+                .all(|cell| self.board.get(&cell).is_some())
+    }
+
+    /// Returns the empty cells, or nothing if [`Self::is_over()`].
+    pub fn moves(&self) -> Vec<Cell> {
+        if self.is_over() {
+            Vec::new()
+        } else {
+            self.board
+                .cells()
+                .filter(|cell| self.board.get(cell).is_none())
+                .collect()
+        }
+    }
+
+    /// Returns the empty cells the opponent would win by occupying,
+    /// that is the cells the player whose turn it is must occupy to not lose on the next move.
+    pub fn threats(&self) -> Vec<Cell> {
+        self.moves()
+            .into_iter()
+            .filter(|cell| {
+                let mut board = self.board.clone();
+                board.set(cell, self.opponent());
+                Logic::<DefaultActionQueue>::check_win(&board, cell).is_some()
+            })
+            .collect()
+    }
+
+    /// Returns the position after the player whose turn it is occupies the `cell`.
+    pub fn play(&self, cell: &Cell) -> Self {
+        let mut board = self.board.clone();
+        board.set(cell, self.turn);
+        Self::new(board, self.opponent())
+    }
+
+    /// A unique number identifying the position.
+    fn key(&self) -> u32 {
+        self.board
+            .cells()
+            .fold(u32::try_from(self.turn.idx).unwrap(), |key, cell| {
+                key * 3
+                    + self
+                        .board
+                        .get(&cell)
+                        .map_or(0, |player_id| u32::try_from(player_id.idx).unwrap() + 1)
+            })
+    }
+}
+
+impl Display for Position {
+    /// The [`Board`] notation followed by the [`Mark`] of the player whose turn it is,
+    /// e.g., `X.O/.X./... O`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.board, self.turn.mark())
+    }
+}
+
+impl FromStr for Position {
+    type Err = String;
+
+    /// Parses the notation described in [`Position::fmt()`].
+    /// The [`Mark`] may be omitted if it can be inferred from the number of marks on the board,
+    /// with [`Mark::X`] moving first.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let board = parts
+            .next()
+            .ok_or_else(|| "the position must not be empty".to_owned())?
+            .parse::<Board>()?;
+        let explicit_turn = parts
+            .next()
+            .map(|mark| mark.parse::<Mark>().map(Mark::player_id))
+            .transpose()?;
+        if let Some(redundant) = parts.next() {
+            return Err(format!("unexpected {redundant:?}"));
+        }
+        let x_count = board.count(Mark::X.player_id());
+        let o_count = board.count(Mark::O.player_id());
+        let turn = match (x_count.cmp(&o_count), explicit_turn) {
+            (Ordering::Equal, turn) => turn.unwrap_or_else(|| Mark::X.player_id()),
+            (Ordering::Greater, None) if x_count == o_count + 1 => Mark::O.player_id(),
+            (Ordering::Less, None) if o_count == x_count + 1 => Mark::X.player_id(),
+            (Ordering::Greater, Some(turn)) if x_count == o_count + 1 && turn.mark() == Mark::O => {
+                turn
+            }
+            (Ordering::Less, Some(turn)) if o_count == x_count + 1 && turn.mark() == Mark::X => {
+                turn
+            }
+            _ => {
+                return Err(format!(
+                    "the position {s:?} is unreachable: {x_count} X, {o_count} O"
+                ))
+            }
+        };
+        let position = Self::new(board, turn);
+        let winners = [Mark::X, Mark::O].map(|mark| {
+            position.board.cells().any(|cell| {
+                position.board.get(&cell) == Some(mark.player_id())
+                    && Logic::<DefaultActionQueue>::check_win(&position.board, &cell).is_some()
+            })
+        });
+        if winners[turn.idx] {
+            Err(format!(
+                "the position {s:?} is unreachable: {} has won and must not have the turn",
+                turn.mark()
+            ))
+        } else {
+            Ok(position)
+        }
+    }
+}
+
+/// The game-theoretic value of a [`Position`] from the point of view of the player
+/// whose turn it is, with the number of plies (turns) until the end of the round
+/// given that both players play perfectly, i.e., win as soon as possible,
+/// or lose as late as possible.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Value {
+    Win(u32),
+    Draw,
+    Loss(u32),
+}
+
+impl Value {
+    const MAX_SCORE: i32 = 100;
+
+    /// Returns the value of a position for the player whose turn it is,
+    /// given that the player makes a move resulting in a position with the value `self`.
+    pub fn backed_up(self) -> Self {
+        match self {
+            Self::Win(plies) => Self::Loss(plies + 1),
+            Self::Draw => Self::Draw,
+            Self::Loss(plies) => Self::Win(plies + 1),
+        }
+    }
+
+    /// The greater, the better.
+    pub fn score(self) -> i32 {
+        match self {
+            Self::Win(plies) => Self::MAX_SCORE - i32::try_from(plies).unwrap(),
+            Self::Draw => 0,
+            Self::Loss(plies) => i32::try_from(plies).unwrap() - Self::MAX_SCORE,
+        }
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score().cmp(&other.score())
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Win(plies) => write!(f, "win in {}", Plies(*plies)),
+            Self::Draw => f.write_str("draw"),
+            Self::Loss(plies) => write!(f, "loss in {}", Plies(*plies)),
+        }
+    }
+}
+
+struct Plies(u32);
+
+impl Display for Plies {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.0 == 1 {
+            f.write_str("1 ply")
+        } else {
+            write!(f, "{} plies", self.0)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Analysis {
+    pub value: Value,
+    /// All moves with the values of making them, the best first.
+    pub moves: Vec<(Cell, Value)>,
+    /// The sequence of the best moves, the first one is made by the player whose turn it is.
+    /// If there are multiple best moves, the first one in [`Self::moves`] is picked.
+    pub principal_variation: Vec<Cell>,
+}
+
+/// Memoizes the [`Value`]s of the [`Position`]s it has seen.
+#[derive(Debug)]
+pub struct Solver {
+    values: HashMap<u32, Value, Xxh3Builder>,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::with_hasher(Xxh3Builder::new()),
+        }
+    }
+
+    pub fn value(&mut self, position: &Position) -> Value {
+        let key = position.key();
+        if let Some(value) = self.values.get(&key) {
+            return *value;
+        }
+        let value = if position.winner().is_some() {
+            // the opponent has won with the last move
+            Value::Loss(0)
+        } else {
+            position
+                .moves()
+                .iter()
+                .map(|cell| self.value(&position.play(cell)).backed_up())
+                .max()
+                .unwrap_or(Value::Draw)
+        };
+        self.values.insert(key, value);
+        value
+    }
+
+    pub fn analyze(&mut self, position: &Position) -> Analysis {
+        let mut moves = position
+            .moves()
+            .into_iter()
+            .map(|cell| (cell, self.value(&position.play(&cell)).backed_up()))
+            .collect::<Vec<_>>();
+        // the sort is stable, so the moves with equal values remain in the order of the cells
+        moves.sort_by(|(_, v1), (_, v2)| v2.cmp(v1));
+        let mut principal_variation = Vec::new();
+        let mut current = position.clone();
+        while let Some(best) = self.best_moves(&current).first() {
+            principal_variation.push(*best);
+            current = current.play(best);
+        }
+        Analysis {
+            value: self.value(position),
+            moves,
+            principal_variation,
+        }
+    }
+
+    /// Returns all moves with the best [`Value`], in the order of the cells.
+    pub fn best_moves(&mut self, position: &Position) -> Vec<Cell> {
+        let values = position
+            .moves()
+            .into_iter()
+            .map(|cell| (cell, self.value(&position.play(&cell)).backed_up()))
+            .collect::<Vec<_>>();
+        values
+            .iter()
+            .map(|(_, value)| *value)
+            .max()
+            .map_or_else(Vec::new, |best| {
+                values
+                    .into_iter()
+                    .filter(|(_, value)| *value == best)
+                    .map(|(cell, _)| cell)
+                    .collect()
+            })
+    }
+}
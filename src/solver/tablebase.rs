@@ -0,0 +1,475 @@
+//! See [`Tablebase`].
+
+use core::error::Error;
+use std::{
+    collections::HashMap,
+    env,
+    ffi::OsString,
+    fs,
+    io::{self, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use once_cell::sync::Lazy;
+use xxhash_rust::xxh3::Xxh3Builder;
+
+use crate::{
+    cli::TablebaseArgs,
+    game::{Board, Cell},
+    solver::{Analysis, Position, Value},
+    util::dirs,
+};
+
+mod test;
+
+/// The [`Tablebase`] of the [`Board`], which is generated on first use, as that is instant.
+pub static STANDARD: Lazy<Tablebase> = Lazy::new(|| Tablebase::generate(Board::SIZE));
+
+/// Generates the [`Tablebase`] specified by `args`, writes it,
+/// and prints where it has been written to the standard output.
+pub fn run(args: &TablebaseArgs) -> Result<(), Box<dyn Error>> {
+    let path = match args.output() {
+        Some(path) => path.clone(),
+        None => Tablebase::dir(&|name| env::var_os(name))
+            .map(|dir| Tablebase::path(&dir, args.size()))
+            .ok_or(
+                "Cannot locate the data directory, neither $XDG_DATA_HOME nor $HOME is specified.",
+            )?,
+    };
+    let start = Instant::now();
+    let tablebase = Tablebase::generate(args.size());
+    tablebase
+        .write(&path)
+        .map_err(|e| format!("Failed to write {}: {e}.", path.display()))?;
+    let size = tablebase.size();
+    println!(
+        "Generated {} positions of the {size}x{size} board in {:.1?}, written to {}.",
+        tablebase.len(),
+        start.elapsed(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// The [`Value`]s of all positions reachable from the empty board of a size that are not over,
+/// regardless of which player moves first.
+/// Positions that are the same up to a symmetry of the board, or up to swapping the marks
+/// together with the turn, have the same [`Value`] and share an entry.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Tablebase {
+    size: usize,
+    /// The keys of the entries in ascending order, see [`Symmetries::key()`].
+    keys: Vec<u32>,
+    /// The [`Value`]s of the entries in the order of [`Self::keys`], see [`encode()`].
+    values: Vec<u8>,
+    symmetries: Symmetries,
+}
+
+impl Tablebase {
+    /// The maximum board size, for which the number of positions is tractable.
+    pub const MAX_SIZE: usize = 4;
+    const DIR: &'static str = "tablebases";
+    const MAGIC: &'static [u8; 4] = b"TTTB";
+    const VERSION: u8 = 1;
+
+    /// Enumerates all positions by searching the game tree exhaustively.
+    /// Takes a few seconds for the board of [`Self::MAX_SIZE`].
+    ///
+    /// # Panics
+    ///
+    /// If the `size` is 0 or greater than [`Self::MAX_SIZE`].
+    pub fn generate(size: usize) -> Self {
+        assert!(
+            (1..=Self::MAX_SIZE).contains(&size),
+            "{size:?}, {:?}",
+            Self::MAX_SIZE
+        );
+        let mut generator = Generator::new(size);
+        generator.solve(&[Generator::EMPTY; MAX_CELLS], &[[0; 2]; 8], None);
+        let mut entries = generator.values.into_iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|&(key, _)| key);
+        Self {
+            size,
+            keys: entries.iter().map(|&(key, _)| key).collect(),
+            values: entries.iter().map(|&(_, value)| encode(value)).collect(),
+            symmetries: generator.symmetries,
+        }
+    }
+
+    /// Returns the directory with the tablebase files inside [`dirs::data_dir()`].
+    pub fn dir(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::data_dir(env).map(|dir| dir.join(Self::DIR))
+    }
+
+    /// Returns the path of the tablebase file of the `size` in `dir`, e.g., `3x3.ttb`.
+    pub fn path(dir: &Path, size: usize) -> PathBuf {
+        dir.join(format!("{size}x{size}.ttb"))
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns the [`Value`] of the `position`, or [`None`] if the `position` has a board
+    /// of another size, or is unreachable.
+    pub fn probe(&self, position: &Position) -> Option<Value> {
+        let board = position.board();
+        if board.size() != self.size {
+            return None;
+        }
+        if position.winner().is_some() {
+            return Some(Value::Loss(0));
+        } else if position.is_over() {
+            return Some(Value::Draw);
+        }
+        let mut cells = [Generator::EMPTY; MAX_CELLS];
+        for (idx, cell) in board.cells().enumerate() {
+            cells[idx] = match board.get(&cell) {
+                Some(player_id) if player_id == position.turn() => Generator::MOVER,
+                Some(_) => Generator::OPPONENT,
+                None => Generator::EMPTY,
+            };
+        }
+        let key = self.symmetries.key(&cells);
+        self.keys
+            .binary_search(&key)
+            .ok()
+            .map(|idx| decode(self.values[idx]))
+    }
+
+    /// Like [`Solver::analyze()`](crate::solver::Solver::analyze),
+    /// but returns [`None`] in the same cases as [`Self::probe()`].
+    pub fn analyze(&self, position: &Position) -> Option<Analysis> {
+        let value = self.probe(position)?;
+        let mut moves = position
+            .moves()
+            .into_iter()
+            .map(|cell| {
+                self.probe(&position.play(&cell))
+                    .map(|value| (cell, value.backed_up()))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        // the sort is stable, so the moves with equal values remain in the order of the cells
+        moves.sort_by(|(_, v1), (_, v2)| v2.cmp(v1));
+        let mut principal_variation = Vec::new();
+        let mut current = position.clone();
+        while let Some(best) = self.best_moves(&current).first() {
+            principal_variation.push(*best);
+            current = current.play(best);
+        }
+        Some(Analysis {
+            value,
+            moves,
+            principal_variation,
+        })
+    }
+
+    /// Like [`Solver::best_moves()`](crate::solver::Solver::best_moves),
+    /// but returns nothing in the same cases as [`Self::probe()`].
+    pub fn best_moves(&self, position: &Position) -> Vec<Cell> {
+        let values = position
+            .moves()
+            .into_iter()
+            .filter_map(|cell| {
+                self.probe(&position.play(&cell))
+                    .map(|value| (cell, value.backed_up()))
+            })
+            .collect::<Vec<_>>();
+        values
+            .iter()
+            .map(|(_, value)| *value)
+            .max()
+            .map_or_else(Vec::new, |best| {
+                values
+                    .into_iter()
+                    .filter(|(_, value)| *value == best)
+                    .map(|(cell, _)| cell)
+                    .collect()
+            })
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Self::read_from(io::BufReader::new(fs::File::open(path)?))
+    }
+
+    /// Like [`Self::read()`], but fails unless the tablebase is of the [`Board::SIZE`],
+    /// as the game is played and analyzed only on the board of that size.
+    pub fn read_playable(path: &Path) -> io::Result<Self> {
+        let tablebase = Self::read(path)?;
+        if tablebase.size != Board::SIZE {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "the tablebase is of the {size}x{size} board, \
+                    but only that of the {playable}x{playable} board can be used",
+                    size = tablebase.size,
+                    playable = Board::SIZE
+                ),
+            ));
+        }
+        Ok(tablebase)
+    }
+
+    /// Creates the parent directory of `path` if needed.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut writer = io::BufWriter::new(fs::File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Reads the format described in [`Self::write_to()`].
+    fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(ErrorKind::InvalidData, msg);
+        let mut header = [0; 10];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != Self::MAGIC {
+            return Err(invalid("not a tablebase".to_owned()));
+        }
+        if header[4] != Self::VERSION {
+            return Err(invalid(format!(
+                "unsupported version {}, expected {}",
+                header[4],
+                Self::VERSION
+            )));
+        }
+        let size = usize::from(header[5]);
+        if !(1..=Self::MAX_SIZE).contains(&size) {
+            return Err(invalid(format!("unsupported board size {size}")));
+        }
+        let len = usize::try_from(u32::from_le_bytes(header[6..].try_into().unwrap())).unwrap();
+        // the keys are distinct, and each is less than the number of the positions
+        let positions = 3_usize.pow(u32::try_from(size * size).unwrap());
+        if len > positions {
+            return Err(invalid(format!(
+                "{len} entries exceed {positions} positions of the board size {size}"
+            )));
+        }
+        let mut keys = vec![0; len * 4];
+        reader.read_exact(&mut keys)?;
+        let keys = keys
+            .chunks_exact(4)
+            .map(|key| u32::from_le_bytes(key.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        if !keys.windows(2).all(|w| w[0] < w[1]) {
+            return Err(invalid("the keys are not in ascending order".to_owned()));
+        }
+        let mut values = vec![0; len];
+        reader.read_exact(&mut values)?;
+        if let Some(value) = values.iter().find(|&&value| try_decode(value).is_none()) {
+            return Err(invalid(format!("invalid value {value:#04x}")));
+        }
+        if reader.read(&mut [0])? != 0 {
+            return Err(invalid("unexpected data after the values".to_owned()));
+        }
+        Ok(Self {
+            size,
+            keys,
+            values,
+            symmetries: Symmetries::new(size),
+        })
+    }
+
+    /// The format is compact, the integers are little-endian:
+    ///
+    /// ```text
+    /// "TTTB"        magic
+    /// u8            version
+    /// u8            board size
+    /// u32           number of entries
+    /// u32 * number  keys in ascending order
+    /// u8 * number   values, the highest 2 bits are 0 for a draw, 1 for a win, 2 for a loss,
+    ///               the lowest 6 bits are the number of plies
+    /// ```
+    fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(Self::MAGIC)?;
+        writer.write_all(&[Self::VERSION, u8::try_from(self.size).unwrap()])?;
+        writer.write_all(&u32::try_from(self.len()).unwrap().to_le_bytes())?;
+        for key in &self.keys {
+            writer.write_all(&key.to_le_bytes())?;
+        }
+        writer.write_all(&self.values)
+    }
+}
+
+/// See [`Tablebase::write_to()`].
+fn encode(value: Value) -> u8 {
+    match value {
+        Value::Draw => 0,
+        Value::Win(plies) => 0x40 | u8::try_from(plies).unwrap(),
+        Value::Loss(plies) => 0x80 | u8::try_from(plies).unwrap(),
+    }
+}
+
+fn decode(value: u8) -> Value {
+    try_decode(value).expect("the value must be valid")
+}
+
+fn try_decode(value: u8) -> Option<Value> {
+    let plies = u32::from(value & 0x3f);
+    match value >> 6 {
+        0 if plies == 0 => Some(Value::Draw),
+        1 => Some(Value::Win(plies)),
+        2 => Some(Value::Loss(plies)),
+        _ => None,
+    }
+}
+
+const MAX_CELLS: usize = Tablebase::MAX_SIZE * Tablebase::MAX_SIZE;
+
+/// Maps the cells of a board to keys that are the same for the boards
+/// that are the same up to a symmetry.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Symmetries {
+    /// For each of the 8 symmetries of the board, the indices the cells are mapped to.
+    maps: [Vec<usize>; 8],
+    /// The powers of 3 by cell indices.
+    powers: Vec<u32>,
+}
+
+impl Symmetries {
+    fn new(size: usize) -> Self {
+        let idx = |x: usize, y: usize| x * size + y;
+        let last = size - 1;
+        let map = |transform: &dyn Fn(usize, usize) -> usize| {
+            (0..size * size)
+                .map(|i| transform(i / size, i % size))
+                .collect::<Vec<_>>()
+        };
+        Self {
+            maps: [
+                map(&|x, y| idx(x, y)),
+                map(&|x, y| idx(y, x)),
+                map(&|x, y| idx(x, last - y)),
+                map(&|x, y| idx(last - x, y)),
+                map(&|x, y| idx(last - x, last - y)),
+                map(&|x, y| idx(y, last - x)),
+                map(&|x, y| idx(last - y, x)),
+                map(&|x, y| idx(last - y, last - x)),
+            ],
+            powers: (0..size * size)
+                .map(|i| 3_u32.pow(u32::try_from(i).unwrap()))
+                .collect(),
+        }
+    }
+
+    /// The `cells` as a number in base 3, which is the least one among all symmetries.
+    fn key(&self, cells: &[u8; MAX_CELLS]) -> u32 {
+        self.maps
+            .iter()
+            .map(|map| {
+                map.iter().enumerate().fold(0, |key, (i, &mapped)| {
+                    key + u32::from(cells[i]) * self.powers[mapped]
+                })
+            })
+            .min()
+            .expect("there must be symmetries")
+    }
+}
+
+/// Searches the game tree with the cells represented from the point of view of the player
+/// whose turn it is, so that positions with swapped marks and turns are the same.
+struct Generator {
+    size: usize,
+    symmetries: Symmetries,
+    /// The lines each cell is on by cell indices.
+    lines: Vec<Vec<Vec<usize>>>,
+    /// The positions that are not over.
+    values: HashMap<u32, Value, Xxh3Builder>,
+}
+
+impl Generator {
+    const EMPTY: u8 = 0;
+    /// A mark of the player whose turn it is.
+    const MOVER: u8 = 1;
+    const OPPONENT: u8 = 2;
+
+    fn new(size: usize) -> Self {
+        let idx = |x: usize, y: usize| x * size + y;
+        let mut lines = (0..size)
+            .flat_map(|i| {
+                [
+                    (0..size).map(|j| idx(i, j)).collect::<Vec<_>>(),
+                    (0..size).map(|j| idx(j, i)).collect(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        lines.push((0..size).map(|i| idx(i, i)).collect());
+        lines.push((0..size).map(|i| idx(i, size - 1 - i)).collect());
+        Self {
+            size,
+            symmetries: Symmetries::new(size),
+            lines: (0..size * size)
+                .map(|i| {
+                    lines
+                        .iter()
+                        .filter(|line| line.contains(&i))
+                        .cloned()
+                        .collect()
+                })
+                .collect(),
+            values: HashMap::with_hasher(Xxh3Builder::new()),
+        }
+    }
+
+    /// Returns the [`Value`] of the `cells`, where the opponent has occupied the `last` cell.
+    /// The `keys` are the [`Symmetries::key()`]s for each symmetry, without taking the minimum,
+    /// as is and with the marks swapped, and are updated incrementally for speed.
+    fn solve(
+        &mut self,
+        cells: &[u8; MAX_CELLS],
+        keys: &[[u32; 2]; 8],
+        last: Option<usize>,
+    ) -> Value {
+        if let Some(last) = last {
+            if self.lines[last]
+                .iter()
+                .any(|line| line.iter().all(|&i| cells[i] == Self::OPPONENT))
+            {
+                return Value::Loss(0);
+            }
+        }
+        let cell_count = self.size * self.size;
+        if cells[..cell_count].iter().all(|&cell| cell != Self::EMPTY) {
+            return Value::Draw;
+        }
+        let key = keys
+            .iter()
+            .map(|&[key, _]| key)
+            .min()
+            .expect("there must be symmetries");
+        if let Some(&value) = self.values.get(&key) {
+            return value;
+        }
+        let mut best = None;
+        for i in (0..cell_count).filter(|&i| cells[i] == Self::EMPTY) {
+            let mut child_cells = cells.map(|cell| match cell {
+                Self::MOVER => Self::OPPONENT,
+                Self::OPPONENT => Self::MOVER,
+                _ => Self::EMPTY,
+            });
+            child_cells[i] = Self::OPPONENT;
+            let mut child_keys = *keys;
+            for (child_key, map) in child_keys.iter_mut().zip(&self.symmetries.maps) {
+                let power = self.symmetries.powers[map[i]];
+                *child_key = [
+                    child_key[1] + u32::from(Self::OPPONENT) * power,
+                    child_key[0] + u32::from(Self::MOVER) * power,
+                ];
+            }
+            let value = self.solve(&child_cells, &child_keys, Some(i)).backed_up();
+            best = best.max(Some(value));
+        }
+        let value = best.expect("there must be moves");
+        self.values.insert(key, value);
+        value
+    }
+}
@@ -0,0 +1,108 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Tablebase {
+    use std::collections::HashSet;
+
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        game::{Board, PlayerId},
+        solver::{
+            tablebase::{decode, Tablebase, STANDARD},
+            Position, Solver, Value,
+        },
+        test::temp_dir,
+    };
+
+    #[test]
+    fn probe__agrees_with_solver_for_all_positions() {
+        let mut solver = Solver::new();
+        let mut stack = [0, 1]
+            .map(|idx| Position::new(Board::default(), PlayerId::new(idx)))
+            .to_vec();
+        let mut visited = HashSet::new();
+        while let Some(position) = stack.pop() {
+            if !visited.insert(position.to_string()) {
+                continue;
+            }
+            assert_eq!(
+                STANDARD.probe(&position),
+                Some(solver.value(&position)),
+                "{position}"
+            );
+            stack.extend(position.moves().iter().map(|cell| position.play(cell)));
+        }
+        // each entry stands for up to 8 symmetric positions, with either mark to move
+        assert!(
+            visited.len() > STANDARD.len() * 4,
+            "{}, {}",
+            visited.len(),
+            STANDARD.len()
+        );
+    }
+
+    #[test_case(".../.../..."; "empty")]
+    #[test_case("X../.O./..X"; "draw")]
+    #[test_case("XX./OO./..."; "win")]
+    #[test_case("XOX/OXO/O.. X"; "last move")]
+    #[test_case("XXX/OO./... O"; "over")]
+    fn analyze__agrees_with_solver(position: &str) {
+        let position = position.parse::<Position>().unwrap();
+        assert_eq!(
+            STANDARD.analyze(&position),
+            Some(Solver::new().analyze(&position))
+        );
+    }
+
+    #[test]
+    fn generate__2x2() {
+        let tablebase = Tablebase::generate(2);
+        // the empty board, a single mark, and 2 marks next to each other or on a diagonal
+        assert_eq!(tablebase.len(), 4);
+        assert_eq!(
+            tablebase
+                .values
+                .iter()
+                .map(|&value| decode(value))
+                .collect::<Vec<_>>(),
+            [Value::Win(3), Value::Loss(2), Value::Win(1), Value::Win(1),]
+        );
+    }
+
+    #[test]
+    fn write_read() {
+        let dir = temp_dir("tablebase");
+        let path = dir.join("3x3.ttb");
+        STANDARD.write(&path).unwrap();
+        assert_eq!(Tablebase::read(&path).unwrap(), *STANDARD);
+    }
+
+    #[test_case(2, false)]
+    #[test_case(Board::SIZE, true)]
+    fn read_playable(size: usize, expected_ok: bool) {
+        let dir = temp_dir("tablebase-playable");
+        let path = Tablebase::path(&dir, size);
+        Tablebase::generate(size).write(&path).unwrap();
+        assert_eq!(Tablebase::read_playable(&path).is_ok(), expected_ok);
+    }
+
+    #[test_case(0, None; "magic")]
+    #[test_case(4, Some(2); "version")]
+    #[test_case(5, Some(5); "size")]
+    #[test_case(9, Some(0xff); "too many entries")]
+    #[test_case(11, None; "truncated")]
+    #[test_case(usize::MAX, Some(0); "trailing data")]
+    fn read_from__Err(idx: usize, replacement: Option<u8>) {
+        let mut bytes = Vec::new();
+        STANDARD.write_to(&mut bytes).unwrap();
+        match (idx, replacement) {
+            (usize::MAX, Some(byte)) => bytes.push(byte),
+            (idx, Some(byte)) => bytes[idx] = byte,
+            (0, None) => bytes[0] = b'X',
+            (idx, None) => bytes.truncate(idx),
+        }
+        assert!(Tablebase::read_from(bytes.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Position {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{game::Mark, solver::Position};
+
+    #[test_case(".../.../...", ".../.../...", Mark::X)]
+    #[test_case(".../.X./...", ".../.X./...", Mark::O)]
+    #[test_case(".../.o./...", ".../.O./...", Mark::X)]
+    #[test_case(".../.../... o", ".../.../...", Mark::O)]
+    #[test_case("XXX/OO./... O", "XXX/OO./...", Mark::O)]
+    fn from_str__Ok(s: &str, expected_board: &str, expected_turn: Mark) {
+        let position = s.parse::<Position>().unwrap();
+        assert_eq!(
+            (position.board().to_string(), position.turn().mark()),
+            (expected_board.to_owned(), expected_turn)
+        );
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case(".../..."; "too few rows")]
+    #[test_case(".../.../.."; "too few cells")]
+    #[test_case(".../.../..Z"; "invalid cell")]
+    #[test_case(".../.../... Z"; "invalid mark")]
+    #[test_case(".../.../... X X"; "redundant mark")]
+    #[test_case("XX./.../..."; "too many marks")]
+    #[test_case(".X./.../... X"; "wrong turn")]
+    #[test_case("XXX/OO./... X"; "turn of winner")]
+    #[test_case("XXX/OOO/X.."; "both won")]
+    fn from_str__Err(s: &str) {
+        assert!(s.parse::<Position>().is_err(), "{s:?}");
+    }
+
+    #[test_case(".../.../...", &[])]
+    #[test_case("XX./OO./...", &["c2"])]
+    #[test_case("XX./OO./... O", &["c1"])]
+    #[test_case("X.X/.O./O.. O", &["b1"])]
+    #[test_case("X.O/.X./O.. O", &["c3"])]
+    #[test_case("O../.X./OX. X", &["a2"])]
+    #[test_case("XXX/OO./...", &[])]
+    fn threats(s: &str, expected: &[&str]) {
+        assert_eq!(
+            s.parse::<Position>()
+                .unwrap()
+                .threats()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn display() {
+        let s = "X.O/.X./... O";
+        assert_eq!(s.parse::<Position>().unwrap().to_string(), s);
+    }
+}
+
+mod Solver {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        game::Cell,
+        solver::{Position, Solver, Value},
+    };
+
+    #[test_case(".../.../...", Value::Draw)]
+    #[test_case("X../.../...", Value::Draw)]
+    #[test_case("XX./OO./...", Value::Win(1))]
+    #[test_case("XX./OO./... O", Value::Win(1))]
+    #[test_case("X../.O./..X", Value::Draw)]
+    #[test_case("X.O/.../X.O", Value::Win(1))]
+    #[test_case("XXX/OO./...", Value::Loss(0))]
+    #[test_case("XOX/XOO/OXX", Value::Draw)]
+    fn value(position: &str, expected: Value) {
+        assert_eq!(
+            Solver::new().value(&position.parse::<Position>().unwrap()),
+            expected
+        );
+    }
+
+    #[test]
+    fn analyze() {
+        let position = "XX./OO./...".parse::<Position>().unwrap();
+        let analysis = Solver::new().analyze(&position);
+        assert_eq!(analysis.value, Value::Win(1));
+        assert_eq!(
+            analysis.moves.first().copied(),
+            Some(("c1".parse::<Cell>().unwrap(), Value::Win(1)))
+        );
+        assert_eq!(analysis.moves.len(), 5);
+        assert!(
+            analysis.moves.windows(2).all(|pair| pair[0].1 >= pair[1].1),
+            "{analysis:?}"
+        );
+        assert_eq!(
+            analysis.principal_variation,
+            vec!["c1".parse::<Cell>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn analyze__principal_variation_of_draw() {
+        let analysis = Solver::new().analyze(&".../.../...".parse::<Position>().unwrap());
+        assert_eq!(analysis.value, Value::Draw);
+        assert_eq!(analysis.principal_variation.len(), 9);
+        assert!(
+            analysis
+                .moves
+                .iter()
+                .all(|(_, value)| *value == Value::Draw),
+            "{analysis:?}"
+        );
+    }
+}
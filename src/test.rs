@@ -1,2 +1,69 @@
 #![cfg(test)]
 #![allow(non_snake_case)]
+
+use core::{ops::Deref, time::Duration};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::{
+    game::{
+        Line, LocalPlayerType::Human, Outcome, Player, PlayerId, PlayerType::Local, RoundLog, State,
+    },
+    util::time::Time,
+};
+
+/// Creates a [`State`] with the rounds whose outcomes are encoded in `outcomes`
+/// as `W<winner idx>`, `S<surrendered player idx>`, or `D`.
+pub fn state(outcomes: &[&str]) -> State {
+    let mut state = State::new(
+        [
+            Player::new(PlayerId::new(0), Local(Human)),
+            Player::new(PlayerId::new(1), Local(Human)),
+        ],
+        u32::try_from(outcomes.len()).unwrap(),
+    );
+    let player_id = |code: &str| PlayerId::new(code[1..].parse().unwrap());
+    state.log = outcomes
+        .iter()
+        .map(|&code| RoundLog {
+            start: Time::new(Duration::ZERO),
+            moves: Vec::new(),
+            outcome: Some(match &code[..1] {
+                "W" => Outcome::Win(player_id(code), Line::D1),
+                "S" => Outcome::Surrender(player_id(code)),
+                _ => Outcome::Draw,
+            }),
+            duration: Duration::ZERO,
+        })
+        .collect();
+    state
+}
+
+/// A directory in [`env::temp_dir()`], which is removed with all its content on drop,
+/// so also when a test panics.
+#[derive(Debug)]
+pub struct TempDir(PathBuf);
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // the directory may not have been created
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Creates a [`TempDir`] path unique to the `name` and the test process,
+/// without creating the directory itself.
+pub fn temp_dir(name: &str) -> TempDir {
+    TempDir(env::temp_dir().join(format!("tic-tac-toe-test-{name}-{}", process::id())))
+}
@@ -0,0 +1,689 @@
+//! Tournaments between [`Ai`](crate::game::Ai)s and humans, see [`run()`].
+//!
+//! The games between [`AiKind`]s are played headlessly via [`sim::play_game()`],
+//! and the games of humans are hosted by a [`TournamentServer`], to which they connect
+//! with their accounts of the dedicated server.
+//! Besides the [`System`], the configurable rules are the number of rounds in each game,
+//! the number of tournament rounds of the [`System::Swiss`], the [`MctsOpts`],
+//! and the opening book all entrants consult first, see [`BookOpts`].
+
+use alloc::sync::Arc;
+use core::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+use std::{io, io::Write};
+
+use oorandom::Rand64;
+use serde::Serialize;
+
+use crate::{
+    ai,
+    ai::{book::BookOpts, mcts::MctsOpts, AiKind},
+    cli::TournamentArgs,
+    game::PlayerId,
+    process::ExitSignal,
+    save::{SavedGame, SavedPlayer},
+    server::TournamentServer,
+    sim::{self, ReportFormat},
+    util::csv,
+};
+
+mod test;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum System {
+    /// Each entrant plays each other entrant once.
+    RoundRobin,
+    /// In each tournament round, entrants with similar points play each other,
+    /// unless they have already played.
+    Swiss,
+    /// Only the winner of each game advances to the next tournament round.
+    SingleElimination,
+}
+
+impl System {
+    pub const NAMES: [&'static str; 3] = ["round-robin", "swiss", "single-elimination"];
+}
+
+impl Display for System {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::RoundRobin => Self::NAMES[0],
+            Self::Swiss => Self::NAMES[1],
+            Self::SingleElimination => Self::NAMES[2],
+        })
+    }
+}
+
+/// An entrant of a [`Tournament`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Entrant {
+    Ai(AiKind),
+    /// A human playing via the [`TournamentServer`] with the account of the name.
+    Human(String),
+}
+
+impl Entrant {
+    /// Precedes the names of the accounts of [`Self::Human`]s.
+    const HUMAN_PREFIX: char = '@';
+
+    fn saved_player(&self) -> SavedPlayer {
+        match self {
+            Self::Ai(kind) => SavedPlayer::ai(*kind),
+            Self::Human(_) => SavedPlayer::HUMAN,
+        }
+    }
+}
+
+impl Display for Entrant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ai(kind) => write!(f, "{kind}"),
+            Self::Human(name) => write!(f, "{}{name}", Self::HUMAN_PREFIX),
+        }
+    }
+}
+
+impl FromStr for Entrant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix(Self::HUMAN_PREFIX) {
+            Some(name) if name.trim().is_empty() => {
+                Err("the name of the account of a human entrant must not be blank".to_owned())
+            }
+            Some(name) => Ok(Self::Human(name.trim().to_owned())),
+            None => s.parse().map(Self::Ai).map_err(|_| {
+                format!(
+                    "unknown entrant {s:?}, expected one of {:?}, \
+                        or the name of an account preceded by {:?}",
+                    AiKind::NAMES,
+                    Self::HUMAN_PREFIX
+                )
+            }),
+        }
+    }
+}
+
+impl FromStr for System {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "round-robin" => Ok(Self::RoundRobin),
+            "swiss" => Ok(Self::Swiss),
+            "single-elimination" => Ok(Self::SingleElimination),
+            _ => Err(format!(
+                "unknown tournament system {s:?}, expected one of {:?}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+/// Plays the tournament specified by `args` and prints the [`Results`] to the standard output.
+/// If any human enters, starts a [`TournamentServer`] listening on the address in the `args`.
+/// If the `exit_signal` is received, the games that have not been completed are skipped,
+/// and the results cover only the completed ones.
+pub fn run(args: &TournamentArgs, exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
+    if args.entrants().len() < 2 {
+        return Err("There must be at least 2 entrants in a tournament.".into());
+    }
+    let book = args
+        .book()
+        .map(|path| BookOpts::read(path, args.book_randomness()))
+        .transpose()?;
+    let names = args
+        .entrants()
+        .iter()
+        .filter_map(|entrant| match entrant {
+            Entrant::Human(name) => Some(name.clone()),
+            Entrant::Ai(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if let Some((_, name)) = names
+        .iter()
+        .enumerate()
+        .find(|&(idx, name)| names[..idx].contains(name))
+    {
+        return Err(
+            format!("The account `{name}` has entered the tournament more than once.").into(),
+        );
+    }
+    let server = if names.is_empty() {
+        None
+    } else {
+        let server = TournamentServer::start(args.listen(), &names, exit_signal)?;
+        eprintln!(
+            "The human entrants may connect to {} and request to play each of their games.",
+            args.listen()
+        );
+        Some(server)
+    };
+    let tournament = Tournament {
+        entrants: args.entrants().to_vec(),
+        system: args.system(),
+        rounds: args.rounds(),
+        swiss_rounds: args.swiss_rounds(),
+        mcts: args.mcts(),
+        book,
+        seed: args.seed().unwrap_or_else(ai::time_based_seed),
+    };
+    let results = tournament.play(server.as_ref(), &|| exit_signal.is_received());
+    let mut out = io::stdout().lock();
+    match args.format() {
+        ReportFormat::Text => results.write_text(&mut out)?,
+        ReportFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &results)?;
+            writeln!(out)?;
+        }
+        ReportFormat::Csv => results.write_crosstable_csv(&mut out)?,
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Tournament {
+    /// In the order of seeding, the top seed first.
+    entrants: Vec<Entrant>,
+    system: System,
+    /// The number of rounds in each game.
+    rounds: u32,
+    /// See [`Self::swiss_rounds()`].
+    swiss_rounds: Option<u32>,
+    mcts: MctsOpts,
+    book: Option<BookOpts>,
+    /// The seed from which the seeds of all games are derived.
+    seed: u64,
+}
+
+impl Tournament {
+    /// The maximum number of games two entrants play in a tournament round of
+    /// the [`System::SingleElimination`]. If all of them are drawn, the higher seed advances.
+    const MAX_ELIMINATION_GAMES: u32 = 9;
+    /// Limits the search in [`swiss_pairs()`], which otherwise may take exponential time.
+    const MAX_SWISS_PAIRING_STEPS: u32 = 100_000;
+
+    /// The results depend only on the tournament, unless it is `interrupted`,
+    /// or humans enter, whose games are hosted by the `server`.
+    fn play(&self, server: Option<&TournamentServer>, interrupted: &dyn Fn() -> bool) -> Results {
+        let mut progress = Progress {
+            rng: Rand64::new(u128::from(self.seed)),
+            server,
+            interrupted,
+            games: Vec::new(),
+            byes: Vec::new(),
+        };
+        let complete = match self.system {
+            System::RoundRobin => self.play_round_robin(&mut progress),
+            System::Swiss => self.play_swiss(&mut progress),
+            System::SingleElimination => self.play_single_elimination(&mut progress),
+        }
+        .is_some();
+        Results::new(self, progress.games, progress.byes, complete)
+    }
+
+    /// The name of the `entrant` in the [`Results`].
+    fn title(&self, entrant: usize) -> String {
+        format!("{} {}", self.entrants[entrant], entrant + 1)
+    }
+
+    /// Returns the specified number of tournament rounds of the [`System::Swiss`],
+    /// or the binary logarithm of the number of entrants rounded up.
+    fn swiss_rounds(&self) -> u32 {
+        self.swiss_rounds
+            .unwrap_or_else(|| usize::BITS - (self.entrants.len() - 1).leading_zeros())
+    }
+
+    /// Pairs the entrants via the [circle method](https://en.wikipedia.org/wiki/Round-robin_tournament#Circle_method).
+    /// Returns [`None`] if interrupted.
+    fn play_round_robin(&self, progress: &mut Progress) -> Option<()> {
+        // `None` stands for a bye, which does not score in this system
+        let mut circle = (0..self.entrants.len()).map(Some).collect::<Vec<_>>();
+        if circle.len() % 2 == 1 {
+            circle.push(None);
+        }
+        let len = circle.len();
+        for tournament_round in 1..u32::try_from(len).unwrap() {
+            for idx in 0..len / 2 {
+                if let (Some(a), Some(b)) = (circle[idx], circle[len - 1 - idx]) {
+                    // alternates the marks of the entrants
+                    let entrants = if (tournament_round as usize + idx) % 2 == 0 {
+                        [a, b]
+                    } else {
+                        [b, a]
+                    };
+                    progress.play(self, tournament_round, entrants)?;
+                }
+            }
+            circle[1..].rotate_right(1);
+        }
+        Some(())
+    }
+
+    /// Pairs the entrants as close in rank as possible such that no pair has played before,
+    /// if such pairing is found, see [`swiss_pairs()`]. If the number of entrants is odd,
+    /// the lowest-ranked entrant that has not had a bye yet gets one.
+    /// Returns [`None`] if interrupted.
+    fn play_swiss(&self, progress: &mut Progress) -> Option<()> {
+        let len = self.entrants.len();
+        for tournament_round in 1..=self.swiss_rounds() {
+            let points = points(len, &progress.games, &progress.byes);
+            let mut ranked = (0..len).collect::<Vec<_>>();
+            ranked.sort_by(|&a, &b| points[b].total_cmp(&points[a]).then(a.cmp(&b)));
+            if len % 2 == 1 {
+                let idx = ranked
+                    .iter()
+                    .rposition(|&entrant| progress.byes.iter().all(|bye| bye.entrant != entrant))
+                    .unwrap_or(len - 1);
+                progress.byes.push(Bye {
+                    tournament_round,
+                    entrant: ranked.remove(idx),
+                });
+            }
+            let mut steps = Self::MAX_SWISS_PAIRING_STEPS;
+            let pairs = swiss_pairs(&ranked, &|a, b| progress.have_played(a, b), &mut steps)
+                .unwrap_or_else(|| ranked.chunks(2).map(|pair| (pair[0], pair[1])).collect());
+            for (a, b) in pairs {
+                // balances the marks of each entrant
+                let entrants = if progress.x_games(a) <= progress.x_games(b) {
+                    [a, b]
+                } else {
+                    [b, a]
+                };
+                progress.play(self, tournament_round, entrants)?;
+            }
+        }
+        Some(())
+    }
+
+    /// Seeds the entrants into a bracket such that the top seeds meet as late as possible,
+    /// the top seeds get byes if the number of entrants is not a power of two.
+    /// Returns [`None`] if interrupted.
+    fn play_single_elimination(&self, progress: &mut Progress) -> Option<()> {
+        let len = self.entrants.len();
+        let mut bracket = vec![0];
+        while bracket.len() < len {
+            let seeds = bracket.len() * 2;
+            bracket = bracket
+                .into_iter()
+                .flat_map(|seed| [seed, seeds - 1 - seed])
+                .collect();
+        }
+        // `None` stands for an absent entrant
+        let mut remaining = bracket
+            .into_iter()
+            .map(|seed| (seed < len).then_some(seed))
+            .collect::<Vec<_>>();
+        let mut tournament_round = 1;
+        while remaining.len() > 1 {
+            let mut advancing = Vec::with_capacity(remaining.len() / 2);
+            for pair in remaining.chunks(2) {
+                advancing.push(match (pair[0], pair[1]) {
+                    (Some(a), Some(b)) => {
+                        Some(self.play_elimination(progress, tournament_round, [a, b])?)
+                    }
+                    (Some(entrant), None) | (None, Some(entrant)) => {
+                        progress.byes.push(Bye {
+                            tournament_round,
+                            entrant,
+                        });
+                        Some(entrant)
+                    }
+                    (None, None) => None,
+                });
+            }
+            remaining = advancing;
+            tournament_round += 1;
+        }
+        Some(())
+    }
+
+    /// Plays until one of the `entrants` wins a game, swapping their marks after each game,
+    /// and returns the one that advances. Returns [`None`] if interrupted.
+    fn play_elimination(
+        &self,
+        progress: &mut Progress,
+        tournament_round: u32,
+        entrants: [usize; 2],
+    ) -> Option<usize> {
+        let mut entrants = entrants;
+        for _ in 0..Self::MAX_ELIMINATION_GAMES {
+            let game = progress.play(self, tournament_round, entrants)?;
+            if let Some(winner) = game.winner() {
+                return Some(entrants[winner.idx]);
+            }
+            entrants.reverse();
+        }
+        Some(entrants[0].min(entrants[1]))
+    }
+}
+
+/// Pairs the even number of `ranked` entrants such that no pair `have_played`,
+/// preferring the opponents closest in rank to the highest-ranked entrants.
+/// Returns [`None`] if there is no such pairing, or it is not found in the number of `steps`.
+fn swiss_pairs(
+    ranked: &[usize],
+    have_played: &dyn Fn(usize, usize) -> bool,
+    steps: &mut u32,
+) -> Option<Vec<(usize, usize)>> {
+    let Some((&a, rest)) = ranked.split_first() else {
+        return Some(Vec::new());
+    };
+    for (idx, &b) in rest.iter().enumerate() {
+        if *steps == 0 {
+            return None;
+        }
+        *steps -= 1;
+        if have_played(a, b) {
+            continue;
+        }
+        let mut others = rest.to_vec();
+        others.remove(idx);
+        if let Some(mut pairs) = swiss_pairs(&others, have_played, steps) {
+            pairs.insert(0, (a, b));
+            return Some(pairs);
+        }
+    }
+    None
+}
+
+/// The games and byes of a [`Tournament`] that is being played.
+struct Progress<'a> {
+    /// Decides the seeds of games.
+    rng: Rand64,
+    /// Hosts the games of humans, [`None`] if no human enters.
+    server: Option<&'a TournamentServer>,
+    interrupted: &'a dyn Fn() -> bool,
+    games: Vec<Game>,
+    byes: Vec<Bye>,
+}
+
+impl Progress<'_> {
+    /// The first of the `entrants` plays X.
+    /// Returns [`None`] if interrupted before the game is over.
+    fn play(
+        &mut self,
+        tournament: &Tournament,
+        tournament_round: u32,
+        entrants: [usize; 2],
+    ) -> Option<Game> {
+        if (self.interrupted)() {
+            return None;
+        }
+        let seed = self.rng.rand_u64();
+        let saved_game = SavedGame {
+            seed,
+            rounds: tournament.rounds,
+            players: entrants.map(|entrant| tournament.entrants[entrant].saved_player()),
+            log: Vec::new(),
+        }
+        .with_mcts(tournament.mcts);
+        let round_wins = if saved_game.has_humans() {
+            // the AIs are known to the humans by their titles
+            let names = entrants.map(|entrant| match &tournament.entrants[entrant] {
+                Entrant::Human(name) => name.clone(),
+                Entrant::Ai(_) => tournament.title(entrant),
+            });
+            self.server
+                .expect("humans should enter only with a server")
+                .play(
+                    &saved_game,
+                    [&names[0], &names[1]],
+                    tournament.book.as_ref(),
+                    self.interrupted,
+                )?
+        } else {
+            let mut round_wins = [0; 2];
+            let outcomes = sim::play_game(&saved_game, tournament.book.as_ref());
+            for winner in outcomes.iter().filter_map(|outcome| outcome.winner) {
+                round_wins[winner.idx] += 1;
+            }
+            round_wins
+        };
+        let game = Game {
+            tournament_round,
+            entrants,
+            round_wins,
+            seed,
+        };
+        self.games.push(game);
+        Some(game)
+    }
+
+    fn have_played(&self, a: usize, b: usize) -> bool {
+        self.games
+            .iter()
+            .any(|game| game.entrants == [a, b] || game.entrants == [b, a])
+    }
+
+    fn x_games(&self, entrant: usize) -> usize {
+        self.games
+            .iter()
+            .filter(|game| game.entrants[0] == entrant)
+            .count()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+struct Game {
+    /// Starts from 1.
+    tournament_round: u32,
+    /// The indices of the entrants indexed by [`PlayerId`].
+    entrants: [usize; 2],
+    /// Indexed by [`PlayerId`].
+    round_wins: [u32; 2],
+    /// Allows replaying the game as a [`SavedGame`] if no human has played it.
+    seed: u64,
+}
+
+impl Game {
+    /// The player that won more rounds, [`None`] iff the game is a draw.
+    fn winner(&self) -> Option<PlayerId> {
+        match self.round_wins[0].cmp(&self.round_wins[1]) {
+            Ordering::Greater => Some(PlayerId::new(0)),
+            Ordering::Less => Some(PlayerId::new(1)),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// 1 for a win, 0.5 for a draw, 0 for a loss.
+    fn points(&self, player_id: PlayerId) -> f64 {
+        match self.winner() {
+            Some(winner) if winner == player_id => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        }
+    }
+}
+
+/// A bye scores as a win.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+struct Bye {
+    tournament_round: u32,
+    entrant: usize,
+}
+
+/// Returns the points of each of the `len` entrants.
+fn points(len: usize, games: &[Game], byes: &[Bye]) -> Vec<f64> {
+    let mut points = vec![0.0; len];
+    for game in games {
+        for idx in [0, 1] {
+            points[game.entrants[idx]] += game.points(PlayerId::new(idx));
+        }
+    }
+    for bye in byes {
+        points[bye.entrant] += 1.0;
+    }
+    points
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+struct Standing {
+    entrant: usize,
+    games: u32,
+    wins: u32,
+    draws: u32,
+    losses: u32,
+    byes: u32,
+    points: f64,
+    /// The sum of the points of the opponents, breaks ties in points.
+    buchholz: f64,
+}
+
+/// The entrants are referred to by their indices.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Results {
+    system: System,
+    seed: u64,
+    rounds_per_game: u32,
+    entrants: Vec<String>,
+    /// `false` iff the tournament was interrupted.
+    complete: bool,
+    games: Vec<Game>,
+    byes: Vec<Bye>,
+    /// The best entrant first.
+    standings: Vec<Standing>,
+    /// The points each entrant, indexed by rows, scored against each other entrant,
+    /// indexed by columns, [`None`] if they have not played.
+    crosstable: Vec<Vec<Option<f64>>>,
+}
+
+impl Results {
+    fn new(tournament: &Tournament, games: Vec<Game>, byes: Vec<Bye>, complete: bool) -> Self {
+        let len = tournament.entrants.len();
+        let points = points(len, &games, &byes);
+        let mut standings = (0..len)
+            .map(|entrant| Standing {
+                entrant,
+                games: 0,
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                byes: 0,
+                points: points[entrant],
+                buchholz: 0.0,
+            })
+            .collect::<Vec<_>>();
+        let mut crosstable = vec![vec![None; len]; len];
+        for game in &games {
+            for idx in [0, 1] {
+                let player_id = PlayerId::new(idx);
+                let (entrant, opponent) = (game.entrants[idx], game.entrants[1 - idx]);
+                let standing = &mut standings[entrant];
+                standing.games += 1;
+                match game.winner() {
+                    Some(winner) if winner == player_id => standing.wins += 1,
+                    Some(_) => standing.losses += 1,
+                    None => standing.draws += 1,
+                }
+                standing.buchholz += points[opponent];
+                *crosstable[entrant][opponent].get_or_insert(0.0) += game.points(player_id);
+            }
+        }
+        for bye in &byes {
+            standings[bye.entrant].byes += 1;
+        }
+        standings.sort_by(|a, b| {
+            b.points
+                .total_cmp(&a.points)
+                .then(b.buchholz.total_cmp(&a.buchholz))
+                .then(a.entrant.cmp(&b.entrant))
+        });
+        Self {
+            system: tournament.system,
+            seed: tournament.seed,
+            rounds_per_game: tournament.rounds,
+            entrants: (0..len).map(|entrant| tournament.title(entrant)).collect(),
+            complete,
+            games,
+            byes,
+            standings,
+            crosstable,
+        }
+    }
+
+    fn write_text(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "System: {}, entrants: {}, rounds per game: {}, seed: {}.",
+            self.system,
+            self.entrants.len(),
+            self.rounds_per_game,
+            self.seed
+        )?;
+        if !self.complete {
+            writeln!(
+                out,
+                "The tournament was interrupted, the results cover only the completed games."
+            )?;
+        }
+        let name_width = self.entrants.iter().map(String::len).max().unwrap_or(0);
+        writeln!(out, "Standings:")?;
+        writeln!(
+            out,
+            "  {:>3}  {:<name_width$}  {:>5}  {:>4}  {:>5}  {:>4}  {:>4}  {:>6}  {:>8}",
+            "#", "entrant", "games", "won", "drawn", "lost", "byes", "points", "Buchholz"
+        )?;
+        for (idx, standing) in self.standings.iter().enumerate() {
+            writeln!(
+                out,
+                "  {:>3}  {:<name_width$}  {:>5}  {:>4}  {:>5}  {:>4}  {:>4}  {:>6.1}  {:>8.1}",
+                idx + 1,
+                self.entrants[standing.entrant],
+                standing.games,
+                standing.wins,
+                standing.draws,
+                standing.losses,
+                standing.byes,
+                standing.points,
+                standing.buchholz
+            )?;
+        }
+        writeln!(
+            out,
+            "Crosstable, the points of the entrants in the rows against those in the columns:"
+        )?;
+        write!(out, "  {:<name_width$}", "")?;
+        for idx in 0..self.entrants.len() {
+            write!(out, "  {:>5}", idx + 1)?;
+        }
+        writeln!(out)?;
+        for (idx, row) in self.crosstable.iter().enumerate() {
+            write!(out, "  {:<name_width$}", self.entrants[idx])?;
+            for points in row {
+                match points {
+                    Some(points) => write!(out, "  {points:>5.1}")?,
+                    None => write!(out, "  {:>5}", "-")?,
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a row for each entrant with the points it scored against each other entrant,
+    /// the cells of entrants that have not played are empty.
+    fn write_crosstable_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "entrant")?;
+        for name in &self.entrants {
+            write!(out, ",{}", csv::field(name))?;
+        }
+        writeln!(out)?;
+        for (idx, row) in self.crosstable.iter().enumerate() {
+            write!(out, "{}", csv::field(&self.entrants[idx]))?;
+            for points in row {
+                write!(out, ",")?;
+                if let Some(points) = points {
+                    write!(out, "{points}")?;
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Tournament {
+    use std::collections::HashSet;
+
+    use ntest::timeout;
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        ai::{mcts::MctsOpts, AiKind},
+        tournament::{Entrant, Results, System, Tournament},
+    };
+
+    fn tournament(system: System, entrants: usize) -> Tournament {
+        Tournament {
+            entrants: vec![Entrant::Ai(AiKind::Random); entrants],
+            system,
+            rounds: 3,
+            swiss_rounds: None,
+            mcts: MctsOpts::DEFAULT,
+            book: None,
+            seed: 42,
+        }
+    }
+
+    /// Returns the unordered pairs of entrants that have played, with repetitions.
+    fn pairs(results: &Results) -> Vec<(usize, usize)> {
+        results
+            .games
+            .iter()
+            .map(|game| {
+                let [a, b] = game.entrants;
+                (a.min(b), a.max(b))
+            })
+            .collect()
+    }
+
+    #[test_case(2, 1)]
+    #[test_case(4, 6)]
+    #[test_case(5, 10)]
+    #[timeout(1_000)]
+    fn play__round_robin(entrants: usize, expected_games: usize) {
+        let results = tournament(System::RoundRobin, entrants).play(None, &|| false);
+        assert!(results.complete);
+        let pairs = pairs(&results);
+        assert_eq!(pairs.len(), expected_games);
+        assert_eq!(pairs.iter().collect::<HashSet<_>>().len(), expected_games);
+        assert!(results.byes.is_empty());
+        assert_eq!(
+            results
+                .standings
+                .iter()
+                .map(|standing| standing.games)
+                .collect::<Vec<_>>(),
+            vec![u32::try_from(entrants - 1).unwrap(); entrants]
+        );
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn play__swiss() {
+        let results = tournament(System::Swiss, 5).play(None, &|| false);
+        // 3 tournament rounds of 2 games and a bye each
+        assert_eq!(results.games.len(), 6);
+        assert_eq!(
+            results
+                .byes
+                .iter()
+                .map(|bye| bye.entrant)
+                .collect::<HashSet<_>>()
+                .len(),
+            3
+        );
+        let pairs = pairs(&results);
+        assert_eq!(pairs.iter().collect::<HashSet<_>>().len(), pairs.len());
+    }
+
+    #[test]
+    #[timeout(1_000)]
+    fn play__single_elimination() {
+        let results = tournament(System::SingleElimination, 5).play(None, &|| false);
+        // the top 3 seeds get byes in a bracket of 8
+        assert_eq!(
+            results
+                .byes
+                .iter()
+                .map(|bye| (bye.tournament_round, bye.entrant))
+                .collect::<Vec<_>>(),
+            vec![(1, 0), (1, 1), (1, 2)]
+        );
+        assert_eq!(pairs(&results).iter().collect::<HashSet<_>>().len(), 4);
+        let unbeaten = results
+            .standings
+            .iter()
+            .filter(|standing| standing.losses == 0)
+            .collect::<Vec<_>>();
+        assert_eq!(unbeaten.len(), 1);
+        assert_eq!(unbeaten[0].wins + unbeaten[0].byes, 3);
+    }
+
+    #[test_case(System::RoundRobin)]
+    #[test_case(System::Swiss)]
+    #[test_case(System::SingleElimination)]
+    #[timeout(1_000)]
+    fn play__deterministic(system: System) {
+        let tournament = tournament(system, 6);
+        assert_eq!(
+            tournament.play(None, &|| false),
+            tournament.play(None, &|| false)
+        );
+    }
+
+    #[test]
+    fn play__interrupted() {
+        let results = tournament(System::RoundRobin, 4).play(None, &|| true);
+        assert!(!results.complete);
+        assert!(results.games.is_empty());
+    }
+
+    #[test_case(2, 1)]
+    #[test_case(5, 3)]
+    #[test_case(8, 3)]
+    #[test_case(9, 4)]
+    fn swiss_rounds(entrants: usize, expected: u32) {
+        assert_eq!(tournament(System::Swiss, entrants).swiss_rounds(), expected);
+    }
+}
+
+mod Results {
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::{
+        ai::{mcts::MctsOpts, AiKind},
+        tournament::{Bye, Entrant, Game, Results, System, Tournament},
+    };
+
+    fn results() -> Results {
+        let game = |entrants: [usize; 2], round_wins: [u32; 2]| Game {
+            tournament_round: 1,
+            entrants,
+            round_wins,
+            seed: 0,
+        };
+        Results::new(
+            &Tournament {
+                entrants: vec![Entrant::Ai(AiKind::Random); 3],
+                system: System::Swiss,
+                rounds: 2,
+                swiss_rounds: None,
+                mcts: MctsOpts::DEFAULT,
+                book: None,
+                seed: 42,
+            },
+            vec![
+                game([0, 1], [1, 1]),
+                game([2, 0], [0, 2]),
+                game([1, 2], [2, 0]),
+            ],
+            vec![Bye {
+                tournament_round: 1,
+                entrant: 2,
+            }],
+            true,
+        )
+    }
+
+    #[test]
+    fn new() {
+        let results = results();
+        assert_eq!(
+            results
+                .standings
+                .iter()
+                .map(|standing| (
+                    standing.entrant,
+                    standing.points.to_string(),
+                    standing.buchholz.to_string()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "1.5".to_owned(), "2.5".to_owned()),
+                (1, "1.5".to_owned(), "2.5".to_owned()),
+                (2, "1".to_owned(), "3".to_owned()),
+            ]
+        );
+        assert_eq!(results.crosstable[0], vec![None, Some(0.5), Some(1.0)]);
+    }
+
+    #[test]
+    fn write_crosstable_csv() {
+        let mut csv = Vec::new();
+        results().write_crosstable_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "entrant,random 1,random 2,random 3\n\
+            random 1,,0.5,1\n\
+            random 2,0.5,,1\n\
+            random 3,0,0,\n"
+        );
+    }
+}
+
+mod Entrant {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{ai::AiKind, tournament::Entrant};
+
+    #[test_case("mcts", &Entrant::Ai(AiKind::Mcts), "mcts")]
+    #[test_case("@ alice", &Entrant::Human("alice".to_owned()), "@alice")]
+    fn from_str(s: &str, expected: &Entrant, expected_display: &str) {
+        let entrant = s.parse::<Entrant>().unwrap();
+        assert_eq!(&entrant, expected);
+        assert_eq!(entrant.to_string(), expected_display);
+    }
+
+    #[test_case("@ ", "the name of the account of a human entrant must not be blank")]
+    #[test_case(
+        "alice",
+        "unknown entrant \"alice\", expected one of [\"random\", \"mcts\", \"tablebase\"], \
+            or the name of an account preceded by '@'"
+    )]
+    fn from_str__err(s: &str, expected: &str) {
+        assert_eq!(s.parse::<Entrant>().unwrap_err(), expected);
+    }
+}
@@ -0,0 +1,147 @@
+//! Training of [`MenaceAi`] by self-play, see [`run()`].
+
+use alloc::{rc::Rc, sync::Arc};
+use core::{
+    cell::RefCell,
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+use std::{env, io, io::Write};
+
+use oorandom::Rand64;
+
+use crate::{
+    ai,
+    ai::{
+        menace::{Menace, MenaceAi},
+        AiKind,
+    },
+    cli::TrainArgs,
+    game::{PlayerId, State},
+    process::ExitSignal,
+    save::SavedGame,
+    sim,
+};
+
+mod test;
+
+/// The number of games against [`AiKind::Random`] played after each epoch.
+const EVALUATION_GAMES: u64 = 200;
+
+/// Continues training from the latest epoch in [`Menace::dir()`], if any.
+/// After each epoch, prints how [`MenaceAi`] plays against [`AiKind::Random`],
+/// and writes the [`Menace`] of the epoch.
+/// If the `exit_signal` is received, the epoch in progress is discarded.
+pub fn run(args: &TrainArgs, exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
+    let dir = Menace::dir(&|name| env::var_os(name))
+        .ok_or("Cannot locate MENACE, neither $XDG_DATA_HOME nor $HOME is specified.")?;
+    let mut menace = match Menace::epochs(&dir)?.last() {
+        Some(&epoch) => {
+            let path = Menace::path(&dir, epoch);
+            Menace::read(&path).map_err(|e| format!("Failed to read {}: {e}.", path.display()))?
+        }
+        None => Menace::default(),
+    };
+    let mut rng = Rand64::new(u128::from(args.seed().unwrap_or_else(ai::time_based_seed)));
+    let mut out = io::stdout().lock();
+    for _ in 0..args.epochs() {
+        let Some(trained) = train_epoch(menace.clone(), args.games(), &mut rng, &|| {
+            exit_signal.is_received()
+        }) else {
+            break;
+        };
+        menace = trained;
+        let results = evaluate(&menace, EVALUATION_GAMES, &mut rng);
+        let path = Menace::path(&dir, menace.epoch);
+        menace
+            .write(&path)
+            .map_err(|e| format!("Failed to write {}: {e}.", path.display()))?;
+        writeln!(out, "Epoch {}: {results}", menace.epoch)?;
+    }
+    Ok(())
+}
+
+/// Plays `games` games of [`MenaceAi`] against itself, learning after each one.
+/// Returns [`None`] if `interrupted`.
+fn train_epoch(
+    menace: Menace,
+    games: u64,
+    rng: &mut Rand64,
+    interrupted: &dyn Fn() -> bool,
+) -> Option<Menace> {
+    let menace = Rc::new(RefCell::new(menace));
+    for _ in 0..games {
+        if interrupted() {
+            return None;
+        }
+        // the AIs are ignored by `world_with()`
+        let game = SavedGame::new(rng.rand_u64(), State::DEFAULT_ROUNDS, [AiKind::Random; 2]);
+        let (_, world) = sim::play_world(game.world_with(|_, seed, action_queue| {
+            Box::new(MenaceAi::new(seed, action_queue, Rc::clone(&menace)))
+        }));
+        for idx in 0..2 {
+            menace.borrow_mut().learn(world.state(), PlayerId::new(idx));
+        }
+    }
+    let mut menace = Rc::try_unwrap(menace)
+        .ok()
+        .map(RefCell::into_inner)
+        .expect("the AIs must have been dropped");
+    menace.epoch += 1;
+    Some(menace)
+}
+
+/// The rounds of [`MenaceAi`] against [`AiKind::Random`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+struct Results {
+    won: u64,
+    drawn: u64,
+    lost: u64,
+}
+
+impl Results {
+    fn rounds(&self) -> u64 {
+        self.won + self.drawn + self.lost
+    }
+}
+
+impl Display for Results {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let percent = |count: u64| 100.0 * count as f64 / self.rounds().max(1) as f64;
+        write!(
+            f,
+            "against random, {:.1}% of rounds won, {:.1}% drawn, {:.1}% lost",
+            percent(self.won),
+            percent(self.drawn),
+            percent(self.lost),
+        )
+    }
+}
+
+/// Plays `games` games of [`MenaceAi`] against [`AiKind::Random`], with [`MenaceAi`]
+/// playing X in even games and O in odd ones. The `menace` does not learn.
+fn evaluate(menace: &Menace, games: u64, rng: &mut Rand64) -> Results {
+    let menace = Rc::new(RefCell::new(menace.clone()));
+    let mut results = Results::default();
+    for game_idx in 0..games {
+        let menace_id = PlayerId::new(usize::try_from(game_idx % 2).unwrap());
+        // the AIs are ignored by `world_with()`
+        let game = SavedGame::new(rng.rand_u64(), State::DEFAULT_ROUNDS, [AiKind::Random; 2]);
+        let (outcomes, _) = sim::play_world(game.world_with(|player_id, seed, action_queue| {
+            if player_id == menace_id {
+                Box::new(MenaceAi::new(seed, action_queue, Rc::clone(&menace)))
+            } else {
+                AiKind::Random.create(seed, action_queue)
+            }
+        }));
+        for outcome in outcomes {
+            match outcome.winner {
+                Some(winner) if winner == menace_id => results.won += 1,
+                Some(_) => results.lost += 1,
+                None => results.drawn += 1,
+            }
+        }
+    }
+    results
+}
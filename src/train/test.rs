@@ -0,0 +1,34 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+use oorandom::Rand64;
+
+use crate::{
+    ai::menace::Menace,
+    train::{evaluate, train_epoch},
+};
+
+#[test]
+fn train_epoch__improves_against_random() {
+    let mut rng = Rand64::new(0);
+    let untrained = Menace::default();
+    let mut menace = untrained.clone();
+    for epoch in 1..=3 {
+        menace = train_epoch(menace, 500, &mut rng, &|| false).unwrap();
+        assert_eq!(menace.epoch, epoch);
+    }
+    let untrained_results = evaluate(&untrained, 200, &mut rng);
+    let results = evaluate(&menace, 200, &mut rng);
+    assert!(
+        results.won > untrained_results.won && results.lost < untrained_results.lost,
+        "{untrained_results}, {results}"
+    );
+}
+
+#[test]
+fn train_epoch__interrupted() {
+    assert_eq!(
+        train_epoch(Menace::default(), 10, &mut Rand64::new(0), &|| true),
+        None
+    );
+}
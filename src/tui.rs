@@ -1,50 +1,73 @@
 use alloc::sync::Arc;
-use std::error::Error;
+use core::num::NonZeroU32;
+use std::{env, error::Error};
 
 use cursive::{
     event::{Event, Key},
     menu::Tree,
-    theme::{BaseColor, BorderStyle, Color, PaletteColor},
     views::{Dialog, Panel},
     Cursive,
 };
 
 use crate::{
+    ai::book::BookOpts,
+    cli::{InteractiveArgs, PlayArgs},
     process::{ExitSignal, APP_METADATA},
     tui::{
+        theme::Themes,
         util::{MenuItemSwitchState::Disabled, MenuItemsStateSwitcher},
         view::SplashScreenView,
     },
 };
 
 mod fx;
+mod glyph;
 mod menu;
+pub mod theme;
 mod util;
 mod view;
 
 // TODO use https://crates.io/crates/anyhow?
-pub fn run(exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
+pub fn run(args: &InteractiveArgs, exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
+    run_with(args, exit_signal, |_, _| {})
+}
+
+/// Starts a Vs. AI game right away, as if it was started via the menu.
+pub fn play(args: &PlayArgs, exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
+    let game = menu::GameOpts::new(
+        NonZeroU32::new(args.rounds()).expect("the number of rounds must be positive"),
+        args.interactive().seed(),
+    );
+    let (ai, rating_period) = (args.ai(), args.interactive().rating_period());
+    run_with(args.interactive(), exit_signal, move |tui, book| {
+        menu::vs_ai::start(
+            menu::vs_ai::VsAiOpts::new(game, ai, rating_period, book),
+            tui,
+        );
+    })
+}
+
+/// Calls `start` with the opening book of Vs. AI games, if any,
+/// once the menu and the splash screen are set up.
+fn run_with(
+    args: &InteractiveArgs,
+    exit_signal: &Arc<ExitSignal>,
+    start: impl FnOnce(&mut Cursive, Option<BookOpts>),
+) -> Result<(), Box<dyn Error>> {
+    let themes = Themes::load(&|name| env::var_os(name))?;
     let mut tui = Cursive::new();
-    {
-        // `Color::Black` works weirdly, using `Color::RgbLowRes` instead
-        let dark_black = Color::RgbLowRes(0, 0, 0);
-        let grey = Color::RgbLowRes(3, 3, 3);
-        tui.update_theme(|theme| {
-            theme.shadow = true;
-            theme.borders = BorderStyle::Simple;
-            theme.palette[PaletteColor::Background] = dark_black;
-            theme.palette[PaletteColor::Shadow] = dark_black;
-            theme.palette[PaletteColor::View] = Color::Light(BaseColor::White);
-            theme.palette[PaletteColor::Primary] = dark_black;
-            theme.palette[PaletteColor::Secondary] = grey;
-            theme.palette[PaletteColor::Tertiary] = Color::Dark(BaseColor::Yellow);
-            theme.palette[PaletteColor::TitlePrimary] = Color::Dark(BaseColor::Blue);
-            theme.palette[PaletteColor::TitleSecondary] = Color::Light(BaseColor::Yellow);
-            theme.palette[PaletteColor::Highlight] = Color::Light(BaseColor::Green);
-            theme.palette[PaletteColor::HighlightInactive] = theme.palette[PaletteColor::Highlight];
-            theme.palette[PaletteColor::HighlightText] = Color::Dark(BaseColor::Blue);
-        });
-    }
+    tui.set_theme(
+        themes
+            .get(args.theme())
+            .ok_or_else(|| format!("Unknown theme `{}`.", args.theme()))?
+            .clone(),
+    );
+    let book = args
+        .book()
+        .map(|path| BookOpts::read(path, args.book_randomness()))
+        .transpose()?;
+    let seed = args.seed();
+    let rating_period = args.rating_period();
     tui.menubar()
         .add_subtree(
             "Game",
@@ -52,11 +75,26 @@ pub fn run(exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
                 .subtree(
                     "Singleplayer",
                     Tree::new()
-                        .leaf("TODO Vs. AI", |_| todo!())
-                        .leaf("AI vs. AI", |tui| {
+                        .leaf("Vs. AI", {
+                            let book = book.clone();
+                            move |tui| {
+                                let book = book.clone();
+                                menu::callback(
+                                    ("Vs. AI options", menu::vs_ai::opts_dlg_content(seed)),
+                                    (
+                                        move |tui| {
+                                            menu::vs_ai::opts(tui, rating_period, book.clone())
+                                        },
+                                        menu::vs_ai::start,
+                                    ),
+                                    tui,
+                                );
+                            }
+                        })
+                        .leaf("AI vs. AI", move |tui| {
                             menu::callback(
-                                ("AI vs. AI options", menu::ai_vs_ai::game_opts_dlg_content()),
-                                (menu::ai_vs_ai::game_opts, menu::ai_vs_ai::start),
+                                ("AI vs. AI options", menu::game_opts_dlg_content(seed)),
+                                (menu::game_opts, menu::ai_vs_ai::start),
                                 tui,
                             );
                         }),
@@ -68,9 +106,18 @@ pub fn run(exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
                         .leaf("TODO Connect", |_| todo!())
                         .leaf("TODO Host", |_| todo!()),
                 )
+                .leaf(
+                    format!("{} ({})", menu::PAUSE_LABEL, view::GameView::PAUSE_KEY),
+                    menu::toggle_paused,
+                )
+                .leaf("Save", menu::saved_game::save)
+                .leaf("Load", menu::saved_game::load)
+                .leaf("Statistics", menu::stats::show)
+                .leaf("Leaderboard", menu::leaderboard::show)
                 .leaf(menu::STOP_LABEL, |_| todo!())
                 .leaf(menu::EXIT_LABEL, exit),
         )
+        .add_subtree("View", Tree::new().subtree("Theme", themes_menu(&themes)))
         .add_subtree("Help", Tree::new().leaf("About", show_about_dlg));
     MenuItemsStateSwitcher::new().switch(tui.menubar(), |lbl| {
         if lbl == menu::STOP_LABEL {
@@ -94,6 +141,7 @@ pub fn run(exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
     tui.set_autohide_menu(false);
     tui.screen_mut()
         .add_fullscreen_layer(Panel::new(SplashScreenView::new()));
+    start(&mut tui, book);
     tui.set_fps(30);
     tui.try_run_with::<Box<dyn Error>, _>(|| {
         // work around https://github.com/gyscos/Cursive/issues/142
@@ -103,6 +151,15 @@ pub fn run(exit_signal: &Arc<ExitSignal>) -> Result<(), Box<dyn Error>> {
     })
 }
 
+fn themes_menu(themes: &Themes) -> Tree {
+    let mut tree = Tree::new();
+    for (name, theme) in themes.iter() {
+        let theme = theme.clone();
+        tree.add_leaf(name, move |tui| tui.set_theme(theme.clone()));
+    }
+    tree
+}
+
 fn exit(tui: &mut Cursive) {
     tui.quit();
 }
@@ -5,13 +5,28 @@ use cursive::{
     Printer,
 };
 
-use crate::util::time::{Time, Timer};
-
-pub const HIGHLIGHTED_COLOR_STYLE: ColorStyle = ColorStyle {
-    front: ColorType::Palette(PaletteColor::Primary),
-    back: ColorType::Palette(PaletteColor::Tertiary),
+use crate::{
+    tui::theme::Themes,
+    util::time::{Time, Timer},
 };
 
+/// Uses [`Themes::HIGHLIGHTED_TEXT`] and [`Themes::HIGHLIGHTED`] of the current theme,
+/// falling back to [`PaletteColor::Primary`] and [`PaletteColor::Tertiary`] respectively.
+pub fn highlighted_color_style(printer: &Printer) -> ColorStyle {
+    let palette = &printer.theme.palette;
+    let color = |key: &str, fallback: PaletteColor| {
+        palette
+            .custom(key)
+            .map_or(ColorType::Palette(fallback), |&color| {
+                ColorType::Color(color)
+            })
+    };
+    ColorStyle {
+        front: color(Themes::HIGHLIGHTED_TEXT, PaletteColor::Primary),
+        back: color(Themes::HIGHLIGHTED, PaletteColor::Tertiary),
+    }
+}
+
 #[derive(Debug)]
 pub struct BlinkingAnimation {
     start: Time,
@@ -44,7 +59,7 @@ impl BlinkingAnimation {
         } else {
             let even_period = (elapsed.as_nanos() / self.period.as_nanos()) & 1 == 0;
             if even_period {
-                printer.with_color(HIGHLIGHTED_COLOR_STYLE, f);
+                printer.with_color(highlighted_color_style(printer), f);
             } else {
                 f(printer);
             }
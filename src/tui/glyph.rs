@@ -0,0 +1,87 @@
+//! Multi-line glyphs of [`Mark`]s, see [`glyph()`].
+
+use std::ffi::OsString;
+
+use cursive::Vec2;
+
+use crate::game::Mark;
+
+mod test;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Charset {
+    Unicode,
+    Ascii,
+}
+
+impl Charset {
+    /// Picks [`Self::Unicode`] if the locale specified via the environment variables
+    /// uses the UTF-8 encoding.
+    pub fn detect(env: &dyn Fn(&str) -> Option<OsString>) -> Self {
+        // the first non-empty one takes precedence
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|&name| env(name))
+            .find(|locale| !locale.is_empty())
+            .map(|locale| locale.to_string_lossy().to_lowercase());
+        match locale {
+            Some(locale) if locale.contains("utf-8") || locale.contains("utf8") => Self::Unicode,
+            _ => Self::Ascii,
+        }
+    }
+}
+
+/// Returns the rows of the largest glyph of `mark` that fits in `max_size`,
+/// all of the same width. Terminal characters are about twice as tall as they are wide,
+/// so a glyph is about twice as wide as it is tall.
+/// If there is not enough space, returns the `mark` as a single character.
+pub fn glyph(mark: Mark, max_size: Vec2, charset: Charset) -> Vec<String> {
+    // the smallest height at which the shapes are recognizable
+    let min_height = 3;
+    let height = max_size.y.min((max_size.x + 1) / 2);
+    if height < min_height {
+        return vec![mark.to_string()];
+    }
+    let width = 2 * height - 1;
+    let pixel = match charset {
+        Charset::Unicode => '█',
+        Charset::Ascii => mark.to_string().chars().next().unwrap(),
+    };
+    (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|col| {
+                    let is_set = match mark {
+                        Mark::X => x_pixel(row, col, height, width),
+                        Mark::O => o_pixel(row, col, height, width),
+                    };
+                    if is_set {
+                        pixel
+                    } else {
+                        ' '
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Two diagonals, each two pixels wide.
+fn x_pixel(row: usize, col: usize, height: usize, width: usize) -> bool {
+    let left = (row * (width - 2) + (height - 1) / 2) / (height - 1);
+    let right = width - 2 - left;
+    (left..=left + 1).contains(&col) || (right..=right + 1).contains(&col)
+}
+
+/// An elliptic ring, two pixels wide horizontally and one pixel wide vertically.
+#[allow(clippy::cast_precision_loss)]
+fn o_pixel(row: usize, col: usize, height: usize, width: usize) -> bool {
+    let (dx, dy) = (
+        col as f64 + 0.5 - width as f64 / 2.0,
+        row as f64 + 0.5 - height as f64 / 2.0,
+    );
+    let (outer_x, outer_y) = (width as f64 / 2.0, height as f64 / 2.0);
+    let (inner_x, inner_y) = (outer_x - 2.0, outer_y - 1.0);
+    let within = |semi_x: f64, semi_y: f64| (dx / semi_x).powi(2) + (dy / semi_y).powi(2) <= 1.0;
+    within(outer_x, outer_y) && !within(inner_x, inner_y)
+}
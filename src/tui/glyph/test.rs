@@ -0,0 +1,74 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod glyph {
+    use cursive::Vec2;
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::{
+        game::Mark,
+        tui::glyph::{glyph, Charset},
+    };
+
+    #[test_case(Mark::X, (8, 9), Charset::Unicode, &[
+        "██   ██",
+        "  ███  ",
+        "  ███  ",
+        "██   ██",
+    ])]
+    #[test_case(Mark::O, (8, 9), Charset::Unicode, &[
+        " █████ ",
+        "██   ██",
+        "██   ██",
+        " █████ ",
+    ])]
+    #[test_case(Mark::X, (20, 7), Charset::Unicode, &[
+        "██         ██",
+        "  ██     ██  ",
+        "    ██ ██    ",
+        "     ███     ",
+        "    ██ ██    ",
+        "  ██     ██  ",
+        "██         ██",
+    ])]
+    #[test_case(Mark::O, (13, 30), Charset::Ascii, &[
+        "   OOOOOOO   ",
+        " OOO     OOO ",
+        "OO         OO",
+        "OO         OO",
+        "OO         OO",
+        " OOO     OOO ",
+        "   OOOOOOO   ",
+    ])]
+    #[test_case(Mark::X, (4, 9), Charset::Ascii, &["X"])]
+    #[test_case(Mark::O, (20, 2), Charset::Unicode, &["O"])]
+    fn fits(mark: Mark, max_size: (usize, usize), charset: Charset, expected: &[&str]) {
+        assert_eq!(glyph(mark, Vec2::from(max_size), charset), expected);
+    }
+}
+
+mod Charset {
+    use std::ffi::OsString;
+
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::tui::glyph::Charset;
+
+    #[test_case(&[], Charset::Ascii)]
+    #[test_case(&[("LANG", "en_US.UTF-8")], Charset::Unicode)]
+    #[test_case(&[("LANG", "C.utf8")], Charset::Unicode)]
+    #[test_case(&[("LANG", "C")], Charset::Ascii)]
+    #[test_case(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")], Charset::Ascii)]
+    #[test_case(&[("LC_ALL", ""), ("LC_CTYPE", "en_US.UTF-8"), ("LANG", "C")], Charset::Unicode)]
+    fn detect(env: &[(&str, &str)], expected: Charset) {
+        assert_eq!(
+            Charset::detect(&|name| env
+                .iter()
+                .find(|(env_name, _)| *env_name == name)
+                .map(|(_, v)| OsString::from(v))),
+            expected
+        );
+    }
+}
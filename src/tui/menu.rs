@@ -1,21 +1,38 @@
 use alloc::rc::Rc;
-use core::fmt::Display;
+use core::{
+    fmt::Display,
+    num::{NonZeroU32, ParseIntError},
+};
 
 use cursive::{
     utils::markup::StyledString,
     view::{IntoBoxedView, Nameable, Resizable},
-    views::{Dialog, EditView, LinearLayout, TextView},
+    views::{Dialog, EditView, LinearLayout, NamedView, TextView},
     Cursive,
 };
 
-use crate::{game::State, tui::util::MenuItemsStateSwitcher};
+use crate::{
+    game::State,
+    tui::{
+        util::{MenuItemSwitchState, MenuItemsStateSwitcher},
+        view::GameView,
+    },
+};
 
 pub mod ai_vs_ai;
+pub mod leaderboard;
+pub mod saved_game;
+pub mod stats;
+pub mod summary;
+pub mod vs_ai;
 
 pub const EXIT_LABEL: &str = "Exit";
+pub const PAUSE_LABEL: &str = "Pause/resume";
 pub const STOP_LABEL: &str = "TODO Stop/disconnect";
 const ROUNDS_GAME_OPTION_VIEW_ID: &str = "ROUNDS_GAME_OPTION_VIEW_ID";
-const ROUNDS_GAME_OPTION_NAME: &str = "TODO Number of rounds";
+const ROUNDS_GAME_OPTION_NAME: &str = "Number of rounds";
+const SEED_GAME_OPTION_VIEW_ID: &str = "SEED_GAME_OPTION_VIEW_ID";
+const SEED_GAME_OPTION_NAME: &str = "Seed (random if empty)";
 
 pub fn callback<O>(
     (game_opts_dlg_title, game_opts_dlg_content): (impl Into<StyledString>, impl IntoBoxedView),
@@ -50,11 +67,120 @@ pub fn callback<O>(
     );
 }
 
+/// Shows the game, disabling the pause menu item for the duration of the game
+/// if the game is not [pausable](GameView::is_pausable).
+pub fn show_game(tui: &mut Cursive, game_view: GameView) {
+    set_pause_state(tui, game_view.is_pausable().into());
+    tui.screen_mut()
+        .add_fullscreen_layer(game_view.with_name(GameView::ID));
+}
+
+/// Closes the game shown by [`show_game`].
+pub fn close_game(tui: &mut Cursive) {
+    drop(tui.pop_layer());
+    set_pause_state(tui, MenuItemSwitchState::Enabled);
+}
+
+fn set_pause_state(tui: &mut Cursive, state: MenuItemSwitchState) {
+    MenuItemsStateSwitcher::new().switch(tui.menubar(), |lbl| {
+        if lbl.starts_with(PAUSE_LABEL) {
+            Some(state)
+        } else {
+            None
+        }
+    });
+}
+
+pub fn toggle_paused(tui: &mut Cursive) {
+    match tui.call_on_name(GameView::ID, |view: &mut NamedView<GameView>| {
+        view.get_mut().toggle_paused()
+    }) {
+        Some(Ok(())) => {}
+        Some(Err(e)) => show_info_dlg(tui, format!("{e}.")),
+        None => show_info_dlg(tui, "There is no game to pause."),
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct GameOpts {
+    rounds: NonZeroU32,
+    seed: Option<u64>,
+}
+
+impl GameOpts {
+    pub fn new(rounds: NonZeroU32, seed: Option<u64>) -> Self {
+        Self { rounds, seed }
+    }
+
+    pub fn rounds(self) -> u32 {
+        self.rounds.get()
+    }
+
+    /// [`None`] if a user has not specified a seed.
+    pub fn seed(self) -> Option<u64> {
+        self.seed
+    }
+
+    /// The same options with the seed to be picked randomly.
+    #[must_use]
+    pub fn without_seed(self) -> Self {
+        Self { seed: None, ..self }
+    }
+
+    fn from_parsed(
+        (rounds_option_name, rounds): (&str, Result<NonZeroU32, ParseIntError>),
+        (seed_option_name, seed): (&str, Result<Option<u64>, ParseIntError>),
+        tui: &mut Cursive,
+    ) -> Option<Self> {
+        match (rounds, seed) {
+            (Ok(rounds), Ok(seed)) => Some(Self { rounds, seed }),
+            (Err(err), _) => {
+                show_game_option_err_dlg(tui, rounds_option_name, err);
+                None
+            }
+            (_, Err(err)) => {
+                show_game_option_err_dlg(tui, seed_option_name, err);
+                None
+            }
+        }
+    }
+}
+
+/// The `seed` is the default content of the seed option.
+pub fn game_opts_dlg_content(seed: Option<u64>) -> LinearLayout {
+    LinearLayout::vertical()
+        .child(rounds_game_option_layout())
+        .child(seed_game_option_layout(seed))
+}
+
+pub fn game_opts(tui: &mut Cursive) -> Option<GameOpts> {
+    let mut content = |view_id: &str| {
+        tui.call_on_name(view_id, |view: &mut NamedView<EditView>| {
+            view.get_mut().get_content().trim().to_owned()
+        })
+        .unwrap()
+    };
+    let rounds = content(ROUNDS_GAME_OPTION_VIEW_ID).parse::<NonZeroU32>();
+    let seed = Some(content(SEED_GAME_OPTION_VIEW_ID))
+        .filter(|seed| !seed.is_empty())
+        .map(|seed| seed.parse::<u64>())
+        .transpose();
+    GameOpts::from_parsed(
+        (ROUNDS_GAME_OPTION_NAME, rounds),
+        (SEED_GAME_OPTION_NAME, seed),
+        tui,
+    )
+}
+
 fn show_game_option_err_dlg(tui: &mut Cursive, opt_name: impl Display, err: impl Display) {
+    show_info_dlg(tui, format!("{opt_name}: {err}."));
+}
+
+fn show_info_dlg(tui: &mut Cursive, txt: impl Into<StyledString>) {
     tui.screen_mut().add_layer(
         Dialog::new()
             .title("Info")
-            .content(TextView::new(format!("{opt_name}: {err}.")))
+            .content(TextView::new(txt))
             .button("OK", |tui| {
                 tui.pop_layer();
             }),
@@ -62,14 +188,30 @@ fn show_game_option_err_dlg(tui: &mut Cursive, opt_name: impl Display, err: impl
 }
 
 fn rounds_game_option_layout() -> LinearLayout {
-    let max_content_width = 15;
+    game_option_layout(
+        ROUNDS_GAME_OPTION_NAME,
+        ROUNDS_GAME_OPTION_VIEW_ID,
+        State::DEFAULT_ROUNDS.to_string(),
+    )
+}
+
+fn seed_game_option_layout(seed: Option<u64>) -> LinearLayout {
+    game_option_layout(
+        SEED_GAME_OPTION_NAME,
+        SEED_GAME_OPTION_VIEW_ID,
+        seed.map(|seed| seed.to_string()).unwrap_or_default(),
+    )
+}
+
+fn game_option_layout(name: &str, view_id: &str, content: String) -> LinearLayout {
+    let max_content_width = 20;
     LinearLayout::horizontal()
-        .child(TextView::new(format!("{ROUNDS_GAME_OPTION_NAME}: ")))
+        .child(TextView::new(format!("{name}: ")))
         .child(
             EditView::new()
                 .max_content_width(max_content_width)
-                .content(State::DEFAULT_ROUNDS.to_string())
-                .with_name(ROUNDS_GAME_OPTION_VIEW_ID)
+                .content(content)
+                .with_name(view_id)
                 .min_width(max_content_width + 1),
         )
 }
@@ -0,0 +1,58 @@
+use core::fmt::Write;
+use std::env;
+
+use cursive::{
+    view::{Resizable, Scrollable},
+    views::{Dialog, TextView},
+    Cursive,
+};
+
+use crate::{
+    rating::Ratings,
+    tui::{menu::show_info_dlg, util::MenuItemsStateSwitcher},
+};
+
+/// Shows the players and AIs ordered by their ratings.
+pub fn show(tui: &mut Cursive) {
+    let Some(path) = Ratings::path(&|name| env::var_os(name)) else {
+        show_info_dlg(
+            tui,
+            "Cannot locate ratings, neither $XDG_DATA_HOME nor $HOME is specified.",
+        );
+        return;
+    };
+    let ratings = match Ratings::read(&path) {
+        Ok(ratings) => ratings,
+        Err(e) => {
+            show_info_dlg(tui, format!("Failed to read {}: {e}.", path.display()));
+            return;
+        }
+    };
+    let leaderboard = ratings.leaderboard();
+    if leaderboard.is_empty() {
+        show_info_dlg(tui, "Nobody has been rated yet.");
+        return;
+    }
+    // the number of rated games or rounds, depending on the rating period
+    let mut txt = format!("{:>3}  {:<24} {:>6} {:>7}", "#", "Name", "Rating", "Rated");
+    for (idx, (name, rating)) in leaderboard.into_iter().enumerate() {
+        write!(
+            txt,
+            "\n{:>3}. {name:<24} {:>6.0} {:>7}",
+            idx + 1,
+            rating.value,
+            rating.updates
+        )
+        .unwrap();
+    }
+    let menu_switcher = MenuItemsStateSwitcher::with_all_disabled(tui.menubar());
+    tui.screen_mut().add_layer(
+        Dialog::new()
+            .title("Leaderboard")
+            .content(TextView::new(txt).scrollable().max_height(16))
+            .button("Close", move |tui| {
+                drop(tui.pop_layer());
+                menu_switcher.restore(tui.menubar());
+            }),
+    );
+}
@@ -0,0 +1,113 @@
+use alloc::rc::Rc;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use cursive::{
+    view::{Resizable, Scrollable},
+    views::{Dialog, NamedView, SelectView},
+    Cursive,
+};
+
+use crate::{
+    save::SavedGame,
+    tui::{
+        menu::{ai_vs_ai, show_info_dlg},
+        util::MenuItemsStateSwitcher,
+        view::GameView,
+    },
+};
+
+pub fn save(tui: &mut Cursive) {
+    let Some(saved_game) = tui.call_on_name(GameView::ID, |view: &mut NamedView<GameView>| {
+        view.get_mut().saved_game()
+    }) else {
+        show_info_dlg(tui, "There is no game to save.");
+        return;
+    };
+    match saved_game {
+        Ok(saved_game) => write(tui, &saved_game),
+        Err(e) => show_info_dlg(tui, format!("{e}.")),
+    }
+}
+
+pub fn write(tui: &mut Cursive, saved_game: &SavedGame) {
+    let Some(dir) = dir(tui) else {
+        return;
+    };
+    match saved_game.write(&dir) {
+        Ok(path) => show_info_dlg(tui, format!("Saved to {}.", path.display())),
+        Err(e) => show_info_dlg(tui, format!("Failed to save to {}: {e}.", dir.display())),
+    }
+}
+
+pub fn load(tui: &mut Cursive) {
+    let Some(dir) = dir(tui) else {
+        return;
+    };
+    let paths = match SavedGame::list(&dir) {
+        Ok(paths) if paths.is_empty() => {
+            show_info_dlg(
+                tui,
+                format!("There are no saved games in {}.", dir.display()),
+            );
+            return;
+        }
+        Ok(paths) => paths,
+        Err(e) => {
+            show_info_dlg(tui, format!("Failed to list {}: {e}.", dir.display()));
+            return;
+        }
+    };
+    let menu_switcher = Rc::new(MenuItemsStateSwitcher::with_all_disabled(tui.menubar()));
+    let mut select = SelectView::new();
+    for path in paths {
+        select.add_item(label(&path), path);
+    }
+    select.set_on_submit({
+        let menu_switcher = Rc::clone(&menu_switcher);
+        move |tui, path: &PathBuf| match SavedGame::read(path) {
+            Ok(saved_game) => {
+                drop(tui.pop_layer());
+                menu_switcher.restore(tui.menubar());
+                ai_vs_ai::replay(saved_game, tui);
+            }
+            Err(e) => show_info_dlg(tui, format!("Failed to load {}: {e}.", path.display())),
+        }
+    });
+    tui.screen_mut().add_layer(
+        Dialog::new()
+            .title("Load a saved game")
+            .content(select.scrollable().max_height(10))
+            .button("Cancel", move |tui| {
+                drop(tui.pop_layer());
+                menu_switcher.restore(tui.menubar());
+            }),
+    );
+}
+
+fn dir(tui: &mut Cursive) -> Option<PathBuf> {
+    let dir = SavedGame::dir(&|name| env::var_os(name));
+    if dir.is_none() {
+        show_info_dlg(
+            tui,
+            "Cannot locate saved games, neither $XDG_DATA_HOME nor $HOME is specified.",
+        );
+    }
+    dir
+}
+
+fn label(path: &Path) -> String {
+    let name = path
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match SavedGame::read(path) {
+        Ok(saved_game) => format!(
+            "{name}: {} vs. {}, {} rounds, seed {}",
+            saved_game.players[0], saved_game.players[1], saved_game.rounds, saved_game.seed
+        ),
+        Err(_) => format!("{name}: unreadable"),
+    }
+}
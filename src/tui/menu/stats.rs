@@ -0,0 +1,235 @@
+use alloc::rc::Rc;
+use core::{cell::RefCell, fmt::Write};
+use std::{env, path::Path};
+
+use cursive::{
+    view::{Nameable, Resizable, Scrollable},
+    views::{Dialog, EditView, LinearLayout, NamedView, RadioGroup, SelectView, TextView},
+    Cursive,
+};
+
+use crate::{
+    game::Mark,
+    profile::{ExportFormat, Profile, Profiles},
+    tui::{menu::show_info_dlg, util::MenuItemsStateSwitcher},
+};
+
+const PROFILES_VIEW_ID: &str = "PROFILES_VIEW_ID";
+const STATS_VIEW_ID: &str = "STATS_VIEW_ID";
+const NAME_VIEW_ID: &str = "NAME_VIEW_ID";
+
+type SharedProfiles = Rc<RefCell<Profiles>>;
+
+/// Shows the statistics of the local player profiles, which allows managing and exporting them.
+pub fn show(tui: &mut Cursive) {
+    let Some(path) = Profiles::path(&|name| env::var_os(name)) else {
+        show_info_dlg(
+            tui,
+            "Cannot locate profiles, neither $XDG_DATA_HOME nor $HOME is specified.",
+        );
+        return;
+    };
+    let profiles = match Profiles::read(&path) {
+        Ok(profiles) => Rc::new(RefCell::new(profiles)),
+        Err(e) => {
+            show_info_dlg(tui, format!("Failed to read {}: {e}.", path.display()));
+            return;
+        }
+    };
+    let menu_switcher = Rc::new(MenuItemsStateSwitcher::with_all_disabled(tui.menubar()));
+    let mut select = SelectView::<String>::new().on_select({
+        let profiles = Rc::clone(&profiles);
+        move |tui, name| show_stats(tui, &profiles.borrow(), name)
+    });
+    for profile in &profiles.borrow().profiles {
+        select.add_item_str(&profile.name);
+    }
+    let selected = profiles.borrow().active.clone();
+    if let Some(idx) = selected.as_ref().and_then(|name| {
+        select
+            .iter()
+            .position(|(_, profile_name)| profile_name == name)
+    }) {
+        select.set_selection(idx);
+    }
+    let stats = selected
+        .and_then(|name| {
+            profiles
+                .borrow()
+                .get(&name)
+                .map(|profile| stats(&profiles.borrow(), profile))
+        })
+        .unwrap_or_else(|| "There are no profiles.".to_owned());
+    tui.screen_mut().add_layer(
+        Dialog::new()
+            .title("Statistics")
+            .content(
+                LinearLayout::horizontal()
+                    .child(
+                        select
+                            .with_name(PROFILES_VIEW_ID)
+                            .scrollable()
+                            .min_width(16)
+                            .max_height(12),
+                    )
+                    .child(TextView::new(stats).with_name(STATS_VIEW_ID).min_width(48)),
+            )
+            .button("New profile", {
+                let profiles = Rc::clone(&profiles);
+                let path = path.clone();
+                move |tui| show_new_profile_dlg(tui, &profiles, &path)
+            })
+            .button("Make active", {
+                let profiles = Rc::clone(&profiles);
+                let path = path.clone();
+                move |tui| make_active(tui, &profiles, &path)
+            })
+            .button("Export CSV", {
+                let profiles = Rc::clone(&profiles);
+                move |tui| export(tui, &profiles.borrow(), ExportFormat::Csv)
+            })
+            .button("Export JSON", {
+                let profiles = Rc::clone(&profiles);
+                move |tui| export(tui, &profiles.borrow(), ExportFormat::Json)
+            })
+            .button("Close", move |tui| {
+                drop(tui.pop_layer());
+                menu_switcher.restore(tui.menubar());
+            }),
+    );
+}
+
+fn selected_name(tui: &mut Cursive) -> Option<String> {
+    tui.call_on_name(
+        PROFILES_VIEW_ID,
+        |view: &mut NamedView<SelectView<String>>| {
+            view.get_mut().selection().map(|name| (*name).clone())
+        },
+    )
+    .flatten()
+}
+
+fn show_stats(tui: &mut Cursive, profiles: &Profiles, name: &str) {
+    if let Some(profile) = profiles.get(name) {
+        let stats = stats(profiles, profile);
+        tui.call_on_name(STATS_VIEW_ID, |view: &mut NamedView<TextView>| {
+            view.get_mut().set_content(stats);
+        });
+    }
+}
+
+fn stats(profiles: &Profiles, profile: &Profile) -> String {
+    let mut txt = format!(
+        "{}{}\nPreferred mark: {}.\nGames: {}, rounds: {}.\n",
+        profile.name,
+        if profiles.active.as_ref() == Some(&profile.name) {
+            " (active)"
+        } else {
+            ""
+        },
+        profile.preferred_mark,
+        profile.games(),
+        profile.rounds()
+    );
+    let streaks = &profile.streaks;
+    if let Some((result, len)) = streaks.current {
+        writeln!(txt, "Current streak: {len} {result}.").unwrap();
+    }
+    writeln!(
+        txt,
+        "Longest streaks: {} wins, {} losses.",
+        streaks.longest_wins, streaks.longest_losses
+    )
+    .unwrap();
+    for record in &profile.records {
+        write!(
+            txt,
+            "\nAgainst {}:\n  {} games, {} rounds,\n  {} wins, {} draws, {} losses.",
+            record.opponent, record.games, record.rounds, record.wins, record.draws, record.losses
+        )
+        .unwrap();
+    }
+    txt
+}
+
+fn make_active(tui: &mut Cursive, profiles: &SharedProfiles, path: &Path) {
+    let Some(name) = selected_name(tui) else {
+        show_info_dlg(tui, "There is no profile to make active.");
+        return;
+    };
+    profiles.borrow_mut().active = Some(name.clone());
+    if let Err(e) = profiles.borrow().write(path) {
+        show_info_dlg(tui, format!("Failed to write {}: {e}.", path.display()));
+    }
+    show_stats(tui, &profiles.borrow(), &name);
+}
+
+fn show_new_profile_dlg(tui: &mut Cursive, profiles: &SharedProfiles, path: &Path) {
+    let mut marks = RadioGroup::new();
+    let content = LinearLayout::vertical()
+        .child(
+            LinearLayout::horizontal()
+                .child(TextView::new("Name: "))
+                .child(EditView::new().with_name(NAME_VIEW_ID).min_width(21)),
+        )
+        .child(
+            LinearLayout::horizontal()
+                .child(TextView::new("Preferred mark: "))
+                .child(marks.button(Mark::X, "X"))
+                .child(TextView::new(" "))
+                .child(marks.button(Mark::O, "O")),
+        );
+    let profiles = Rc::clone(profiles);
+    let path = path.to_owned();
+    tui.screen_mut().add_layer(
+        Dialog::new()
+            .title("New profile")
+            .content(content)
+            .button("Create", move |tui| {
+                let name = tui
+                    .call_on_name(NAME_VIEW_ID, |view: &mut NamedView<EditView>| {
+                        view.get_mut().get_content()
+                    })
+                    .unwrap();
+                let added = profiles.borrow_mut().add(&name, *marks.selection());
+                if let Err(e) = added {
+                    show_info_dlg(tui, e);
+                    return;
+                }
+                drop(tui.pop_layer());
+                let name = profiles.borrow().active.clone().unwrap();
+                let on_select = tui
+                    .call_on_name(
+                        PROFILES_VIEW_ID,
+                        |view: &mut NamedView<SelectView<String>>| {
+                            let mut view = view.get_mut();
+                            let idx = view.len();
+                            view.add_item_str(&name);
+                            view.set_selection(idx)
+                        },
+                    )
+                    .unwrap();
+                on_select(tui);
+                if let Err(e) = profiles.borrow().write(&path) {
+                    show_info_dlg(tui, format!("Failed to write {}: {e}.", path.display()));
+                }
+            })
+            .button("Cancel", |tui| {
+                drop(tui.pop_layer());
+            }),
+    );
+}
+
+fn export(tui: &mut Cursive, profiles: &Profiles, format: ExportFormat) {
+    let Some(dir) = Profiles::export_dir(&|name| env::var_os(name)) else {
+        show_info_dlg(
+            tui,
+            "Cannot locate exports, neither $XDG_DATA_HOME nor $HOME is specified.",
+        );
+        return;
+    };
+    match profiles.export(&dir, format) {
+        Ok(path) => show_info_dlg(tui, format!("Exported to {}.", path.display())),
+        Err(e) => show_info_dlg(tui, format!("Failed to export to {}: {e}.", dir.display())),
+    }
+}
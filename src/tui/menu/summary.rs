@@ -0,0 +1,106 @@
+use alloc::rc::Rc;
+use core::fmt::Write;
+
+use cursive::{
+    views::{Dialog, TextView},
+    Cursive,
+};
+
+use crate::{
+    game::{Outcome, State},
+    save::SavedGame,
+    tui::{menu, menu::saved_game, util::MenuItemsStateSwitcher, view::Rematch},
+};
+
+/// Shows the summary of the game that is over, followed by the `note`, if any.
+/// The "Save replay" button is only shown if there is a `saved_game`,
+/// and the "Rematch" button is only shown if there is a `rematch`,
+/// which is called after the game has been closed.
+pub fn show(
+    tui: &mut Cursive,
+    (game_state, note): (&State, Option<String>),
+    (saved_game, rematch): (Option<SavedGame>, Option<Rematch>),
+) {
+    let menu_switcher = Rc::new(MenuItemsStateSwitcher::with_all_disabled(tui.menubar()));
+    let close = {
+        let menu_switcher = Rc::clone(&menu_switcher);
+        move |tui: &mut Cursive| {
+            // the dialog
+            drop(tui.pop_layer());
+            menu_switcher.restore(tui.menubar());
+            menu::close_game(tui);
+        }
+    };
+    let mut dlg = Dialog::new()
+        .title("Game over")
+        .content(TextView::new(match note {
+            None => summary(game_state),
+            Some(note) => format!("{}\n\n{note}", summary(game_state)),
+        }));
+    if let Some(rematch) = rematch {
+        let close = close.clone();
+        dlg.add_button("Rematch", move |tui| {
+            close(tui);
+            rematch(tui);
+        });
+    }
+    if let Some(saved_game) = saved_game {
+        dlg.add_button("Save replay", move |tui| {
+            saved_game::write(tui, &saved_game);
+        });
+    }
+    tui.screen_mut().add_layer(dlg.button("Main menu", close));
+}
+
+fn summary(game_state: &State) -> String {
+    let [p0, p1] = &game_state.players;
+    let mut txt = format!(
+        "Final score: {} {} - {} {}.\n\n",
+        p0.mark(),
+        p0.wins,
+        p1.mark(),
+        p1.wins
+    );
+    for (idx, round_log) in game_state.log.iter().enumerate() {
+        let result = match round_log.outcome {
+            Some(Outcome::Win(player_id, line)) => {
+                format!("{} won by occupying the {line}", player_id.mark())
+            }
+            Some(Outcome::Surrender(player_id)) => format!("{} surrendered", player_id.mark()),
+            Some(Outcome::Draw) => "draw".to_owned(),
+            None => "not finished".to_owned(),
+        };
+        writeln!(
+            txt,
+            "Round {}: {result}, {} moves, {:.1} s.",
+            idx + 1,
+            round_log.moves.len(),
+            round_log.duration.as_secs_f64()
+        )
+        .unwrap();
+    }
+    let longest_think = game_state
+        .log
+        .iter()
+        .enumerate()
+        .flat_map(|(round_idx, round_log)| {
+            round_log
+                .moves
+                .iter()
+                .enumerate()
+                .map(move |(move_idx, mv)| (round_idx, move_idx, mv))
+        })
+        .max_by_key(|(_, _, mv)| mv.think_time);
+    if let Some((round_idx, move_idx, mv)) = longest_think {
+        write!(
+            txt,
+            "\nLongest think: {}, {:.1} s (round {}, move {}).",
+            mv.player_id.mark(),
+            mv.think_time.as_secs_f64(),
+            round_idx + 1,
+            move_idx + 1
+        )
+        .unwrap();
+    }
+    txt
+}
@@ -0,0 +1,290 @@
+use alloc::rc::Rc;
+use core::fmt::Write;
+use std::{env, path::PathBuf};
+
+use cursive::{
+    view::Nameable,
+    views::{Checkbox, LinearLayout, NamedView, SelectView, TextView},
+    Cursive,
+};
+
+use crate::{
+    ai,
+    ai::{book::BookOpts, mcts::MctsOpts, menace::Menace, personality::Personality, AiKind},
+    game::{
+        DefaultActionQueue,
+        LocalPlayerType::{Ai, Human},
+        Logic, Mark, Player, PlayerId,
+        PlayerType::Local,
+        State, World,
+    },
+    profile::{Opponent, Profiles},
+    rating::{RatingPeriod, Ratings},
+    save::{SavedBook, SavedGame, SavedPlayer},
+    tui::{
+        menu,
+        menu::{show_info_dlg, GameOpts},
+        view::{Assistance, GameView},
+    },
+};
+
+const AI_GAME_OPTION_VIEW_ID: &str = "AI_GAME_OPTION_VIEW_ID";
+const HINTS_GAME_OPTION_VIEW_ID: &str = "HINTS_GAME_OPTION_VIEW_ID";
+const THREATS_GAME_OPTION_VIEW_ID: &str = "THREATS_GAME_OPTION_VIEW_ID";
+
+#[derive(Debug, Clone)]
+pub struct VsAiOpts {
+    game: GameOpts,
+    /// Is never [`Opponent::Human`].
+    opponent: Opponent,
+    assistance: Assistance,
+    rating_period: RatingPeriod,
+    /// The opening book the [`Self::opponent`] consults first.
+    book: Option<BookOpts>,
+}
+
+impl VsAiOpts {
+    /// The options of a game against the `ai` without [`Assistance`].
+    pub fn new(
+        game: GameOpts,
+        ai: AiKind,
+        rating_period: RatingPeriod,
+        book: Option<BookOpts>,
+    ) -> Self {
+        Self {
+            game,
+            opponent: Opponent::Ai(ai),
+            assistance: Assistance::default(),
+            rating_period,
+            book,
+        }
+    }
+}
+
+/// The `seed` is the default content of the seed option.
+/// The AI option lists the [`Personality`]s, and [`MenaceAi`] of each epoch it has been trained for.
+pub fn opts_dlg_content(seed: Option<u64>) -> LinearLayout {
+    let mut ais = SelectView::new().popup();
+    for kind in AiKind::NAMES {
+        let kind = kind.parse::<AiKind>().expect("the name must be valid");
+        ais.add_item(kind.to_string(), Opponent::Ai(kind));
+    }
+    for personality in Personality::ALL {
+        ais.add_item(personality.to_string(), Opponent::Personality(personality));
+    }
+    // the errors are reported only when MENACE is chosen
+    let epochs = Menace::dir(&|name| env::var_os(name))
+        .and_then(|dir| Menace::epochs(&dir).ok())
+        .unwrap_or_default();
+    for epoch in epochs {
+        ais.add_item(format!("MENACE, epoch {epoch}"), Opponent::Menace(epoch));
+    }
+    menu::game_opts_dlg_content(seed)
+        .child(
+            LinearLayout::horizontal()
+                .child(TextView::new("AI: "))
+                .child(ais.with_name(AI_GAME_OPTION_VIEW_ID)),
+        )
+        .child(checkbox_option_layout("Hints", HINTS_GAME_OPTION_VIEW_ID))
+        .child(checkbox_option_layout(
+            "Highlight threats",
+            THREATS_GAME_OPTION_VIEW_ID,
+        ))
+}
+
+/// The `book` is what the AI consults first, if any.
+pub fn opts(
+    tui: &mut Cursive,
+    rating_period: RatingPeriod,
+    book: Option<BookOpts>,
+) -> Option<VsAiOpts> {
+    let game = menu::game_opts(tui)?;
+    let opponent = tui
+        .call_on_name(
+            AI_GAME_OPTION_VIEW_ID,
+            |view: &mut NamedView<SelectView<Opponent>>| view.get_mut().selection(),
+        )
+        .unwrap()
+        .map_or(Opponent::Ai(AiKind::Random), |opponent| *opponent);
+    let mut is_checked = |view_id: &str| {
+        tui.call_on_name(view_id, |view: &mut NamedView<Checkbox>| {
+            view.get_mut().is_checked()
+        })
+        .unwrap()
+    };
+    Some(VsAiOpts {
+        game,
+        opponent,
+        assistance: Assistance {
+            hints: is_checked(HINTS_GAME_OPTION_VIEW_ID),
+            threats: is_checked(THREATS_GAME_OPTION_VIEW_ID),
+        },
+        rating_period,
+        book,
+    })
+}
+
+/// The local human player plays with the preferred mark of the active [`Profile`](crate::profile::Profile),
+/// if there is one, and the results of the game are recorded in it and rated.
+pub fn start(opts: VsAiOpts, tui: &mut Cursive) {
+    let seed = opts.game.seed().unwrap_or_else(ai::time_based_seed);
+    let env = |name: &str| env::var_os(name);
+    let paths = Profiles::path(&env).zip(Ratings::path(&env));
+    let profiles = paths
+        .as_ref()
+        .map(|(profiles_path, _)| Profiles::read(profiles_path))
+        .transpose();
+    let active_profile = profiles
+        .as_ref()
+        .ok()
+        .and_then(Option::as_ref)
+        .and_then(Profiles::active)
+        .map(|profile| (profile.name.clone(), profile.preferred_mark));
+    let human_id = active_profile
+        .as_ref()
+        .map_or(Mark::X, |&(_, mark)| mark)
+        .player_id();
+    let ai_id = PlayerId::new(1 - human_id.idx);
+    let action_queues = [0, 1].map(|idx| Rc::new(DefaultActionQueue::new(PlayerId::new(idx))));
+    let players = [0, 1].map(|idx| {
+        let player_id = PlayerId::new(idx);
+        Player::new(
+            player_id,
+            if player_id == human_id {
+                Local(Human)
+            } else {
+                Local(Ai)
+            },
+        )
+    });
+    let mut saved_players = [SavedPlayer::HUMAN, SavedPlayer::HUMAN];
+    saved_players[ai_id.idx] = SavedPlayer {
+        kind: opts.opponent,
+        book: opts.book.as_ref().map(SavedBook::from),
+        mcts: MctsOpts::DEFAULT,
+    };
+    let saved_game = SavedGame {
+        seed,
+        rounds: opts.game.rounds(),
+        players: saved_players,
+        log: Vec::new(),
+    };
+    let human_act_queue = Rc::clone(&action_queues[human_id.idx]);
+    let ai = match saved_game.players[ai_id.idx].create_ai(
+        ai::player_seeds(seed)[ai_id.idx],
+        Rc::clone(&action_queues[ai_id.idx]),
+        opts.book.as_ref(),
+    ) {
+        Ok(ai) => ai,
+        Err(e) => {
+            show_info_dlg(tui, e);
+            return;
+        }
+    };
+    let game_world = World::new(
+        State::new(players, opts.game.rounds()),
+        Logic::new(action_queues),
+        vec![ai],
+    );
+    menu::show_game(
+        tui,
+        GameView::new(
+            game_world,
+            vec![human_act_queue],
+            opts.assistance,
+            seed,
+            Some(saved_game.clone()),
+        )
+        .with_player_title(
+            ai_id,
+            format!("{}: {}", ai_id.mark(), saved_game.players[ai_id.idx]),
+        )
+        .with_on_game_over({
+            let (opponent, rating_period) = (opts.opponent, opts.rating_period);
+            move |game_state| {
+                let (paths, (name, _)) = paths.as_ref().zip(active_profile.as_ref())?;
+                Some(record(
+                    paths,
+                    name,
+                    (game_state, human_id, opponent),
+                    rating_period,
+                ))
+            }
+        })
+        .with_rematch(move |tui| {
+            start(
+                VsAiOpts {
+                    game: opts.game.without_seed(),
+                    ..opts.clone()
+                },
+                tui,
+            );
+        }),
+    );
+    if let Err(e) = profiles {
+        show_info_dlg(
+            tui,
+            format!("Failed to read the profiles, the results will not be recorded: {e}."),
+        );
+    }
+}
+
+/// Records the results of the game in the profile named `name` and rates it against the `opponent`,
+/// the profiles and the ratings are stored at the `paths`.
+/// Returns the changes of the ratings, or the errors.
+fn record(
+    (profiles_path, ratings_path): &(PathBuf, PathBuf),
+    name: &str,
+    (game_state, player_id, opponent): (&State, PlayerId, Opponent),
+    rating_period: RatingPeriod,
+) -> String {
+    let mut note = String::new();
+    let recorded = Profiles::read(profiles_path).and_then(|mut profiles| {
+        if let Some(profile) = profiles.get_mut(name) {
+            profile.record_game(game_state, player_id, opponent);
+        }
+        profiles.write(profiles_path)
+    });
+    if let Err(e) = recorded {
+        writeln!(
+            note,
+            "Failed to record the results in {}: {e}.",
+            profiles_path.display()
+        )
+        .unwrap();
+    }
+    let opponent = opponent.to_string();
+    let mut names = [opponent.as_str(); 2];
+    names[player_id.idx] = name;
+    let rated = Ratings::read(ratings_path).and_then(|mut ratings| {
+        let changes = ratings.update(names, game_state, rating_period);
+        ratings.write(ratings_path)?;
+        Ok((ratings, changes))
+    });
+    match rated {
+        Ok((ratings, changes)) => {
+            let changes = [player_id.idx, 1 - player_id.idx].map(|idx| {
+                format!(
+                    "{} {:.0} ({:+.0})",
+                    names[idx],
+                    ratings.get(names[idx]).value,
+                    changes[idx]
+                )
+            });
+            write!(note, "Ratings: {}.", changes.join(", ")).unwrap();
+        }
+        Err(e) => write!(
+            note,
+            "Failed to rate the game in {}: {e}.",
+            ratings_path.display()
+        )
+        .unwrap(),
+    }
+    note
+}
+
+fn checkbox_option_layout(name: &str, view_id: &str) -> LinearLayout {
+    LinearLayout::horizontal()
+        .child(Checkbox::new().with_name(view_id))
+        .child(TextView::new(format!(" {name}")))
+}
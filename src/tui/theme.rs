@@ -0,0 +1,250 @@
+//! Color themes, see [`Themes`].
+
+use core::str::FromStr;
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use cursive::theme::{BaseColor, BorderStyle, Color, Palette, PaletteColor, Theme};
+use toml::{value::Table, Value};
+
+use crate::util::dirs;
+
+mod test;
+
+/// The built-in themes followed by the user-defined ones, if any.
+#[derive(Debug, Clone)]
+pub struct Themes {
+    v: Vec<(String, Theme)>,
+}
+
+impl Themes {
+    pub const DEFAULT: &'static str = "dark";
+    pub const BUILT_IN_NAMES: [&'static str; 4] =
+        [Self::DEFAULT, "light", "high-contrast", "monochrome"];
+    pub const FILE_NAME: &'static str = "themes.toml";
+    /// The custom [`Palette`] color of the background of highlighted marks.
+    pub const HIGHLIGHTED: &'static str = "highlighted";
+    /// The custom [`Palette`] color of highlighted marks.
+    pub const HIGHLIGHTED_TEXT: &'static str = "highlighted_text";
+    const BASE_KEY: &'static str = "base";
+    const SHADOW_KEY: &'static str = "shadow";
+    const BORDERS_KEY: &'static str = "borders";
+    const COLORS_KEY: &'static str = "colors";
+
+    pub fn built_in() -> Self {
+        Self {
+            v: Self::BUILT_IN_NAMES
+                .iter()
+                .map(|&name| (name.to_owned(), Self::built_in_theme(name)))
+                .collect(),
+        }
+    }
+
+    /// Returns the built-in themes and the ones defined in [`Self::FILE_NAME`]
+    /// inside [`dirs::config_dir()`], if the file exists.
+    ///
+    /// # Errors
+    ///
+    /// If the file exists but cannot be read, or is invalid.
+    pub fn load(env: &dyn Fn(&str) -> Option<OsString>) -> Result<Self, String> {
+        let themes = Self::built_in();
+        let Some(path) = Self::path(env) else {
+            return Ok(themes);
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => themes.with_user_defined(&content, &path),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(themes),
+            Err(e) => Err(format!("Failed to read {}: {e}.", path.display())),
+        }
+    }
+
+    pub fn path(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+        dirs::config_dir(env).map(|dir| dir.join(Self::FILE_NAME))
+    }
+
+    /// Adds the themes defined in `content`, which is read from `path`. For example,
+    ///
+    /// ```toml
+    /// [solarized]
+    /// # the theme to start from, "dark" if not specified
+    /// base = "light"
+    /// shadow = false
+    /// # "simple", "outset" or "none"
+    /// borders = "outset"
+    /// [solarized.colors]
+    /// background = "#fdf6e3"
+    /// highlighted = "light cyan"
+    /// ```
+    ///
+    /// The colors are the ones of [`PaletteColor`],
+    /// plus [`Self::HIGHLIGHTED`] and [`Self::HIGHLIGHTED_TEXT`].
+    ///
+    /// # Errors
+    ///
+    /// If `content` is invalid.
+    pub fn with_user_defined(mut self, content: &str, path: &Path) -> Result<Self, String> {
+        let err = |msg: &dyn core::fmt::Display| format!("Invalid {}: {msg}.", path.display());
+        let mut pending = toml::from_str::<Table>(content)
+            .map_err(|e| err(&e))?
+            .into_iter()
+            .collect::<Vec<_>>();
+        if let Some((name, _)) = pending.iter().find(|(name, _)| self.get(name).is_some()) {
+            return Err(err(&format!("the theme `{name}` is already defined")));
+        }
+        while !pending.is_empty() {
+            // a theme may be based on one defined after it,
+            // and if there is no theme with a known base, then the first one fails
+            let idx = pending
+                .iter()
+                .position(|(_, def)| {
+                    def.get(Self::BASE_KEY)
+                        .and_then(Value::as_str)
+                        .map_or(true, |base| self.get(base).is_some())
+                })
+                .unwrap_or(0);
+            let (name, def) = pending.remove(idx);
+            let theme = self
+                .user_defined_theme(&def)
+                .map_err(|msg| err(&format!("{msg} in the theme `{name}`")))?;
+            self.v.push((name, theme));
+        }
+        Ok(self)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Theme> {
+        self.v
+            .iter()
+            .find(|(theme_name, _)| theme_name == name)
+            .map(|(_, theme)| theme)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Theme)> {
+        self.v.iter().map(|(name, theme)| (name.as_str(), theme))
+    }
+
+    fn user_defined_theme(&self, def: &Value) -> Result<Theme, String> {
+        let def = def.as_table().ok_or("expected a table")?;
+        let base = match def.get(Self::BASE_KEY) {
+            None => Self::DEFAULT,
+            Some(base) => base.as_str().ok_or("expected a string `base`")?,
+        };
+        let mut theme = self
+            .get(base)
+            .ok_or_else(|| format!("unknown `base` theme `{base}`"))?
+            .clone();
+        for (key, value) in def {
+            match key.as_str() {
+                Self::BASE_KEY => {}
+                Self::SHADOW_KEY => {
+                    theme.shadow = value.as_bool().ok_or("expected a boolean `shadow`")?;
+                }
+                Self::BORDERS_KEY => {
+                    theme.borders = match value.as_str() {
+                        Some("simple") => BorderStyle::Simple,
+                        Some("outset") => BorderStyle::Outset,
+                        Some("none") => BorderStyle::None,
+                        _ => return Err("expected `borders` to be simple, outset or none".into()),
+                    };
+                }
+                Self::COLORS_KEY => {
+                    let colors = value.as_table().ok_or("expected a table `colors`")?;
+                    for (color_key, color) in colors {
+                        if PaletteColor::from_str(color_key).is_err()
+                            && color_key != Self::HIGHLIGHTED
+                            && color_key != Self::HIGHLIGHTED_TEXT
+                        {
+                            return Err(format!("unknown color `{color_key}`"));
+                        }
+                        let color = color
+                            .as_str()
+                            .and_then(Color::parse)
+                            .ok_or_else(|| format!("invalid value of the color `{color_key}`"))?;
+                        theme.palette.set_color(color_key, color);
+                    }
+                }
+                key => return Err(format!("unknown key `{key}`")),
+            }
+        }
+        Ok(theme)
+    }
+
+    fn built_in_theme(name: &str) -> Theme {
+        // `Color::Black` works weirdly, using `Color::RgbLowRes` instead
+        let dark_black = Color::RgbLowRes(0, 0, 0);
+        let grey = Color::RgbLowRes(3, 3, 3);
+        let light_white = Color::Light(BaseColor::White);
+        let mut theme = Theme {
+            shadow: true,
+            borders: BorderStyle::Simple,
+            palette: Palette::default(),
+        };
+        let palette = &mut theme.palette;
+        match name {
+            Self::DEFAULT => {
+                palette[PaletteColor::Background] = dark_black;
+                palette[PaletteColor::Shadow] = dark_black;
+                palette[PaletteColor::View] = light_white;
+                palette[PaletteColor::Primary] = dark_black;
+                palette[PaletteColor::Secondary] = grey;
+                palette[PaletteColor::Tertiary] = Color::Dark(BaseColor::Yellow);
+                palette[PaletteColor::TitlePrimary] = Color::Dark(BaseColor::Blue);
+                palette[PaletteColor::TitleSecondary] = Color::Light(BaseColor::Yellow);
+                palette[PaletteColor::Highlight] = Color::Light(BaseColor::Green);
+                palette[PaletteColor::HighlightText] = Color::Dark(BaseColor::Blue);
+                palette.set_color(Self::HIGHLIGHTED, Color::Dark(BaseColor::Yellow));
+                palette.set_color(Self::HIGHLIGHTED_TEXT, dark_black);
+            }
+            "light" => {
+                palette[PaletteColor::Background] = light_white;
+                palette[PaletteColor::Shadow] = grey;
+                palette[PaletteColor::View] = light_white;
+                palette[PaletteColor::Primary] = dark_black;
+                palette[PaletteColor::Secondary] = grey;
+                palette[PaletteColor::Tertiary] = Color::Light(BaseColor::Cyan);
+                palette[PaletteColor::TitlePrimary] = Color::Dark(BaseColor::Blue);
+                palette[PaletteColor::TitleSecondary] = Color::Dark(BaseColor::Magenta);
+                palette[PaletteColor::Highlight] = Color::Dark(BaseColor::Blue);
+                palette[PaletteColor::HighlightText] = light_white;
+                palette.set_color(Self::HIGHLIGHTED, Color::Light(BaseColor::Cyan));
+                palette.set_color(Self::HIGHLIGHTED_TEXT, dark_black);
+            }
+            "high-contrast" => {
+                theme.shadow = false;
+                palette[PaletteColor::Background] = dark_black;
+                palette[PaletteColor::Shadow] = dark_black;
+                palette[PaletteColor::View] = dark_black;
+                palette[PaletteColor::Primary] = light_white;
+                palette[PaletteColor::Secondary] = light_white;
+                palette[PaletteColor::Tertiary] = Color::Light(BaseColor::Yellow);
+                palette[PaletteColor::TitlePrimary] = Color::Light(BaseColor::Yellow);
+                palette[PaletteColor::TitleSecondary] = Color::Light(BaseColor::Cyan);
+                palette[PaletteColor::Highlight] = Color::Light(BaseColor::Yellow);
+                palette[PaletteColor::HighlightText] = dark_black;
+                palette.set_color(Self::HIGHLIGHTED, Color::Light(BaseColor::Yellow));
+                palette.set_color(Self::HIGHLIGHTED_TEXT, dark_black);
+            }
+            "monochrome" => {
+                // only the colors supported by 8-color terminals
+                let black = Color::Dark(BaseColor::Black);
+                let white = Color::Dark(BaseColor::White);
+                theme.shadow = false;
+                for color in PaletteColor::all() {
+                    palette[color] = white;
+                }
+                palette[PaletteColor::Background] = black;
+                palette[PaletteColor::Shadow] = black;
+                palette[PaletteColor::View] = black;
+                palette[PaletteColor::HighlightText] = black;
+                // inverted marks are the only way to highlight them
+                palette.set_color(Self::HIGHLIGHTED, white);
+                palette.set_color(Self::HIGHLIGHTED_TEXT, black);
+            }
+            name => panic!("unknown built-in theme `{name}`"),
+        }
+        palette[PaletteColor::HighlightInactive] = palette[PaletteColor::Highlight];
+        theme
+    }
+}
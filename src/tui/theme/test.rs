@@ -0,0 +1,95 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod Themes {
+    use std::path::Path;
+
+    use cursive::theme::{BaseColor, BorderStyle, Color, PaletteColor};
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::tui::theme::Themes;
+
+    fn with_user_defined(content: &str) -> Result<Themes, String> {
+        Themes::built_in().with_user_defined(content, Path::new("/themes.toml"))
+    }
+
+    #[test]
+    fn built_in() {
+        let themes = Themes::built_in();
+        assert_eq!(
+            themes.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            Themes::BUILT_IN_NAMES
+        );
+        for (_, theme) in themes.iter() {
+            assert!(theme.palette.custom(Themes::HIGHLIGHTED).is_some());
+            assert!(theme.palette.custom(Themes::HIGHLIGHTED_TEXT).is_some());
+        }
+    }
+
+    #[test]
+    fn with_user_defined__ok() {
+        let themes = with_user_defined(
+            r##"
+            [b]
+            base = "light"
+            borders = "outset"
+            [b.colors]
+            background = "#102030"
+            highlighted = "light cyan"
+            [a]
+            base = "b"
+            shadow = false
+            "##,
+        )
+        .unwrap();
+        let light = Themes::built_in().get("light").unwrap().clone();
+        let b = themes.get("b").unwrap();
+        assert_eq!(b.shadow, light.shadow);
+        assert_eq!(b.borders, BorderStyle::Outset);
+        assert_eq!(
+            b.palette[PaletteColor::Background],
+            Color::Rgb(0x10, 0x20, 0x30)
+        );
+        assert_eq!(
+            b.palette[PaletteColor::Primary],
+            light.palette[PaletteColor::Primary]
+        );
+        assert_eq!(
+            b.palette.custom(Themes::HIGHLIGHTED),
+            Some(&Color::Light(BaseColor::Cyan))
+        );
+        let a = themes.get("a").unwrap();
+        assert!(!a.shadow);
+        assert_eq!(a.borders, BorderStyle::Outset);
+        assert_eq!(
+            themes.iter().map(|(name, _)| name).last(),
+            Some("a"),
+            "user-defined themes must follow the built-in ones"
+        );
+    }
+
+    #[test_case("x = 1", "expected a table in the theme `x`")]
+    #[test_case("[dark]", "the theme `dark` is already defined")]
+    #[test_case("[x]\nbase = \"y\"", "unknown `base` theme `y` in the theme `x`")]
+    #[test_case(
+        "[x]\nbase = \"y\"\n[y]\nbase = \"x\"",
+        "unknown `base` theme `y` in the theme `x`"
+    )]
+    #[test_case("[x]\ny = 1", "unknown key `y` in the theme `x`")]
+    #[test_case(
+        "[x]\nborders = \"y\"",
+        "expected `borders` to be simple, outset or none in the theme `x`"
+    )]
+    #[test_case("[x.colors]\ny = \"red\"", "unknown color `y` in the theme `x`")]
+    #[test_case(
+        "[x.colors]\nview = \"y\"",
+        "invalid value of the color `view` in the theme `x`"
+    )]
+    fn with_user_defined__err(content: &str, expected: &str) {
+        assert_eq!(
+            with_user_defined(content).unwrap_err(),
+            format!("Invalid /themes.toml: {expected}.")
+        );
+    }
+}
@@ -3,14 +3,17 @@
 
 use alloc::{borrow::Cow, rc::Rc};
 use core::{cell::RefCell, time::Duration};
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+};
 
 use cursive::{
     align::HAlign,
     direction::{Direction, Orientation},
-    event::{AnyCb, MouseButton, MouseEvent},
-    traits::{Finder, Nameable, View},
-    view::{CannotFocus, Selector, ViewNotFound},
+    event::{AnyCb, Key, MouseButton, MouseEvent},
+    traits::{Finder, Nameable, Resizable, Scrollable, View},
+    view::{CannotFocus, ScrollStrategy, Selector, ViewNotFound},
     views::{
         Button, DummyView, EnableableView, NamedView, Panel, ResizedView, SliderView, TextContent,
     },
@@ -20,16 +23,23 @@ use unicode_width::UnicodeWidthStr;
 use xxhash_rust::xxh3::Xxh3Builder;
 use EventResult::Ignored;
 use LocalPlayerType::Ai;
-use PlayerType::_Remote;
+use PlayerType::Remote;
 
 use crate::{
     ai,
     game::{
-        Action, Cell,
+        Action, Cell, Mark, Outcome,
         Phase::{Beginning, Inround, Outround},
+        State,
+    },
+    save::SavedGame,
+    solver::{Position, Solver},
+    tui::{
+        fx::{highlighted_color_style, BlinkingAnimation},
+        glyph::{glyph, Charset},
+        menu,
     },
-    tui::fx::{BlinkingAnimation, HIGHLIGHTED_COLOR_STYLE},
-    util::time::AdvanceableClock,
+    util::time::{AdvanceableClock, RealClockSource, ScaledClockSource, TimeScale},
     ActionQueue, Cursive, DefaultActionQueue, Event, EventResult,
     EventResult::Consumed,
     Human, LinearLayout, Local, LocalPlayerType, Logic, Player, PlayerId, PlayerType, Printer,
@@ -39,19 +49,126 @@ use crate::{
 type GameWorld = Rc<RefCell<World<DefaultActionQueue>>>;
 type ActionQueues = Rc<HashMap<PlayerId, Rc<DefaultActionQueue>, Xxh3Builder>>;
 type Clock = Rc<RefCell<AdvanceableClock>>;
+/// The [`Cell`] selected via the keyboard.
+type Cursor = Rc<RefCell<Cell>>;
+type SharedHint = Rc<RefCell<Hint>>;
+/// The position shown on the board instead of the current one, if any.
+type SharedPreview = Rc<RefCell<Option<Preview>>>;
+/// Starts a new game when the current one is over.
+pub type Rematch = Rc<dyn Fn(&mut Cursive)>;
+/// Is called with the final [`State`] when the game is over,
+/// and returns the text to add to the summary of the game, if any.
+type OnGameOver = Rc<dyn Fn(&State) -> Option<String>>;
+
+/// How the local human players are assisted.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Assistance {
+    /// Whether a player may ask for the best move.
+    pub hints: bool,
+    /// Whether the cells the opponent would win by occupying are highlighted.
+    pub threats: bool,
+}
+
+/// The best move suggested to the local human player whose turn it is.
+#[derive(Debug)]
+struct Hint {
+    enabled: bool,
+    solver: Solver,
+    /// The [`Position`] for which the move was suggested.
+    v: Option<(Position, Cell)>,
+}
+
+impl Hint {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            solver: Solver::new(),
+            v: None,
+        }
+    }
+
+    /// Suggests a move if it is the turn of the `player_id`.
+    fn request(&mut self, game_state: &State, player_id: PlayerId) {
+        if self.enabled && game_state.phase == Inround && game_state.turn() == player_id {
+            let position = Position::new(game_state.board.clone(), player_id);
+            self.v = self
+                .solver
+                .best_moves(&position)
+                .first()
+                .map(|&cell| (position, cell));
+        }
+    }
+
+    /// Returns the suggested move, unless a move has been made since it was suggested.
+    fn get(&self, game_state: &State) -> Option<Cell> {
+        self.v.as_ref().and_then(|(position, cell)| {
+            (game_state.phase == Inround
+                && position.turn() == game_state.turn()
+                && *position.board() == game_state.board)
+                .then_some(*cell)
+        })
+    }
+}
+
+/// A position from the [`State::log`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Preview {
+    /// The index of the round in the [`State::log`].
+    round: usize,
+    /// The number of the first moves of the round that result in the position.
+    moves: usize,
+}
+
+impl Preview {
+    /// Returns the player who occupies the `cell` in the position.
+    fn get(self, game_state: &State, cell: Cell) -> Option<PlayerId> {
+        game_state.log[self.round].moves[..self.moves]
+            .iter()
+            .find(|mv| mv.cell == cell)
+            .map(|mv| mv.player_id)
+    }
+
+    fn is_last_move(self, game_state: &State, cell: Cell) -> bool {
+        game_state.log[self.round].moves[self.moves - 1].cell == cell
+    }
+}
 
 pub struct GameView {
     game_world: GameWorld,
+    preview: SharedPreview,
     clock: Clock,
+    time_scale: TimeScale,
+    saved_game: Option<SavedGame>,
+    rematch: Option<Rematch>,
+    on_game_over: Option<OnGameOver>,
+    /// Whether the summary has been shown since the game is over.
+    summary_shown: bool,
     layout: LinearLayout,
 }
 
 impl GameView {
+    pub const ID: &'static str = "GAME_VIEW_ID";
     const AI_COMMON_CTRLS_VIEW_ID: &'static str = "AI_COMMON_CTRLS_VIEW_ID";
-
+    const BOARD_VIEW_ID: &'static str = "BOARD_VIEW_ID";
+    /// Indexed by [`PlayerId`].
+    const PLAYER_VIEW_IDS: [&'static str; 2] = ["PLAYER_0_VIEW_ID", "PLAYER_1_VIEW_ID"];
+    const PLAYER_MAX_WIDTH: usize = 30;
+    const TIME_SCALES: [f64; 7] = [0.125, 0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+    const SLOW_DOWN_KEY: char = '<';
+    const SPEED_UP_KEY: char = '>';
+    pub const PAUSE_KEY: char = 'p';
+    pub const READY_KEY: char = 'r';
+    pub const SURRENDER_KEY: char = 's';
+    pub const HINT_KEY: char = '?';
+
+    /// The `saved_game`, if any, is what allows replaying the game.
+    /// The `seed` is only shown to the user.
     pub fn new(
         game_world: World<DefaultActionQueue>,
         action_queues: Vec<Rc<DefaultActionQueue>>,
+        assistance: Assistance,
+        seed: u64,
+        saved_game: Option<SavedGame>,
     ) -> Self {
         assert_eq!(
             game_world.state().players.len(),
@@ -64,16 +181,27 @@ impl GameView {
                 .any(|aq| game_world.state().players[aq.player_id().idx].typ != Local(Human)),
             "the provided actions queues must correspond to `Local(Human)` players: {game_world:?}, {action_queues:?}"
         );
-        let action_queues = {
-            let mut map = HashMap::with_hasher(Xxh3Builder::new());
-            for aq in action_queues {
-                map.insert(aq.player_id(), aq);
-            }
-            map
+        let action_queues = action_queues
+            .into_iter()
+            .map(|aq| (aq.player_id(), aq))
+            .collect::<HashMap<_, _, Xxh3Builder>>();
+        // both the game and the animations are slowed down or sped up
+        let time_scale = TimeScale::new(1.0);
+        let scaled_real_clock_source = || {
+            Box::new(ScaledClockSource::new(
+                Box::new(RealClockSource::new()),
+                time_scale.clone(),
+            ))
         };
-        let game_world = Rc::new(RefCell::new(game_world));
+        let game_world = Rc::new(RefCell::new(
+            game_world.with_clock_source(scaled_real_clock_source()),
+        ));
         let action_queues = Rc::new(action_queues);
-        let clock = Rc::new(RefCell::new(AdvanceableClock::new(Instant::now())));
+        let hint = Rc::new(RefCell::new(Hint::new(assistance.hints)));
+        let preview = Rc::new(RefCell::new(None));
+        let clock = Rc::new(RefCell::new(AdvanceableClock::new(
+            scaled_real_clock_source(),
+        )));
         let layout = {
             let game_world_ref = game_world.borrow();
             let players_local_human_first = {
@@ -86,75 +214,200 @@ impl GameView {
                 vec
             };
             let mut layout = LinearLayout::vertical()
-                .child(Panel::new(GameInfoView::new(&game_world)))
+                .child(Panel::new(GameInfoView::new(
+                    &game_world,
+                    &preview,
+                    seed,
+                    &time_scale,
+                )))
                 .child(
                     LinearLayout::horizontal()
                         .child(Self::player_layout(
                             players_local_human_first[0].id,
                             &game_world,
                             &action_queues,
+                            &hint,
                             &clock,
                         ))
-                        .child(Self::game_board_layout(&game_world, &action_queues, &clock))
+                        .child(
+                            BoardView::new(
+                                &game_world,
+                                &action_queues,
+                                (&hint, &preview, assistance.threats),
+                                &clock,
+                            )
+                            .with_name(Self::BOARD_VIEW_ID),
+                        )
                         .child(Self::player_layout(
                             players_local_human_first[1].id,
                             &game_world,
                             &action_queues,
+                            &hint,
                             &clock,
-                        )),
+                        ))
+                        .child(Self::history_layout(&game_world, &preview)),
                 );
             if players_local_human_first.first().unwrap().typ == Local(Ai) {
                 // all players are AI
-                layout.add_child(
-                    Panel::new(
-                        LocalAiCommonControlsView::new(&game_world)
-                            .with_name(Self::AI_COMMON_CTRLS_VIEW_ID),
-                    )
-                    .title("Common AI controls")
-                    .title_position(HAlign::Left),
-                );
+                layout.add_child(Self::ai_common_ctrls_layout(&game_world));
+            } else {
+                // the board is controlled via the keyboard only while it has the focus
+                layout
+                    .focus_view(&Selector::Name(Self::BOARD_VIEW_ID))
+                    .unwrap();
             }
             layout
         };
         Self {
             game_world,
+            preview,
             clock: Rc::clone(&clock),
+            time_scale,
+            saved_game,
+            rematch: None,
+            on_game_over: None,
+            summary_shown: false,
             layout,
         }
     }
 
-    fn game_board_layout(
-        game_world: &GameWorld,
-        action_queues: &ActionQueues,
-        clock: &Clock,
-    ) -> impl View {
-        let game_world_ref = game_world.borrow();
-        let game_state = game_world_ref.state();
-        let board_size = game_state.board.size();
-        let mut game_board_layout = LinearLayout::vertical();
-        for x in 0..board_size {
-            let mut column = LinearLayout::horizontal();
-            for y in 0..board_size {
-                column.add_child(Panel::new(CellView::new(
-                    Cell::new(x, y),
-                    game_world,
-                    action_queues,
-                    clock,
-                )));
-            }
-            game_board_layout.add_child(column);
+    /// Calls `on_game_over` when the game is over, before showing the summary of the game.
+    #[must_use]
+    pub fn with_on_game_over(
+        mut self,
+        on_game_over: impl 'static + Fn(&State) -> Option<String>,
+    ) -> Self {
+        self.on_game_over = Some(Rc::new(on_game_over));
+        self
+    }
+
+    /// Replaces the title of the panel of the player, which is the [`Player`] by default.
+    #[must_use]
+    pub fn with_player_title(mut self, player_id: PlayerId, title: String) -> Self {
+        self.layout
+            .call_on_name(
+                Self::PLAYER_VIEW_IDS[player_id.idx],
+                |view: &mut NamedView<Panel<LinearLayout>>| view.get_mut().set_title(title),
+            )
+            .unwrap();
+        self
+    }
+
+    /// Offers starting a new game via `rematch` when the game is over.
+    #[must_use]
+    pub fn with_rematch(mut self, rematch: impl 'static + Fn(&mut Cursive)) -> Self {
+        self.rematch = Some(Rc::new(rematch));
+        self
+    }
+
+    /// Picks the previous (`faster` is `false`) or the next (`faster` is `true`)
+    /// of [`Self::TIME_SCALES`], if any.
+    fn change_time_scale(&self, faster: bool) {
+        let current = self.time_scale.unpaused();
+        let next = if faster {
+            Self::TIME_SCALES.iter().find(|&&scale| scale > current)
+        } else {
+            Self::TIME_SCALES
+                .iter()
+                .rev()
+                .find(|&&scale| scale < current)
+        };
+        if let Some(&next) = next {
+            self.time_scale.set(next);
         }
-        game_board_layout
+    }
+
+    /// Returns the game to be saved, with the [`SavedGame::log`] once the game is over.
+    ///
+    /// # Errors
+    ///
+    /// If the game cannot be replayed, e.g., if it has human players and is not over yet.
+    pub fn saved_game(&self) -> Result<SavedGame, &'static str> {
+        let saved_game = self
+            .saved_game
+            .as_ref()
+            .ok_or("The game cannot be replayed")?;
+        let game_world = self.game_world.borrow();
+        if !saved_game.log.is_empty() {
+            Ok(saved_game.clone())
+        } else if Logic::<DefaultActionQueue>::is_game_over(game_world.state()) {
+            Ok(saved_game.clone().with_log(game_world.state()))
+        } else if saved_game.has_humans() {
+            Err("Games with human players can only be saved once they are over")
+        } else {
+            Ok(saved_game.clone())
+        }
+    }
+
+    /// Games with remote players cannot be paused, as they would have to agree to pause.
+    pub fn is_pausable(&self) -> bool {
+        !self
+            .game_world
+            .borrow()
+            .state()
+            .players
+            .iter()
+            .any(|player| player.typ == Remote)
+    }
+
+    /// Pauses or resumes the game, which freezes the [`State::clock`](crate::game::State::clock),
+    /// and with it the [`Ai`](crate::game::Ai)s and animations.
+    ///
+    /// # Errors
+    ///
+    /// If the game is not [pausable](Self::is_pausable).
+    pub fn toggle_paused(&mut self) -> Result<(), &'static str> {
+        if !self.is_pausable() {
+            return Err("Games with remote players cannot be paused");
+        }
+        self.time_scale.set_paused(!self.time_scale.is_paused());
+        Ok(())
+    }
+
+    fn draw_paused(printer: &Printer) {
+        let txt = "Paused";
+        let txt_width = UnicodeWidthStr::width(txt);
+        // the text and the spaces around it, the borders
+        let size = Vec2::new(txt_width + 4, 3);
+        let start = Vec2::new(
+            HAlign::Center.get_offset(size.x, printer.size.x),
+            HAlign::Center.get_offset(size.y, printer.size.y),
+        );
+        printer.print_hline(start + Vec2::new(1, 1), txt_width + 2, " ");
+        printer.print_box(start, size, false);
+        printer.with_color(highlighted_color_style(printer), |printer| {
+            printer.print(start + Vec2::new(2, 1), txt);
+        });
+    }
+
+    fn ai_common_ctrls_layout(game_world: &GameWorld) -> impl View {
+        Panel::new(
+            LocalAiCommonControlsView::new(game_world).with_name(Self::AI_COMMON_CTRLS_VIEW_ID),
+        )
+        .title("Common AI controls")
+        .title_position(HAlign::Left)
+    }
+
+    fn history_layout(game_world: &GameWorld, preview: &SharedPreview) -> impl View {
+        Panel::new(
+            HistoryView::new(game_world, preview)
+                .scrollable()
+                .scroll_strategy(ScrollStrategy::StickToBottom),
+        )
+        .title("Moves")
+        .title_position(HAlign::Left)
+        .fixed_width(HistoryView::WIDTH)
     }
 
     fn player_layout(
         player_id: PlayerId,
         game_world: &GameWorld,
         action_queues: &ActionQueues,
+        hint: &SharedHint,
         clock: &Clock,
     ) -> impl View {
         let title = game_world.borrow().state().players[player_id.idx].to_string();
-        match game_world.borrow().state().players[player_id.idx].typ {
+        let panel = match game_world.borrow().state().players[player_id.idx].typ {
             Local(Human) => Panel::new(
                 LinearLayout::vertical()
                     .child(PlayerInfoView::new(player_id, game_world, clock))
@@ -162,6 +415,7 @@ impl GameView {
                         Panel::new(LocalHumanControlsView::new(
                             game_world,
                             &action_queues[&player_id],
+                            hint,
                         ))
                         .title("Controls")
                         .title_position(HAlign::Left),
@@ -169,16 +423,24 @@ impl GameView {
             )
             .title(title)
             .title_position(HAlign::Left),
-            Local(Ai) | _Remote => Panel::new(
+            Local(Ai) | Remote => Panel::new(
                 LinearLayout::vertical().child(PlayerInfoView::new(player_id, game_world, clock)),
             )
             .title(title)
             .title_position(HAlign::Left),
-        }
+        };
+        // the rest of the width is for the board
+        panel
+            .with_name(Self::PLAYER_VIEW_IDS[player_id.idx])
+            .max_width(Self::PLAYER_MAX_WIDTH)
     }
 
     fn advance(&mut self) {
-        self.clock.borrow_mut().advance_to_real_now();
+        self.clock.borrow_mut().advance();
+        if self.time_scale.is_paused() {
+            // zero delays of `Ai`s do not depend on the clock, so we must not advance at all
+            return;
+        }
         let maximise_advance = self
             .layout
             .call_on_name(
@@ -187,7 +449,8 @@ impl GameView {
                     view.get_mut().unleashed_promptness
                 },
             )
-            .unwrap();
+            // there are no common AI controls if there is a human player
+            .unwrap_or(false);
         if maximise_advance {
             // TODO implement this based on FPS
             for _ in 0..200_000 {
@@ -202,6 +465,9 @@ impl GameView {
 impl View for GameView {
     fn draw(&self, printer: &Printer) {
         self.layout.draw(printer);
+        if self.time_scale.is_paused() {
+            Self::draw_paused(printer);
+        }
     }
 
     fn layout(&mut self, view_size: Vec2) {
@@ -218,7 +484,46 @@ impl View for GameView {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
-        self.layout.on_event(event)
+        if !self.summary_shown
+            && Logic::<DefaultActionQueue>::is_game_over(self.game_world.borrow().state())
+        {
+            self.summary_shown = true;
+            let game_world = Rc::clone(&self.game_world);
+            let rematch = self.rematch.clone();
+            let saved_game = self.saved_game().ok();
+            let on_game_over = self.on_game_over.clone();
+            return EventResult::with_cb(move |tui| {
+                let game_world = game_world.borrow();
+                let game_state = game_world.state();
+                let note = on_game_over
+                    .as_ref()
+                    .and_then(|on_game_over| on_game_over(game_state));
+                menu::summary::show(
+                    tui,
+                    (game_state, note),
+                    (saved_game.clone(), rematch.clone()),
+                );
+            });
+        }
+        match event {
+            // same as the menu item, which also shows errors
+            Event::Char(Self::PAUSE_KEY) => EventResult::with_cb(menu::toggle_paused),
+            // a paused game does not accept user actions
+            _ if self.time_scale.is_paused() => Consumed(None),
+            Event::Key(Key::Esc) if self.preview.borrow().is_some() => {
+                *self.preview.borrow_mut() = None;
+                Consumed(None)
+            }
+            Event::Char(Self::SLOW_DOWN_KEY) => {
+                self.change_time_scale(false);
+                Consumed(None)
+            }
+            Event::Char(Self::SPEED_UP_KEY) => {
+                self.change_time_scale(true);
+                Consumed(None)
+            }
+            event => self.layout.on_event(event),
+        }
     }
 
     fn call_on_any<'a>(&mut self, selector: &Selector<'_>, cb: AnyCb<'_>) {
@@ -241,13 +546,24 @@ impl View for GameView {
 #[derive(Debug)]
 struct GameInfoView {
     game_world: GameWorld,
+    preview: SharedPreview,
+    seed: u64,
+    time_scale: TimeScale,
     size: Vec2,
 }
 
 impl GameInfoView {
-    fn new(game_world: &GameWorld) -> Self {
+    fn new(
+        game_world: &GameWorld,
+        preview: &SharedPreview,
+        seed: u64,
+        time_scale: &TimeScale,
+    ) -> Self {
         Self {
             game_world: Rc::clone(game_world),
+            preview: Rc::clone(preview),
+            seed,
+            time_scale: time_scale.clone(),
             size: Vec2::default(),
         }
     }
@@ -257,7 +573,14 @@ impl View for GameInfoView {
     fn draw(&self, printer: &Printer) {
         let game_world = self.game_world.borrow();
         let game_state = game_world.state();
-        let txt_round = &format!("Round {} of {}", game_state.round + 1, game_state.rounds);
+        let txt_round = &match *self.preview.borrow() {
+            None => format!("Round {} of {}", game_state.round + 1, game_state.rounds),
+            Some(preview) => format!(
+                "Previewing round {}, move {} (Esc)",
+                preview.round + 1,
+                preview.moves
+            ),
+        };
         printer.print(
             Vec2::new(
                 HAlign::Center.get_offset(UnicodeWidthStr::width(txt_round.as_str()), self.size.x),
@@ -265,6 +588,29 @@ impl View for GameInfoView {
             ),
             txt_round,
         );
+        printer.print(
+            Vec2::new(1, HAlign::Center.get_offset(1, self.size.y)),
+            &format!("Seed: {}", self.seed),
+        );
+        let txt_speed = &if self.time_scale.is_paused() {
+            format!("Paused ({})", GameView::PAUSE_KEY)
+        } else {
+            format!(
+                "Speed: {}x ({}, {})",
+                self.time_scale.get(),
+                GameView::SLOW_DOWN_KEY,
+                GameView::SPEED_UP_KEY
+            )
+        };
+        printer.print(
+            Vec2::new(
+                self.size
+                    .x
+                    .saturating_sub(UnicodeWidthStr::width(txt_speed.as_str()) + 1),
+                HAlign::Center.get_offset(1, self.size.y),
+            ),
+            txt_speed,
+        );
     }
 
     fn layout(&mut self, view_size: Vec2) {
@@ -319,7 +665,7 @@ impl View for PlayerInfoView {
                         (
                             match player.typ {
                                 Local(Human) => "ready up",
-                                Local(Ai) | _Remote => "readying up",
+                                Local(Ai) | Remote => "readying up",
                             },
                             true,
                         )
@@ -334,7 +680,7 @@ impl View for PlayerInfoView {
                         (
                             match player.typ {
                                 Local(Human) => "your turn",
-                                Local(Ai) | _Remote => "thinking",
+                                Local(Ai) | Remote => "thinking",
                             },
                             true,
                         )
@@ -356,6 +702,17 @@ impl View for PlayerInfoView {
             start + Vec2::new(0, 1),
             &format!("Rounds won: {}", player.wins),
         );
+        // the commentary on the latest move of the player in the round, if explained
+        if let Some((cell, reason)) = game_state
+            .round_log()
+            .moves
+            .iter()
+            .rev()
+            .find(|mv| mv.player_id == self.player_id)
+            .and_then(|mv| mv.reason.map(|reason| (mv.cell, reason)))
+        {
+            printer.print(start + Vec2::new(0, 2), &format!("Why {cell}: {reason}"));
+        }
     }
 
     fn layout(&mut self, view_size: Vec2) {
@@ -367,12 +724,212 @@ impl View for PlayerInfoView {
     }
 }
 
+/// The cells of the board. It takes the focus only if there is a local human player,
+/// and then controls the board via the keyboard while it has the focus.
+struct BoardView {
+    game_world: GameWorld,
+    action_queues: ActionQueues,
+    cursor: Cursor,
+    hint: SharedHint,
+    preview: SharedPreview,
+    /// Indexed by [`Cell::x`] first, the rows of the board.
+    cells: Vec<Vec<Panel<CellView>>>,
+    cell_size: Vec2,
+}
+
+impl BoardView {
+    /// `show_threats` is [`Assistance::threats`].
+    fn new(
+        game_world: &GameWorld,
+        action_queues: &ActionQueues,
+        (hint, preview, show_threats): (&SharedHint, &SharedPreview, bool),
+        clock: &Clock,
+    ) -> Self {
+        let board_size = game_world.borrow().state().board.size();
+        let cursor = Rc::new(RefCell::new(Cell::new(board_size / 2, board_size / 2)));
+        let cells = (0..board_size)
+            .map(|x| {
+                (0..board_size)
+                    .map(|y| {
+                        Panel::new(CellView::new(
+                            Cell::new(x, y),
+                            game_world,
+                            action_queues,
+                            (&cursor, hint, preview, show_threats),
+                            clock,
+                        ))
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            game_world: Rc::clone(game_world),
+            action_queues: Rc::clone(action_queues),
+            cursor,
+            hint: Rc::clone(hint),
+            preview: Rc::clone(preview),
+            cells,
+            cell_size: Vec2::default(),
+        }
+    }
+
+    /// Moves the [`Cursor`] by `dx` rows and `dy` columns, staying within the board.
+    /// Ignores moving beyond the board, which lets the layout move the focus instead.
+    fn move_cursor(&self, dx: isize, dy: isize) -> EventResult {
+        let max = self.game_world.borrow().state().board.size() - 1;
+        let mut cursor = self.cursor.borrow_mut();
+        let moved = |v: usize, d: isize| v.saturating_add_signed(d).min(max);
+        let next = Cell::new(moved(cursor.x(), dx), moved(cursor.y(), dy));
+        if next == *cursor {
+            return Ignored;
+        }
+        *cursor = next;
+        Consumed(None)
+    }
+
+    /// Maps the digits to the cells of a 3x3 board as they are laid out on a numeric keypad,
+    /// that is `7` is the top left cell, and `3` is the bottom right one.
+    fn numpad_cell(&self, c: char) -> Option<Cell> {
+        let size = self.game_world.borrow().state().board.size();
+        match c.to_digit(10) {
+            Some(digit @ 1..=9) if size == 3 => {
+                let idx = usize::try_from(digit - 1).unwrap();
+                Some(Cell::new(size - 1 - idx / size, idx % size))
+            }
+            _ => None,
+        }
+    }
+
+    /// Same as the "Ready/Continue" button of the local human player.
+    fn ready(&self) -> EventResult {
+        let game_world = self.game_world.borrow();
+        let game_state = game_world.state();
+        for (player_id, action_queue) in self.action_queues.iter() {
+            // more than one pending action would not be expected by the `Logic`
+            if game_state.required_ready.contains(player_id) && action_queue.is_empty() {
+                action_queue.add(Action::Ready);
+            }
+        }
+        Consumed(None)
+    }
+
+    /// Same as the "Surrender the round" button of the local human player whose turn it is.
+    fn surrender(&self) -> EventResult {
+        let game_world = self.game_world.borrow();
+        let game_state = game_world.state();
+        if let Some(action_queue) = self.action_queues.get(&game_state.turn()) {
+            if game_state.phase == Inround && action_queue.is_empty() {
+                action_queue.add(Action::Surrender);
+            }
+        }
+        Consumed(None)
+    }
+
+    fn on_key_event(&self, event: &Event) -> EventResult {
+        match *event {
+            Event::Key(Key::Up) | Event::Char('k') => self.move_cursor(-1, 0),
+            Event::Key(Key::Down) | Event::Char('j') => self.move_cursor(1, 0),
+            Event::Key(Key::Left) | Event::Char('h') => self.move_cursor(0, -1),
+            Event::Key(Key::Right) | Event::Char('l') => self.move_cursor(0, 1),
+            Event::Key(Key::Enter) | Event::Char(' ') => {
+                let cell = *self.cursor.borrow();
+                occupy(&self.game_world, &self.action_queues, &self.preview, cell)
+            }
+            Event::Char(c) if self.numpad_cell(c).is_some() => {
+                let cell = self.numpad_cell(c).unwrap();
+                *self.cursor.borrow_mut() = cell;
+                occupy(&self.game_world, &self.action_queues, &self.preview, cell)
+            }
+            Event::Char(GameView::READY_KEY) => self.ready(),
+            Event::Char(GameView::SURRENDER_KEY) => self.surrender(),
+            Event::Char(GameView::HINT_KEY) => {
+                let game_world = self.game_world.borrow();
+                let game_state = game_world.state();
+                if self.action_queues.contains_key(&game_state.turn()) {
+                    self.hint
+                        .borrow_mut()
+                        .request(game_state, game_state.turn());
+                }
+                Consumed(None)
+            }
+            _ => Ignored,
+        }
+    }
+
+    /// Returns the cell at the `position` relative to the board, if any.
+    fn cell_at(&self, position: Vec2) -> Option<Cell> {
+        if self.cell_size.x == 0 || self.cell_size.y == 0 {
+            return None;
+        }
+        let (x, y) = (position.y / self.cell_size.y, position.x / self.cell_size.x);
+        let board_size = self.cells.len();
+        (x < board_size && y < board_size).then(|| Cell::new(x, y))
+    }
+
+    fn on_mouse_press_left(&self, cell: Cell) -> EventResult {
+        if !self.action_queues.is_empty() {
+            *self.cursor.borrow_mut() = cell;
+        }
+        occupy(&self.game_world, &self.action_queues, &self.preview, cell)
+    }
+}
+
+impl View for BoardView {
+    fn draw(&self, printer: &Printer) {
+        for (x, row) in self.cells.iter().enumerate() {
+            for (y, cell) in row.iter().enumerate() {
+                let offset = Vec2::new(y * self.cell_size.x, x * self.cell_size.y);
+                cell.draw(&printer.offset(offset).cropped(self.cell_size));
+            }
+        }
+    }
+
+    fn layout(&mut self, view_size: Vec2) {
+        let board_size = self.cells.len();
+        self.cell_size = view_size / board_size;
+        for cell in self.cells.iter_mut().flatten() {
+            cell.layout(self.cell_size);
+        }
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        constraint
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Mouse {
+                event: MouseEvent::Press(MouseButton::Left),
+                position,
+                offset,
+            } => position
+                .checked_sub(offset)
+                .and_then(|position| self.cell_at(position))
+                .map_or(Ignored, |cell| self.on_mouse_press_left(cell)),
+            event => self.on_key_event(&event),
+        }
+    }
+
+    fn take_focus(&mut self, _source: Direction) -> Result<EventResult, CannotFocus> {
+        if self.action_queues.is_empty() {
+            Err(CannotFocus)
+        } else {
+            Ok(EventResult::consumed())
+        }
+    }
+}
+
 #[derive(Debug)]
 struct CellView {
     cell: Cell,
     game_world: GameWorld,
     action_queues: ActionQueues,
+    cursor: Cursor,
+    hint: SharedHint,
+    preview: SharedPreview,
+    show_threats: bool,
     clock: Clock,
+    charset: Charset,
     size: Vec2,
     occupied_anim: Option<BlinkingAnimation>,
 }
@@ -382,31 +939,91 @@ impl CellView {
         cell: Cell,
         game_world: &GameWorld,
         action_queues: &ActionQueues,
+        (cursor, hint, preview, show_threats): (&Cursor, &SharedHint, &SharedPreview, bool),
         clock: &Clock,
     ) -> Self {
         Self {
             cell,
             game_world: Rc::clone(game_world),
             action_queues: Rc::clone(action_queues),
+            cursor: Rc::clone(cursor),
+            hint: Rc::clone(hint),
+            preview: Rc::clone(preview),
+            show_threats,
             clock: Rc::clone(clock),
+            charset: Charset::detect(&|name| env::var_os(name)),
             size: Vec2::default(),
             occupied_anim: None,
         }
     }
 
-    fn on_mouse_press_left(&self) -> EventResult {
-        let game_world = self.game_world.borrow();
-        let game_state = game_world.state();
-        if let Some(action_queue) = self.action_queues.get(&game_state.turn()) {
-            if game_state.phase == Inround && game_state.board.get(&self.cell).is_none() {
-                action_queue.add(Action::Occupy(self.cell));
-                Consumed(None)
-            } else {
-                Ignored
-            }
+    /// Returns the label of the [`Hint`] or of a threat, if the cell is the one.
+    fn assistance_label(&self, game_state: &State) -> Option<&'static str> {
+        if self.hint.borrow().get(game_state) == Some(self.cell) {
+            Some("hint")
+        } else if self.show_threats
+            && game_state.phase == Inround
+            && self.action_queues.contains_key(&game_state.turn())
+            && Position::new(game_state.board.clone(), game_state.turn())
+                .threats()
+                .contains(&self.cell)
+        {
+            Some("threat")
+        } else {
+            None
+        }
+    }
+
+    fn draw_glyph(&self, mark: Mark, printer: &Printer) {
+        // the leftmost and the rightmost columns are reserved for the cursor
+        let glyph = glyph(mark, self.size.saturating_sub((2, 0)), self.charset);
+        let glyph_width = glyph
+            .first()
+            .map_or(0, |row| UnicodeWidthStr::width(row.as_str()));
+        let start = Vec2::new(
+            HAlign::Center.get_offset(glyph_width, self.size.x),
+            HAlign::Center.get_offset(glyph.len(), self.size.y),
+        );
+        for (idx, row) in glyph.iter().enumerate() {
+            printer.print(start + (0, idx), row);
+        }
+    }
+
+    fn draw_cursor(&self, printer: &Printer) {
+        let y = HAlign::Center.get_offset(1, self.size.y);
+        printer.with_color(highlighted_color_style(printer), |printer| {
+            printer.print((0, y), "[");
+            printer.print((self.size.x.saturating_sub(1), y), "]");
+        });
+    }
+}
+
+/// Occupies the `cell` on behalf of the local human player whose turn it is, if possible.
+/// If a [`Preview`] is shown, only stops showing it.
+fn occupy(
+    game_world: &GameWorld,
+    action_queues: &ActionQueues,
+    preview: &SharedPreview,
+    cell: Cell,
+) -> EventResult {
+    if preview.borrow_mut().take().is_some() {
+        return Consumed(None);
+    }
+    let game_world = game_world.borrow();
+    let game_state = game_world.state();
+    if let Some(action_queue) = action_queues.get(&game_state.turn()) {
+        // more than one pending action would not be expected by the `Logic`
+        if game_state.phase == Inround
+            && game_state.board.get(&cell).is_none()
+            && action_queue.is_empty()
+        {
+            action_queue.add(Action::Occupy(cell, None));
+            Consumed(None)
         } else {
             Ignored
         }
+    } else {
+        Ignored
     }
 }
 
@@ -414,28 +1031,46 @@ impl View for CellView {
     fn draw(&self, printer: &Printer) {
         let game_world = self.game_world.borrow();
         let game_state = game_world.state();
+        if let Some(preview) = *self.preview.borrow() {
+            if let Some(player_id) = preview.get(game_state, self.cell) {
+                if preview.is_last_move(game_state, self.cell) {
+                    printer.with_color(highlighted_color_style(printer), |printer| {
+                        self.draw_glyph(player_id.mark(), printer);
+                    });
+                } else {
+                    self.draw_glyph(player_id.mark(), printer);
+                }
+            }
+            return;
+        }
+        // the board has the focus only if there is a local human player
+        if printer.focused && *self.cursor.borrow() == self.cell {
+            self.draw_cursor(printer);
+        }
         if let Some(player_id) = game_state.board.get(&self.cell) {
-            let txt_mark = &game_state.players[player_id.idx].mark().to_string();
             let draw = |printer: &Printer| {
-                printer.print(
-                    Vec2::new(
-                        HAlign::Center
-                            .get_offset(UnicodeWidthStr::width(txt_mark.as_str()), self.size.x),
-                        HAlign::Center.get_offset(1, self.size.y),
-                    ),
-                    txt_mark,
-                );
+                self.draw_glyph(game_state.players[player_id.idx].mark(), printer);
             };
             if game_state
                 .win_line
                 .map_or(false, |line| line.contains(&self.cell))
             {
-                printer.with_color(HIGHLIGHTED_COLOR_STYLE, draw);
+                printer.with_color(highlighted_color_style(printer), draw);
             } else if let Some(occupied_anim) = &self.occupied_anim {
                 occupied_anim.draw(self.clock.borrow().now(), printer, draw);
             } else {
                 draw(printer);
             }
+        } else if let Some(txt) = self.assistance_label(game_state) {
+            printer.with_color(highlighted_color_style(printer), |printer| {
+                printer.print(
+                    Vec2::new(
+                        HAlign::Center.get_offset(UnicodeWidthStr::width(txt), self.size.x),
+                        HAlign::Center.get_offset(1, self.size.y),
+                    ),
+                    txt,
+                );
+            });
         }
     }
 
@@ -467,6 +1102,172 @@ impl View for CellView {
     fn required_size(&mut self, constraint: Vec2) -> Vec2 {
         constraint
     }
+}
+
+/// What [`HistoryView`] lists.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum HistoryItem {
+    /// The index of the round in the [`State::log`].
+    Round(usize),
+    /// The position after the move.
+    Move(Preview),
+}
+
+/// Lists the moves of each round. The moves of the previous rounds are hidden
+/// unless the round is activated, and activating a move previews the position after it.
+/// An item is activated by clicking it, or by selecting it via the arrow keys and pressing `Enter`.
+#[derive(Debug)]
+struct HistoryView {
+    game_world: GameWorld,
+    preview: SharedPreview,
+    /// The indices in the [`State::log`] of the previous rounds whose moves are listed.
+    expanded: HashSet<usize, Xxh3Builder>,
+    items: Vec<(String, HistoryItem)>,
+    /// Is kept even if it is not listed anymore, in which case no item is selected.
+    selected: Option<HistoryItem>,
+}
+
+impl HistoryView {
+    const WIDTH: usize = 28;
+
+    fn new(game_world: &GameWorld, preview: &SharedPreview) -> Self {
+        Self {
+            game_world: Rc::clone(game_world),
+            preview: Rc::clone(preview),
+            expanded: HashSet::with_hasher(Xxh3Builder::new()),
+            items: Vec::new(),
+            selected: None,
+        }
+    }
+
+    fn update_items(&mut self) {
+        let game_world = self.game_world.borrow();
+        let game_state = game_world.state();
+        let current_round = game_state.log.len() - 1;
+        self.items.clear();
+        for (round, round_log) in game_state.log.iter().enumerate() {
+            let expanded = round == current_round || self.expanded.contains(&round);
+            let sign = match (round == current_round, expanded) {
+                (true, _) => ' ',
+                (false, true) => '-',
+                (false, false) => '+',
+            };
+            let result = match round_log.outcome {
+                Some(Outcome::Win(player_id, _)) => format!(": {} won", player_id.mark()),
+                Some(Outcome::Surrender(player_id)) => {
+                    format!(": {} surrendered", player_id.mark())
+                }
+                Some(Outcome::Draw) => ": draw".to_owned(),
+                None => String::new(),
+            };
+            self.items.push((
+                format!("{sign} Round {}{result}", round + 1),
+                HistoryItem::Round(round),
+            ));
+            if expanded {
+                for (idx, mv) in round_log.moves.iter().enumerate() {
+                    self.items.push((
+                        format!(
+                            "  {:>2}. {} {} {:>7.1} s",
+                            idx + 1,
+                            mv.player_id.mark(),
+                            mv.cell,
+                            mv.round_time.as_secs_f64()
+                        ),
+                        HistoryItem::Move(Preview {
+                            round,
+                            moves: idx + 1,
+                        }),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn selected_row(&self) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|&(_, item)| Some(item) == self.selected)
+    }
+
+    /// Selects the previous (`down` is `false`) or the next (`down` is `true`) item,
+    /// or the last one if none is selected. Ignores moving beyond the list,
+    /// which lets the layout move the focus instead.
+    fn move_selection(&mut self, down: bool) -> EventResult {
+        let row = match self.selected_row() {
+            None => self.items.len().checked_sub(1),
+            Some(row) if down => Some(row + 1).filter(|&row| row < self.items.len()),
+            Some(row) => row.checked_sub(1),
+        };
+        row.map_or(Ignored, |row| {
+            self.selected = Some(self.items[row].1);
+            Consumed(None)
+        })
+    }
+
+    /// Shows or hides the moves of a previous round, or shows or hides the preview after a move.
+    fn activate(&mut self, item: HistoryItem) -> EventResult {
+        let current_round = self.game_world.borrow().state().log.len() - 1;
+        match item {
+            HistoryItem::Round(round) if round != current_round => {
+                if !self.expanded.remove(&round) {
+                    self.expanded.insert(round);
+                }
+            }
+            HistoryItem::Move(activated) => {
+                let mut preview = self.preview.borrow_mut();
+                *preview = if *preview == Some(activated) {
+                    None
+                } else {
+                    Some(activated)
+                };
+            }
+            HistoryItem::Round(_) => return Ignored,
+        }
+        Consumed(None)
+    }
+
+    fn on_mouse_press_left(&mut self, row: usize) -> EventResult {
+        let Some(&(_, item)) = self.items.get(row) else {
+            return Ignored;
+        };
+        self.selected = Some(item);
+        self.activate(item)
+    }
+}
+
+impl View for HistoryView {
+    fn draw(&self, printer: &Printer) {
+        let preview = *self.preview.borrow();
+        for (row, (txt, item)) in self.items.iter().enumerate() {
+            // the selection is shown only while the view has the focus
+            printer.with_selection(printer.focused && self.selected == Some(*item), |printer| {
+                if preview.map(HistoryItem::Move) == Some(*item) {
+                    printer.with_color(highlighted_color_style(printer), |printer| {
+                        printer.print((0, row), txt);
+                    });
+                } else {
+                    printer.print((0, row), txt);
+                }
+            });
+        }
+    }
+
+    fn layout(&mut self, _view_size: Vec2) {
+        self.update_items();
+    }
+
+    fn required_size(&mut self, _constraint: Vec2) -> Vec2 {
+        self.update_items();
+        Vec2::new(
+            self.items
+                .iter()
+                .map(|(txt, _)| UnicodeWidthStr::width(txt.as_str()))
+                .max()
+                .unwrap_or(0),
+            self.items.len(),
+        )
+    }
 
     fn on_event(&mut self, event: Event) -> EventResult {
         match event {
@@ -474,7 +1275,14 @@ impl View for CellView {
                 event: MouseEvent::Press(MouseButton::Left),
                 position,
                 offset,
-            } if position.fits_in_rect(offset, self.size) => self.on_mouse_press_left(),
+            } => position
+                .checked_sub(offset)
+                .map_or(Ignored, |position| self.on_mouse_press_left(position.y)),
+            Event::Key(Key::Up) => self.move_selection(false),
+            Event::Key(Key::Down) => self.move_selection(true),
+            Event::Key(Key::Enter) => self
+                .selected_row()
+                .map_or(Ignored, |row| self.activate(self.items[row].1)),
             _ => Ignored,
         }
     }
@@ -482,59 +1290,111 @@ impl View for CellView {
     fn take_focus(&mut self, _source: Direction) -> Result<EventResult, CannotFocus> {
         Ok(EventResult::consumed())
     }
+
+    fn important_area(&self, view_size: Vec2) -> Rect {
+        // makes the scroll view follow the selection
+        self.selected_row().map_or_else(
+            || Rect::from_size((0, 0), view_size),
+            |row| Rect::from_size((0, row), (view_size.x, 1)),
+        )
+    }
 }
 
 struct LocalHumanControlsView {
     game_world: GameWorld,
     action_queue: Rc<DefaultActionQueue>,
+    hint: SharedHint,
     layout: LinearLayout,
 }
 
 impl LocalHumanControlsView {
     const GO_BTN_ID: &'static str = "GO_BTN_ID";
     const SURRENDER_BTN_ID: &'static str = "SURRENDER_BTN_ID";
+    const HINT_BTN_ID: &'static str = "HINT_BTN_ID";
 
-    fn new(game_world: &GameWorld, action_queue: &Rc<DefaultActionQueue>) -> Self {
+    fn new(
+        game_world: &GameWorld,
+        action_queue: &Rc<DefaultActionQueue>,
+        hint: &SharedHint,
+    ) -> Self {
+        let mut btns_layout = LinearLayout::vertical()
+            .child(Self::go_btn(action_queue))
+            .child(Self::surrender_btn(action_queue));
+        if hint.borrow().enabled {
+            btns_layout.add_child(Self::hint_btn(game_world, action_queue, hint));
+        }
         let centering_layout = LinearLayout::horizontal()
             .child(ResizedView::with_full_width(DummyView {}))
-            .child(
-                LinearLayout::vertical()
-                    .child(Self::go_btn(action_queue))
-                    .child(Self::surrender_btn(action_queue)),
-            )
+            .child(btns_layout)
             .child(ResizedView::with_full_width(DummyView {}));
         Self {
             game_world: Rc::clone(game_world),
             action_queue: Rc::clone(action_queue),
+            hint: Rc::clone(hint),
             layout: centering_layout,
         }
     }
 
+    fn hint_btn(
+        game_world: &GameWorld,
+        action_queue: &Rc<DefaultActionQueue>,
+        hint: &SharedHint,
+    ) -> NamedView<EnableableView<Button>> {
+        let game_world = Rc::clone(game_world);
+        let player_id = action_queue.player_id();
+        let hint = Rc::clone(hint);
+        let label = format!("Hint ({})", GameView::HINT_KEY);
+        Self::btn_disabled_on_cb(Self::HINT_BTN_ID, label, move |_| {
+            hint.borrow_mut()
+                .request(game_world.borrow().state(), player_id);
+        })
+    }
+
     fn go_btn(action_queue: &Rc<DefaultActionQueue>) -> NamedView<EnableableView<Button>> {
         let action_queue = Rc::clone(action_queue);
-        Self::btn_disabled_on_cb(Self::GO_BTN_ID, "Ready/Continue", move |_| {
+        let label = format!("Ready/Continue ({})", GameView::READY_KEY);
+        Self::btn_disabled_on_cb(Self::GO_BTN_ID, label, move |_| {
             action_queue.add(Action::Ready);
         })
     }
 
     fn surrender_btn(action_queue: &Rc<DefaultActionQueue>) -> NamedView<EnableableView<Button>> {
         let action_queue = Rc::clone(action_queue);
-        Self::btn_disabled_on_cb(Self::SURRENDER_BTN_ID, "Surrender the round", move |_| {
+        let label = format!("Surrender the round ({})", GameView::SURRENDER_KEY);
+        Self::btn_disabled_on_cb(Self::SURRENDER_BTN_ID, label, move |_| {
             action_queue.add(Action::Surrender);
         })
     }
 
     fn layout_go_btn(&mut self) {
+        let enable = self
+            .game_world
+            .borrow()
+            .state()
+            .required_ready
+            .contains(&self.action_queue.player_id());
+        self.layout.call_on_name(
+            Self::GO_BTN_ID,
+            |btn: &mut NamedView<EnableableView<Button>>| {
+                if enable {
+                    btn.get_mut().enable();
+                } else {
+                    btn.get_mut().disable();
+                }
+            },
+        );
+    }
+
+    fn layout_hint_btn(&mut self) {
         let enable = {
             let game_world = self.game_world.borrow();
             let game_state = game_world.state();
-            game_state
-                .required_ready
-                .contains(&self.action_queue.player_id())
-                || Logic::<DefaultActionQueue>::is_game_over(game_state)
+            game_state.phase == Inround
+                && game_state.turn() == self.action_queue.player_id()
+                && self.hint.borrow().get(game_state).is_none()
         };
         self.layout.call_on_name(
-            Self::GO_BTN_ID,
+            Self::HINT_BTN_ID,
             |btn: &mut NamedView<EnableableView<Button>>| {
                 if enable {
                     btn.get_mut().enable();
@@ -585,6 +1445,7 @@ impl View for LocalHumanControlsView {
     fn layout(&mut self, view_size: Vec2) {
         self.layout_go_btn();
         self.layout_surrender_btn();
+        self.layout_hint_btn();
         self.layout.layout(view_size);
     }
 
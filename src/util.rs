@@ -1 +1,3 @@
+pub mod csv;
+pub mod dirs;
 pub mod time;
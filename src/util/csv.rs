@@ -0,0 +1,12 @@
+//! Helpers for writing CSV, see [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180).
+
+mod test;
+
+/// Quotes the `field` if it contains characters special in CSV.
+pub fn field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
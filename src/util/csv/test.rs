@@ -0,0 +1,18 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod field {
+    use pretty_assertions_sorted::assert_eq;
+    use test_case::test_case;
+
+    use crate::util::csv;
+
+    #[test_case("random 1", "random 1")]
+    #[test_case("", "")]
+    #[test_case("a,b", "\"a,b\"")]
+    #[test_case("say \"hi\"", "\"say \"\"hi\"\"\"")]
+    #[test_case("a\nb", "\"a\nb\"")]
+    fn field(field: &str, expected: &str) {
+        assert_eq!(csv::field(field), expected);
+    }
+}
@@ -0,0 +1,37 @@
+//! The directories of the application files as per the
+//! [XDG Base Directory Specification](https://specifications.freedesktop.org/basedir-spec/latest/).
+
+use std::{ffi::OsString, path::PathBuf};
+
+pub const APP_DIR: &str = "tic-tac-toe";
+
+/// Returns `$XDG_CONFIG_HOME/tic-tac-toe`, or `$HOME/.config/tic-tac-toe` if the former is not set.
+/// Environment variables are read via `env`.
+pub fn config_dir(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    app_dir(env, "XDG_CONFIG_HOME", &[".config"])
+}
+
+/// Returns `$XDG_DATA_HOME/tic-tac-toe`, or `$HOME/.local/share/tic-tac-toe` if the former is not set.
+/// Environment variables are read via `env`.
+pub fn data_dir(env: &dyn Fn(&str) -> Option<OsString>) -> Option<PathBuf> {
+    app_dir(env, "XDG_DATA_HOME", &[".local", "share"])
+}
+
+fn app_dir(
+    env: &dyn Fn(&str) -> Option<OsString>,
+    xdg_var: &str,
+    home_relative: &[&str],
+) -> Option<PathBuf> {
+    env(xdg_var)
+        .map(PathBuf::from)
+        // the specification requires ignoring relative paths
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| {
+            env("HOME").filter(|home| !home.is_empty()).map(|home| {
+                let mut dir = PathBuf::from(home);
+                dir.extend(home_relative);
+                dir
+            })
+        })
+        .map(|dir| dir.join(APP_DIR))
+}
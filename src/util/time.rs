@@ -1,6 +1,9 @@
-use core::time::Duration;
+use alloc::rc::Rc;
+use core::{cell::Cell, fmt::Debug, time::Duration};
 use std::time::Instant;
 
+mod test;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Time {
     pub v: Duration,
@@ -12,22 +15,172 @@ impl Time {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+/// A source of [`Time`] for an [`AdvanceableClock`].
+pub trait ClockSource: Debug {
+    /// Must not return a smaller [`Time`] than the one returned previously.
+    fn now(&mut self) -> Time;
+}
+
+/// Measures the real time elapsed since the first invocation of [`ClockSource::now()`].
+#[derive(Debug, Default)]
+pub struct RealClockSource {
+    start: Option<Instant>,
+}
+
+impl RealClockSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ClockSource for RealClockSource {
+    fn now(&mut self) -> Time {
+        Time::new(self.start.get_or_insert_with(Instant::now).elapsed())
+    }
+}
+
+/// Does not change unless advanced explicitly via [`Self::advance()`].
+/// Clones share the time, so a clone may be used to advance the time
+/// of a clock that owns another clone.
+#[derive(Debug, Default, Clone)]
+pub struct ManualClockSource {
+    now: Rc<Cell<Duration>>,
+}
+
+impl ManualClockSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, d: Duration) {
+        self.now.set(self.now.get() + d);
+    }
+}
+
+impl ClockSource for ManualClockSource {
+    fn now(&mut self) -> Time {
+        Time::new(self.now.get())
+    }
+}
+
+/// The scale of a [`ScaledClockSource`].
+/// Clones share the value, so a clone may be used to change the scale
+/// of a clock that owns another clone.
+#[derive(Debug, Clone)]
+pub struct TimeScale {
+    v: Rc<Cell<f64>>,
+    paused: Rc<Cell<bool>>,
+}
+
+impl TimeScale {
+    /// # Panics
+    ///
+    /// If `v` is negative or not finite.
+    pub fn new(v: f64) -> Self {
+        let scale = Self {
+            v: Rc::new(Cell::new(0.0)),
+            paused: Rc::new(Cell::new(false)),
+        };
+        scale.set(v);
+        scale
+    }
+
+    /// Returns 0 if [`Self::is_paused()`], otherwise returns [`Self::unpaused()`].
+    pub fn get(&self) -> f64 {
+        if self.is_paused() {
+            0.0
+        } else {
+            self.unpaused()
+        }
+    }
+
+    /// Returns the scale in effect when not [`Self::is_paused()`].
+    pub fn unpaused(&self) -> f64 {
+        self.v.get()
+    }
+
+    /// A scale of 0 stops the time, a scale greater than 1 speeds it up.
+    /// Does not resume if [`Self::is_paused()`].
+    ///
+    /// # Panics
+    ///
+    /// If `v` is negative or not finite.
+    pub fn set(&self, v: f64) {
+        assert!(v >= 0.0 && v.is_finite(), "{v:?}");
+        self.v.set(v);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    /// Stops the time until resumed, without changing [`Self::unpaused()`].
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.set(paused);
+    }
+}
+
+/// Advances by the time elapsed in the `source` multiplied by the [`TimeScale`]
+/// that was in effect when [`ClockSource::now()`] was invoked.
+#[derive(Debug)]
+pub struct ScaledClockSource {
+    source: Box<dyn ClockSource>,
+    scale: TimeScale,
+    source_last: Option<Time>,
+    now: Time,
+}
+
+impl ScaledClockSource {
+    pub fn new(source: Box<dyn ClockSource>, scale: TimeScale) -> Self {
+        Self {
+            source,
+            scale,
+            source_last: None,
+            now: Time::new(Duration::ZERO),
+        }
+    }
+}
+
+impl ClockSource for ScaledClockSource {
+    fn now(&mut self) -> Time {
+        let source_now = self.source.now();
+        if let Some(source_last) = self.source_last {
+            self.now = Time::new(
+                self.now.v
+                    + source_now
+                        .v
+                        .saturating_sub(source_last.v)
+                        .mul_f64(self.scale.get()),
+            );
+        }
+        self.source_last = Some(source_now);
+        self.now
+    }
+}
+
+/// Holds the [`Time`] as of the last invocation of [`Self::advance()`],
+/// such that all readers see the same time until the clock is advanced again.
+#[derive(Debug)]
 pub struct AdvanceableClock {
-    start: Instant,
+    source: Box<dyn ClockSource>,
     now: Time,
 }
 
 impl AdvanceableClock {
-    pub fn new(start: Instant) -> Self {
+    pub fn new(source: Box<dyn ClockSource>) -> Self {
         Self {
-            start,
-            now: Time::new(Duration::from_nanos(0)),
+            source,
+            now: Time::new(Duration::ZERO),
         }
     }
 
-    pub fn advance_to_real_now(&mut self) {
-        self.now = Time::new(self.start.elapsed());
+    /// Creates a clock with a [`RealClockSource`].
+    pub fn real() -> Self {
+        Self::new(Box::new(RealClockSource::new()))
+    }
+
+    pub fn advance(&mut self) {
+        self.now = self.source.now();
     }
 
     pub fn now(&self) -> Time {
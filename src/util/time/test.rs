@@ -0,0 +1,71 @@
+#![cfg(test)]
+#![allow(non_snake_case)]
+
+mod ScaledClockSource {
+    use core::time::Duration;
+
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::util::time::{
+        AdvanceableClock, ManualClockSource, ScaledClockSource, Time, TimeScale,
+    };
+
+    #[test]
+    fn now() {
+        let source = ManualClockSource::new();
+        let scale = TimeScale::new(2.0);
+        let mut clock = AdvanceableClock::new(Box::new(ScaledClockSource::new(
+            Box::new(source.clone()),
+            scale.clone(),
+        )));
+        clock.advance();
+        let mut advance = |source_d: Duration, scale_v: f64| {
+            scale.set(scale_v);
+            source.advance(source_d);
+            clock.advance();
+            clock.now()
+        };
+        assert_eq!(
+            [
+                advance(Duration::from_secs(1), 2.0),
+                advance(Duration::from_secs(1), 0.0),
+                advance(Duration::from_secs(2), 0.5),
+                advance(Duration::from_secs(1), 1.0),
+            ],
+            [2, 2, 3, 4].map(|secs| Time::new(Duration::from_secs(secs)))
+        );
+    }
+
+    #[test]
+    fn now__paused() {
+        let source = ManualClockSource::new();
+        let scale = TimeScale::new(2.0);
+        let mut clock = AdvanceableClock::new(Box::new(ScaledClockSource::new(
+            Box::new(source.clone()),
+            scale.clone(),
+        )));
+        clock.advance();
+        scale.set_paused(true);
+        source.advance(Duration::from_secs(1));
+        clock.advance();
+        assert_eq!(clock.now(), Time::new(Duration::ZERO));
+        // changing the scale while paused does not resume
+        scale.set(0.5);
+        source.advance(Duration::from_secs(1));
+        clock.advance();
+        assert_eq!((clock.now(), scale.get()), (Time::new(Duration::ZERO), 0.0));
+        scale.set_paused(false);
+        source.advance(Duration::from_secs(2));
+        clock.advance();
+        assert_eq!(
+            (clock.now(), scale.get()),
+            (Time::new(Duration::from_secs(1)), 0.5)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "-1.0")]
+    fn TimeScale__negative() {
+        TimeScale::new(-1.0);
+    }
+}
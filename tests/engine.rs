@@ -0,0 +1,59 @@
+//! Tests the `engine` command as an external engine played by the `simulate` command.
+
+#![allow(non_snake_case)]
+
+use std::{collections::HashMap, process::Command};
+
+use ntest::timeout;
+use pretty_assertions_sorted::assert_eq;
+
+const EXE: &str = env!("CARGO_BIN_EXE_tic-tac-toe");
+
+#[test]
+#[timeout(30_000)]
+fn simulate__engine() {
+    let output = Command::new(EXE)
+        .args([
+            "simulate",
+            "--games",
+            "4",
+            "--rounds",
+            "3",
+            "--seed",
+            "42",
+            "--ai-o",
+            "tablebase",
+            "--engine-x",
+            &format!("'{EXE}' engine --seed 7"),
+            "--engine-time",
+            "200",
+            "--format",
+            "csv",
+        ])
+        // so that no configuration file is read
+        .env_clear()
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let csv = String::from_utf8(output.stdout).unwrap();
+    let mut lines = csv.lines();
+    let report = lines
+        .next()
+        .unwrap()
+        .split(',')
+        .zip(lines.next().unwrap().split(','))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(
+        (
+            report["games"],
+            report["engine_failures_x"],
+            // the engine cannot win against the perfect play
+            report["rounds_won_by_x"],
+        ),
+        ("4", "0", "0")
+    );
+}